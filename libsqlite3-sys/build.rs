@@ -264,6 +264,12 @@ mod build_bundled {
         if cfg!(feature = "unlock_notify") {
             cfg.flag("-DSQLITE_ENABLE_UNLOCK_NOTIFY");
         }
+        if cfg!(feature = "snapshot") {
+            cfg.flag("-DSQLITE_ENABLE_SNAPSHOT");
+        }
+        if cfg!(feature = "normalize") {
+            cfg.flag("-DSQLITE_ENABLE_NORMALIZE");
+        }
         if cfg!(feature = "preupdate_hook") {
             cfg.flag("-DSQLITE_ENABLE_PREUPDATE_HOOK");
         }
@@ -589,6 +595,12 @@ mod bindings {
         if cfg!(feature = "unlock_notify") {
             bindings = bindings.clang_arg("-DSQLITE_ENABLE_UNLOCK_NOTIFY");
         }
+        if cfg!(feature = "snapshot") {
+            bindings = bindings.clang_arg("-DSQLITE_ENABLE_SNAPSHOT");
+        }
+        if cfg!(feature = "normalize") {
+            bindings = bindings.clang_arg("-DSQLITE_ENABLE_NORMALIZE");
+        }
         if cfg!(feature = "preupdate_hook") {
             bindings = bindings.clang_arg("-DSQLITE_ENABLE_PREUPDATE_HOOK");
         }
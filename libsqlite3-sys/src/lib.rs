@@ -27,6 +27,17 @@ mod bindings {
 }
 pub use bindings::*;
 
+// Only declared by `sqlite3.h` when `SQLITE_ENABLE_NORMALIZE` is defined, so
+// missing from the pre-generated bindings.
+#[cfg(all(
+    feature = "normalize",
+    not(feature = "buildtime_bindgen"),
+    not(feature = "loadable_extension")
+))]
+extern "C" {
+    pub fn sqlite3_normalized_sql(pStmt: *mut sqlite3_stmt) -> *const ::std::os::raw::c_char;
+}
+
 impl Default for sqlite3_vtab {
     fn default() -> Self {
         unsafe { mem::zeroed() }
@@ -0,0 +1,198 @@
+use std::mem::ManuallyDrop;
+use std::path::Path;
+
+use crate::{Connection, DatabaseName, Error, Result};
+
+/// RAII guard for an attached database, which is detached when the guard goes
+/// out of scope.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rusqlite::{Connection, Result};
+/// fn stage(conn: &Connection) -> Result<()> {
+///     let scratch = conn.attach_in_memory("scratch")?;
+///     conn.execute_batch(
+///         "CREATE TABLE scratch.import(line TEXT);
+///          INSERT INTO main.log SELECT line FROM scratch.import;",
+///     )?;
+///     scratch.detach()
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AttachGuard<'conn> {
+    conn: &'conn Connection,
+    schema_name: String,
+}
+
+impl AttachGuard<'_> {
+    /// Name of the attached database.
+    #[inline]
+    #[must_use]
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    /// The attached database, for APIs taking a [`DatabaseName`].
+    #[inline]
+    #[must_use]
+    pub fn database_name(&self) -> DatabaseName<'_> {
+        DatabaseName::Attached(&self.schema_name)
+    }
+
+    /// Detach the database now, reporting any error (dropping the guard
+    /// ignores them).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the database is in use, e.g. within a
+    /// transaction. The database then stays attached.
+    #[inline]
+    pub fn detach(self) -> Result<()> {
+        // skip `Drop`, which would detach again, but still free the name
+        let mut guard = ManuallyDrop::new(self);
+        let r = guard.detach_();
+        drop(std::mem::take(&mut guard.schema_name));
+        r
+    }
+
+    #[inline]
+    fn detach_(&self) -> Result<()> {
+        self.conn
+            .execute("DETACH DATABASE ?1", [&self.schema_name])
+            .map(|_| ())
+    }
+}
+
+#[allow(unused_must_use)]
+impl Drop for AttachGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.detach_();
+    }
+}
+
+impl Connection {
+    /// Attach the database file at `path` under `schema_name`, until the
+    /// returned guard is dropped.
+    ///
+    /// See [`ATTACH DATABASE`](https://www.sqlite.org/lang_attach.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `path` is not valid UTF-8, if `schema_name` is
+    /// already in use, or if the database cannot be opened.
+    pub fn attach<P: AsRef<Path>>(&self, path: P, schema_name: &str) -> Result<AttachGuard<'_>> {
+        let path = path.as_ref();
+        let path = path
+            .to_str()
+            .ok_or_else(|| Error::InvalidPath(path.to_owned()))?;
+        self.execute("ATTACH DATABASE ?1 AS ?2", [path, schema_name])?;
+        Ok(AttachGuard {
+            conn: self,
+            schema_name: schema_name.to_owned(),
+        })
+    }
+
+    /// Attach the encrypted database file at `path` under `schema_name`,
+    /// with `key`, until the returned guard is dropped.
+    ///
+    /// The key is given with the `KEY` clause of `ATTACH`, as supported by
+    /// SQLCipher and SEE; a raw key is given as `x'...'` (SQLCipher's
+    /// syntax). See also [`Connection::key`].
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `path` is not valid UTF-8, if `schema_name` is
+    /// already in use, or if the database cannot be opened. Note that a wrong
+    /// key may only be detected when the database is first read.
+    #[cfg(feature = "encryption")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+    pub fn attach_with_key<P: AsRef<Path>>(
+        &self,
+        path: P,
+        schema_name: &str,
+        key: crate::EncryptionKey<'_>,
+    ) -> Result<AttachGuard<'_>> {
+        let path = path.as_ref();
+        let path = path
+            .to_str()
+            .ok_or_else(|| Error::InvalidPath(path.to_owned()))?;
+        self.execute(
+            "ATTACH DATABASE ?1 AS ?2 KEY ?3",
+            [path, schema_name, &key.attach_value()],
+        )?;
+        Ok(AttachGuard {
+            conn: self,
+            schema_name: schema_name.to_owned(),
+        })
+    }
+
+    /// Attach a new, empty, in-memory database under `schema_name`, until the
+    /// returned guard is dropped.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `schema_name` is already in use.
+    #[inline]
+    pub fn attach_in_memory(&self, schema_name: &str) -> Result<AttachGuard<'_>> {
+        self.attach(":memory:", schema_name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Connection, Result};
+
+    fn schemas(db: &Connection) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        db.pragma_query(None, "database_list", |r| {
+            names.push(r.get(1)?);
+            Ok(())
+        })?;
+        Ok(names)
+    }
+
+    #[test]
+    fn test_attach_in_memory() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE items(id INTEGER); INSERT INTO items VALUES (1), (2);")?;
+        {
+            let scratch = db.attach_in_memory("scratch")?;
+            assert_eq!(scratch.schema_name(), "scratch");
+            assert_eq!(schemas(&db)?, ["main", "scratch"]);
+            db.execute_batch(
+                "CREATE TABLE scratch.seen(id INTEGER);
+                 INSERT INTO scratch.seen VALUES (2), (3);",
+            )?;
+            let n: i64 =
+                db.one_column("SELECT COUNT(*) FROM main.items JOIN scratch.seen USING (id)")?;
+            assert_eq!(n, 1);
+            // the name is taken
+            db.attach_in_memory("scratch").unwrap_err();
+        }
+        assert_eq!(schemas(&db)?, ["main"]);
+        db.execute_batch("SELECT * FROM scratch.seen").unwrap_err();
+
+        // a fresh database each time
+        let scratch = db.attach_in_memory("scratch")?;
+        db.execute_batch("CREATE TABLE scratch.seen(id INTEGER)")?;
+        scratch.detach()?;
+        assert_eq!(schemas(&db)?, ["main"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_attach_file() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("other.db3");
+        let db = Connection::open_in_memory()?;
+        let other = db.attach(&path, "other")?;
+        db.execute_batch("CREATE TABLE other.foo(x); INSERT INTO other.foo VALUES (42);")?;
+        other.detach()?;
+
+        let other = Connection::open(&path)?;
+        assert_eq!(42, other.one_column::<i64>("SELECT x FROM foo")?);
+        Ok(())
+    }
+}
@@ -195,6 +195,7 @@ use super::types::{ToSql, ToSqlOutput};
 use crate::{Connection, DatabaseName, Result};
 
 mod pos_io;
+mod stream;
 
 /// Handle to an open BLOB. See
 /// [`rusqlite::blob`](crate::blob) documentation for in-depth discussion.
@@ -209,6 +210,20 @@ impl Connection {
     /// Open a handle to the BLOB located in `row_id`,
     /// `column`, `table` in database `db`.
     ///
+    /// The size of the BLOB is known as soon as it is opened, see
+    /// [`Blob::len`], so there is no need for a separate query:
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, DatabaseName, Result};
+    /// # use std::io::Read;
+    /// fn read_blob(db: &Connection, rowid: i64) -> Result<Vec<u8>> {
+    ///     let mut blob = db.blob_open(DatabaseName::Main, "test", "content", rowid, true)?;
+    ///     let mut buf = Vec::with_capacity(blob.len());
+    ///     blob.read_to_end(&mut buf).unwrap();
+    ///     Ok(buf)
+    /// }
+    /// ```
+    ///
     /// # Failure
     ///
     /// Will return `Err` if `db`/`table`/`column` cannot be converted to a
@@ -264,6 +279,9 @@ impl Blob<'_> {
     }
 
     /// Return the size in bytes of the BLOB.
+    ///
+    /// This does not query the database: SQLite records the size when the
+    /// BLOB is opened (or reopened).
     #[inline]
     #[must_use]
     pub fn size(&self) -> i32 {
@@ -434,6 +452,31 @@ mod test {
         Ok((db, rowid))
     }
 
+    #[test]
+    fn test_blob_len() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE test (content BLOB);
+             INSERT INTO test VALUES (ZEROBLOB(1000)), (x'0102030405'), (x'');",
+        )?;
+
+        let mut blob = db.blob_open(DatabaseName::Main, "test", "content", 1, true)?;
+        assert_eq!(blob.len(), 1000);
+        assert_eq!(blob.size(), 1000);
+        assert!(!blob.is_empty());
+
+        blob.reopen(2)?;
+        assert_eq!(blob.len(), 5);
+        let mut buf = Vec::with_capacity(blob.len());
+        blob.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4, 5]);
+
+        blob.reopen(3)?;
+        assert_eq!(blob.len(), 0);
+        assert!(blob.is_empty());
+        Ok(())
+    }
+
     #[test]
     fn test_blob() -> Result<()> {
         let (db, rowid) = db_with_test_blob()?;
@@ -0,0 +1,221 @@
+use std::io::{self, Read, Write};
+
+use super::ZeroBlob;
+use crate::pragma::Sql;
+use crate::{params, Connection, DatabaseName, Error, Result, Savepoint};
+
+// size of the buffer used to copy data from and to a BLOB
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl Connection {
+    /// Insert a new row into `table` of the main database, with the first
+    /// `len` bytes from `reader` as the content of `column`, and return its
+    /// rowid.
+    ///
+    /// The row is inserted with a zeroblob of `len` bytes, which is then
+    /// filled incrementally from `reader`, so the content is never loaded in
+    /// memory as a whole. The row uses `rowid` if given, or a new rowid
+    /// chosen by SQLite otherwise; the other columns get their default value.
+    ///
+    /// Everything is done within a savepoint, so no row is inserted if this
+    /// fails. Any data in `reader` past `len` bytes is left unread.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// # use std::fs::File;
+    /// fn store_file(conn: &Connection, path: &str) -> Result<i64> {
+    ///     let file = File::open(path).unwrap();
+    ///     let len = file.metadata().unwrap().len() as usize;
+    ///     conn.insert_blob_from_reader("files", "content", None, file, len)
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the row cannot be inserted, if `len` is too large
+    /// for a BLOB, if reading from `reader` fails ([`Error::IoError`]), or if
+    /// `reader` has fewer than `len` bytes ([`Error::BlobSizeError`]).
+    pub fn insert_blob_from_reader<R: Read>(
+        &self,
+        table: &str,
+        column: &str,
+        rowid: Option<i64>,
+        mut reader: R,
+        len: usize,
+    ) -> Result<i64> {
+        let size = i32::try_from(len).map_err(|_| Error::BlobSizeError)?;
+        let mut target = Sql::new();
+        target.push_identifier(table);
+        let mut content = Sql::new();
+        content.push_identifier(column);
+        let sql = format!(
+            "INSERT INTO main.{}(rowid, {}) VALUES (?1, ?2)",
+            target.as_str(),
+            content.as_str()
+        );
+
+        let sp = Savepoint::new_(self)?;
+        sp.execute(&sql, params![rowid, ZeroBlob(size)])?;
+        let rowid = sp.last_insert_rowid();
+        {
+            let mut blob = sp.blob_open(DatabaseName::Main, table, column, rowid, false)?;
+            let mut buf = vec![0u8; CHUNK_SIZE.min(len)];
+            let mut written = 0;
+            while written < len {
+                let n = (len - written).min(buf.len());
+                let n = match reader.read(&mut buf[..n]) {
+                    Ok(0) => return Err(Error::BlobSizeError),
+                    Ok(n) => n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(Error::IoError(e)),
+                };
+                blob.write_at(&buf[..n], written)?;
+                written += n;
+            }
+            blob.close()?;
+        }
+        sp.commit()?;
+        Ok(rowid)
+    }
+
+    /// Copy the BLOB located in `rowid`, `column`, `table` in database `db`
+    /// to `writer`, and return the number of bytes written.
+    ///
+    /// The BLOB is read incrementally, in bounded chunks, so it is never
+    /// loaded in memory as a whole.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, DatabaseName, Result};
+    /// # use std::io::Write;
+    /// fn send_file(conn: &Connection, id: i64, out: impl Write) -> Result<u64> {
+    ///     conn.read_blob_to_writer(DatabaseName::Main, "files", "content", id, out)
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the BLOB cannot be opened or read, or if writing
+    /// to `writer` fails ([`Error::IoError`]).
+    pub fn read_blob_to_writer<W: Write>(
+        &self,
+        db: DatabaseName<'_>,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        mut writer: W,
+    ) -> Result<u64> {
+        let blob = self.blob_open(db, table, column, rowid, true)?;
+        let len = blob.len();
+        let mut buf = vec![0u8; CHUNK_SIZE.min(len)];
+        let mut read = 0;
+        while read < len {
+            let n = (len - read).min(buf.len());
+            blob.read_at_exact(&mut buf[..n], read)?;
+            writer.write_all(&buf[..n]).map_err(Error::IoError)?;
+            read += n;
+        }
+        blob.close()?;
+        Ok(read as u64)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Seek, SeekFrom, Write};
+
+    use crate::{Connection, DatabaseName, Error, Result};
+
+    fn contents(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_insert_blob_from_reader() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE files(name TEXT DEFAULT 'x', content BLOB)")?;
+
+        let len = 3 * 1024 * 1024 + 17;
+        let expected = contents(len);
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&expected).unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+
+        let rowid = db.insert_blob_from_reader("files", "content", None, &mut file, len)?;
+        let content: Vec<u8> =
+            db.query_row("SELECT content FROM files WHERE rowid = ?1", [rowid], |r| {
+                r.get(0)
+            })?;
+        assert!(content == expected);
+        let name: String = db.one_column("SELECT name FROM files")?;
+        assert_eq!("x", name);
+
+        let rowid = db.insert_blob_from_reader("files", "content", Some(42), &b"abc"[..], 2)?;
+        assert_eq!(42, rowid);
+        let blob = db.blob_open(DatabaseName::Main, "files", "content", 42, true)?;
+        assert_eq!(2, blob.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_blob_from_short_reader() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE files(content BLOB)")?;
+        let data = contents(1000);
+        assert_eq!(
+            Some(Error::BlobSizeError),
+            db.insert_blob_from_reader("files", "content", None, &data[..], 1001)
+                .err()
+        );
+        // nothing was inserted
+        let n: i64 = db.one_column("SELECT count(*) FROM files")?;
+        assert_eq!(0, n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_blob_from_failing_reader() -> Result<()> {
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("boom"))
+            }
+        }
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE files(content BLOB)")?;
+        match db.insert_blob_from_reader("files", "content", None, Failing, 10) {
+            Err(Error::IoError(e)) => assert_eq!("boom", e.to_string()),
+            r => panic!("unexpected result {r:?}"),
+        }
+        let n: i64 = db.one_column("SELECT count(*) FROM files")?;
+        assert_eq!(0, n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_blob_to_writer() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE files(content BLOB)")?;
+        let expected = contents(200 * 1024 + 3);
+        db.execute("INSERT INTO files VALUES (?1), (x'')", [&expected])?;
+
+        let mut out = Vec::new();
+        let n = db.read_blob_to_writer(DatabaseName::Main, "files", "content", 1, &mut out)?;
+        assert_eq!(expected.len() as u64, n);
+        assert!(out == expected);
+
+        let mut out = Vec::new();
+        let n = db.read_blob_to_writer(DatabaseName::Main, "files", "content", 2, &mut out)?;
+        assert_eq!(0, n);
+        assert!(out.is_empty());
+
+        let mut out = [0u8; 10];
+        assert!(matches!(
+            db.read_blob_to_writer(DatabaseName::Main, "files", "content", 1, &mut out[..]),
+            Err(Error::IoError(..))
+        ));
+        db.read_blob_to_writer(DatabaseName::Main, "files", "content", 3, Vec::new())
+            .unwrap_err();
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Error, Result, Rows, Statement, ToSql};
+
+/// A prepared statement with some parameters pinned to a constant value.
+///
+/// Pinned parameters are bound once, by [`BoundStatement::pin`]; the values
+/// given to [`execute`](BoundStatement::execute) or
+/// [`query`](BoundStatement::query) are bound, in order, to the remaining
+/// parameters only.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rusqlite::{BoundStatement, Connection, Result};
+/// fn tag_all(conn: &Connection, tenant_id: i64, ids: &[i64]) -> Result<()> {
+///     let mut stmt =
+///         conn.prepare("UPDATE doc SET tagged = 1 WHERE tenant_id = ?1 AND id = ?2")?;
+///     let mut stmt = BoundStatement::new(&mut stmt);
+///     stmt.pin(1, tenant_id)?;
+///     for id in ids {
+///         stmt.execute([id])?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BoundStatement<'stmt, 'conn> {
+    stmt: &'stmt mut Statement<'conn>,
+    // indexed by zero-based parameter index
+    pinned: Vec<bool>,
+}
+
+impl<'stmt, 'conn> BoundStatement<'stmt, 'conn> {
+    /// Wrap `stmt`, with no parameter pinned yet.
+    #[inline]
+    pub fn new(stmt: &'stmt mut Statement<'conn>) -> BoundStatement<'stmt, 'conn> {
+        let pinned = vec![false; stmt.parameter_count()];
+        BoundStatement { stmt, pinned }
+    }
+
+    /// Bind `value` to the parameter at `one_based_index` for all subsequent
+    /// executions. Pinning an already pinned parameter replaces its value.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `one_based_index` is out of range or if `value`
+    /// cannot be converted.
+    pub fn pin<T: ToSql>(&mut self, one_based_index: usize, value: T) -> Result<()> {
+        self.stmt.raw_bind_parameter(one_based_index, value)?;
+        self.pinned[one_based_index - 1] = true;
+        Ok(())
+    }
+
+    /// Like [`BoundStatement::pin`], for the parameter named `name` (e.g.
+    /// `:tenant_id`).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if there is no parameter named `name` or if `value`
+    /// cannot be converted.
+    pub fn pin_named<T: ToSql>(&mut self, name: &str, value: T) -> Result<()> {
+        match self.stmt.parameter_index(name)? {
+            Some(i) => self.pin(i, value),
+            None => Err(Error::InvalidParameterName(name.into())),
+        }
+    }
+
+    /// Number of parameters not pinned, i.e. expected by
+    /// [`execute`](BoundStatement::execute) and
+    /// [`query`](BoundStatement::query).
+    #[inline]
+    #[must_use]
+    pub fn unpinned_count(&self) -> usize {
+        self.pinned.iter().filter(|p| !**p).count()
+    }
+
+    /// Bind `params` to the parameters that are not pinned, then execute the
+    /// statement. See [`Statement::execute`].
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the number of `params` differs from
+    /// [`unpinned_count`](BoundStatement::unpinned_count), if binding fails,
+    /// or if the statement fails.
+    pub fn execute<P>(&mut self, params: P) -> Result<usize>
+    where
+        P: IntoIterator,
+        P::Item: ToSql,
+    {
+        self.bind_unpinned(params)?;
+        self.stmt.raw_execute()
+    }
+
+    /// Bind `params` to the parameters that are not pinned, then run the
+    /// query. See [`Statement::query`].
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the number of `params` differs from
+    /// [`unpinned_count`](BoundStatement::unpinned_count) or if binding fails.
+    pub fn query<P>(&mut self, params: P) -> Result<Rows<'_>>
+    where
+        P: IntoIterator,
+        P::Item: ToSql,
+    {
+        self.bind_unpinned(params)?;
+        Ok(self.stmt.raw_query())
+    }
+
+    /// Access the underlying statement.
+    #[inline]
+    #[must_use]
+    pub fn statement(&self) -> &Statement<'conn> {
+        self.stmt
+    }
+
+    fn bind_unpinned<P>(&mut self, params: P) -> Result<()>
+    where
+        P: IntoIterator,
+        P::Item: ToSql,
+    {
+        let expected = self.unpinned_count();
+        let mut free = (1..=self.pinned.len()).filter(|&i| !self.pinned[i - 1]);
+        let mut given = 0;
+        for p in params {
+            given += 1;
+            match free.next() {
+                Some(i) => self.stmt.raw_bind_parameter(i, p)?,
+                None => return Err(Error::InvalidParameterCount(given, expected)),
+            }
+        }
+        if given != expected {
+            return Err(Error::InvalidParameterCount(given, expected));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BoundStatement;
+    use crate::{Connection, Error, Result};
+
+    #[test]
+    fn test_pinned_parameter() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE doc(tenant_id INTEGER, name TEXT);
+             INSERT INTO doc VALUES (1, 'a'), (1, 'b'), (2, 'c');",
+        )?;
+        let mut stmt = db.prepare("SELECT count(*) FROM doc WHERE tenant_id = ?1 AND name = ?2")?;
+        let mut stmt = BoundStatement::new(&mut stmt);
+        stmt.pin(1, 1)?;
+        assert_eq!(1, stmt.unpinned_count());
+        for (name, expected) in [("a", 1), ("b", 1), ("c", 0)] {
+            let mut rows = stmt.query([name])?;
+            assert_eq!(expected, rows.next()?.unwrap().get::<_, i64>(0)?);
+        }
+
+        assert_eq!(
+            Some(Error::InvalidParameterCount(2, 1)),
+            stmt.query([1, 2]).err()
+        );
+        stmt.pin(3, 0).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_pinned_named_parameter() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE doc(tenant_id INTEGER, name TEXT)")?;
+        let mut stmt = db.prepare("INSERT INTO doc VALUES (:tenant_id, :name)")?;
+        let mut stmt = BoundStatement::new(&mut stmt);
+        stmt.pin_named(":tenant_id", 7)?;
+        for name in ["a", "b", "c"] {
+            assert_eq!(1, stmt.execute([name])?);
+        }
+        assert_eq!(
+            Some(Error::InvalidParameterName(":id".to_owned())),
+            stmt.pin_named(":id", 0).err()
+        );
+
+        let n: i64 = db.one_column("SELECT count(*) FROM doc WHERE tenant_id = 7")?;
+        assert_eq!(3, n);
+        Ok(())
+    }
+}
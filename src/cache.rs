@@ -1,7 +1,7 @@
 //! Prepared statements cache for faster execution.
 
 use crate::raw_statement::RawStatement;
-use crate::{Connection, PrepFlags, Result, Statement};
+use crate::{Connection, Params, PrepFlags, Result, Row, Statement};
 use hashlink::LruCache;
 use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
@@ -39,6 +39,37 @@ impl Connection {
         self.cache.get(self, sql)
     }
 
+    /// Run the query `sql`, prepared with
+    /// [`prepare_cached`](Connection::prepare_cached), and collect its rows
+    /// mapped with `f`.
+    ///
+    /// Repeated calls with the same `sql` reuse the same prepared statement,
+    /// which is reset and has its bindings cleared before it goes back to the
+    /// cache.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn names(conn: &Connection, team_id: i64) -> Result<Vec<String>> {
+    ///     conn.cached_query("SELECT name FROM person WHERE team_id = ?1", [team_id], |row| {
+    ///         row.get(0)
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible string,
+    /// if the underlying SQLite call fails, or if `f` fails.
+    pub fn cached_query<T, P, F>(&self, sql: &str, params: P, f: F) -> Result<Vec<T>>
+    where
+        P: Params,
+        F: FnMut(&Row<'_>) -> Result<T>,
+    {
+        let mut stmt = self.prepare_cached(sql)?;
+        let rows = stmt.query_map(params, f)?;
+        rows.collect()
+    }
+
     /// Set the maximum number of cached prepared statements this connection
     /// will hold. By default, a connection will hold a relatively small
     /// number of cached statements. If you need more, or know that you
@@ -143,8 +174,16 @@ impl StatementCache {
         let trimmed = sql.trim();
         let mut cache = self.0.borrow_mut();
         let stmt = match cache.remove(trimmed) {
-            Some(raw_stmt) => Ok(Statement::new(conn, raw_stmt)),
-            None => conn.prepare_with_flags(trimmed, PrepFlags::SQLITE_PREPARE_PERSISTENT),
+            Some(raw_stmt) => {
+                #[cfg(feature = "metrics")]
+                conn.record_metrics(|m| m.cache_hits += 1);
+                Ok(Statement::new(conn, raw_stmt))
+            }
+            None => {
+                #[cfg(feature = "metrics")]
+                conn.record_metrics(|m| m.cache_misses += 1);
+                conn.prepare_with_flags(trimmed, PrepFlags::SQLITE_PREPARE_PERSISTENT)
+            }
         };
         stmt.map(|mut stmt| {
             stmt.stmt.set_statement_cache_key(trimmed);
@@ -225,6 +264,36 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_cached_query() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (1), (2), (3);")?;
+        let cache = &db.cache;
+        let sql = "SELECT x FROM foo WHERE x >= ?1 ORDER BY x";
+
+        let xs: Vec<i64> = db.cached_query(sql, [2], |r| r.get(0))?;
+        assert_eq!(vec![2, 3], xs);
+        assert_eq!(1, cache.len());
+        let xs: Vec<i64> = db.cached_query(sql, [3], |r| r.get(0))?;
+        assert_eq!(vec![3], xs);
+        assert_eq!(1, cache.len());
+
+        // bindings were cleared: `x >= NULL` matches nothing
+        {
+            let mut stmt = db.prepare_cached(sql)?;
+            assert_eq!(0, cache.len());
+            let mut rows = stmt.raw_query();
+            assert!(rows.next()?.is_none());
+        }
+        assert_eq!(1, cache.len());
+
+        // a failed query still returns the statement to the cache
+        db.cached_query(sql, [1], |r| r.get::<_, String>(0))
+            .unwrap_err();
+        assert_eq!(1, cache.len());
+        Ok(())
+    }
+
     #[test]
     fn test_set_capacity() -> Result<()> {
         let db = Connection::open_in_memory()?;
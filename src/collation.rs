@@ -6,7 +6,17 @@ use std::ptr;
 use std::slice;
 
 use crate::ffi;
-use crate::{str_to_cstring, Connection, InnerConnection, Result};
+use crate::{str_to_cstring, Connection, Error, InnerConnection, Result};
+
+pub(crate) type BoxedCollationNeeded =
+    Box<CollationNeeded<dyn FnMut(&Connection, &str) -> Result<()> + Send + 'static>>;
+
+/// The collation needed callback, and the last error it returned, to be
+/// reported by the statement preparation which triggered it.
+pub(crate) struct CollationNeeded<F: ?Sized> {
+    error: Option<Error>,
+    callback: F,
+}
 
 // FIXME copy/paste from function.rs
 unsafe extern "C" fn free_boxed_value<T>(p: *mut c_void) {
@@ -25,12 +35,23 @@ impl Connection {
             .create_collation(collation_name, x_compare)
     }
 
-    /// Collation needed callback
+    /// Register a callback invoked when a statement requires a collation
+    /// that is not defined yet, so that it can be registered on demand (with
+    /// [`Connection::create_collation`]).
+    ///
+    /// The callback receives the connection and the name of the missing
+    /// collation. Passing `None` removes the callback.
+    ///
+    /// If the callback returns an error (or panics), preparing the statement
+    /// which needed the collation fails with this error instead of "no such
+    /// collation sequence".
+    ///
+    /// See [`sqlite3_collation_needed`](https://www.sqlite.org/c3ref/collation_needed.html).
     #[inline]
-    pub fn collation_needed(
-        &self,
-        x_coll_needed: fn(&Connection, &str) -> Result<()>,
-    ) -> Result<()> {
+    pub fn collation_needed<F>(&self, x_coll_needed: Option<F>) -> Result<()>
+    where
+        F: FnMut(&Connection, &str) -> Result<()> + Send + 'static,
+    {
         self.db.borrow_mut().collation_needed(x_coll_needed)
     }
 
@@ -127,47 +148,77 @@ impl InnerConnection {
         res
     }
 
-    fn collation_needed(
-        &mut self,
-        x_coll_needed: fn(&Connection, &str) -> Result<()>,
-    ) -> Result<()> {
-        use std::mem;
-        #[allow(clippy::needless_return)]
-        unsafe extern "C" fn collation_needed_callback(
+    fn collation_needed<F>(&mut self, x_coll_needed: Option<F>) -> Result<()>
+    where
+        F: FnMut(&Connection, &str) -> Result<()> + Send + 'static,
+    {
+        unsafe extern "C" fn collation_needed_callback<F>(
             arg1: *mut c_void,
             arg2: *mut ffi::sqlite3,
             e_text_rep: c_int,
             arg3: *const c_char,
-        ) {
+        ) where
+            F: FnMut(&Connection, &str) -> Result<()>,
+        {
             use std::ffi::CStr;
-            use std::str;
 
             if e_text_rep != ffi::SQLITE_UTF8 {
                 // TODO: validate
                 return;
             }
 
-            let callback: fn(&Connection, &str) -> Result<()> = mem::transmute(arg1);
-            let res = catch_unwind(|| {
+            let r = catch_unwind(|| {
+                let boxed: *mut CollationNeeded<F> = arg1.cast::<CollationNeeded<F>>();
                 let conn = Connection::from_handle(arg2).unwrap();
                 let collation_name = CStr::from_ptr(arg3)
                     .to_str()
                     .expect("illegal collation sequence name");
-                callback(&conn, collation_name)
+                ((*boxed).callback)(&conn, collation_name)
             });
-            if res.is_err() {
-                return; // FIXME How ?
-            }
+            (*arg1.cast::<CollationNeeded<F>>()).error = match r {
+                Ok(r) => r.err(),
+                Err(_) => Some(Error::UnwindingPanic),
+            };
         }
 
-        let r = unsafe {
-            ffi::sqlite3_collation_needed(
-                self.db(),
-                x_coll_needed as *mut c_void,
-                Some(collation_needed_callback),
-            )
-        };
-        self.decode_result(r)
+        if let Some(x_coll_needed) = x_coll_needed {
+            let mut boxed = Box::new(CollationNeeded {
+                error: None,
+                callback: x_coll_needed,
+            });
+            let r = unsafe {
+                ffi::sqlite3_collation_needed(
+                    self.db(),
+                    &mut *boxed as *mut CollationNeeded<F> as *mut _,
+                    Some(collation_needed_callback::<F>),
+                )
+            };
+            self.decode_result(r)?;
+            self.collation_needed = Some(boxed);
+            Ok(())
+        } else {
+            self.remove_collation_needed()
+        }
+    }
+
+    /// Take the error returned by the collation needed callback since the
+    /// last call, if any.
+    #[inline]
+    pub(crate) fn take_collation_needed_error(&mut self) -> Option<Error> {
+        self.collation_needed.as_mut().and_then(|c| c.error.take())
+    }
+
+    /// Unregister the collation needed callback, if any. Only callbacks set
+    /// through this `InnerConnection` are unregistered, as the same handle
+    /// may be shared (see [`Connection::from_handle`]).
+    pub(crate) fn remove_collation_needed(&mut self) -> Result<()> {
+        if self.collation_needed.is_none() {
+            return Ok(());
+        }
+        let r = unsafe { ffi::sqlite3_collation_needed(self.db(), ptr::null_mut(), None) };
+        self.decode_result(r)?;
+        self.collation_needed = None;
+        Ok(())
     }
 
     #[inline]
@@ -189,7 +240,7 @@ impl InnerConnection {
 
 #[cfg(test)]
 mod test {
-    use crate::{Connection, Result};
+    use crate::{ffi, Connection, Error, Result};
     use fallible_streaming_iterator::FallibleStreamingIterator;
     use std::cmp::Ordering;
     use unicase::UniCase;
@@ -230,7 +281,62 @@ mod test {
     #[test]
     fn test_collation_needed() -> Result<()> {
         let db = Connection::open_in_memory()?;
-        db.collation_needed(collation_needed)?;
+        db.collation_needed(Some(collation_needed))?;
         collate(db)
     }
+
+    #[test]
+    fn test_collation_needed_closure() -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+        use std::sync::Arc;
+
+        let db = Connection::open_in_memory()?;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        db.collation_needed(Some(move |db: &Connection, name: &str| {
+            counter.fetch_add(1, AtomicOrdering::SeqCst);
+            if name == "locale_xx" {
+                db.create_collation(name, unicase_compare)
+            } else {
+                Ok(())
+            }
+        }))?;
+        let n: i64 = db.query_row(
+            "WITH t(x) AS (VALUES ('a'), ('A'), ('b')) SELECT COUNT(DISTINCT x COLLATE locale_xx) FROM t",
+            [],
+            |r| r.get(0),
+        )?;
+        assert_eq!(n, 2);
+        // already registered: the callback is not invoked again
+        db.query_row("SELECT 'a' = 'A' COLLATE locale_xx", [], |_| Ok(()))?;
+        assert_eq!(calls.load(AtomicOrdering::SeqCst), 1);
+
+        db.collation_needed(None::<fn(&Connection, &str) -> Result<()>>)?;
+        db.query_row("SELECT 'a' = 'A' COLLATE other", [], |_| Ok(()))
+            .unwrap_err();
+        assert_eq!(calls.load(AtomicOrdering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collation_needed_error() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.collation_needed(Some(|_: &Connection, name: &str| {
+            Err(Error::SqliteFailure(
+                ffi::Error::new(ffi::SQLITE_ERROR),
+                Some(format!("unknown locale {name}")),
+            ))
+        }))?;
+        assert_eq!(
+            db.prepare("SELECT 'a' = 'A' COLLATE locale_xx").err(),
+            Some(Error::SqliteFailure(
+                ffi::Error::new(ffi::SQLITE_ERROR),
+                Some("unknown locale locale_xx".to_owned())
+            ))
+        );
+        // not reported again by an unrelated failure
+        let err = db.prepare("SELECT * FROM missing").unwrap_err();
+        assert!(err.to_string().contains("no such table"), "{err}");
+        Ok(())
+    }
 }
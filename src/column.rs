@@ -119,26 +119,40 @@ impl Statement<'_> {
     /// sure that current statement has already been stepped once before
     /// calling this method.
     ///
+    /// Column names are compared ASCII case-insensitively. If several columns
+    /// have this name (e.g. with `SELECT *` over a join), the index of the
+    /// first one is returned: use [`Statement::column_indices`] to find the
+    /// others.
+    ///
     /// # Failure
     ///
     /// Will return an `Error::InvalidColumnName` when there is no column with
     /// the specified `name`.
     #[inline]
     pub fn column_index(&self, name: &str) -> Result<usize> {
-        let bytes = name.as_bytes();
-        let n = self.column_count();
-        for i in 0..n {
-            // Note: `column_name` is only fallible if `i` is out of bounds,
-            // which we've already checked.
-            if bytes.eq_ignore_ascii_case(self.stmt.column_name(i).unwrap().to_bytes()) {
-                return Ok(i);
-            }
-        }
-        Err(Error::InvalidColumnName(String::from(name)))
+        // Memoized by name, so repeated lookups (e.g. `row.get("name")` in a
+        // loop) don't scan all columns.
+        self.stmt
+            .column_index(name)
+            .ok_or_else(|| Error::InvalidColumnName(String::from(name)))
+    }
+
+    /// Returns the indices, in order, of all the columns of the result set
+    /// named `name` (ASCII case-insensitively), for result sets with
+    /// duplicate column names. The vector is empty if there is no such
+    /// column.
+    #[must_use]
+    pub fn column_indices(&self, name: &str) -> Vec<usize> {
+        (0..self.column_count())
+            .filter(|&i| self.stmt.column_has_name(i, name))
+            .collect()
     }
 
     /// Returns a slice describing the columns of the result of the query.
     ///
+    /// There is one [`Column`] per result column, in order, so the returned
+    /// vector has [`column_count`](Statement::column_count) elements.
+    ///
     /// If associated DB schema can be altered concurrently, you should make
     /// sure that current statement has already been stepped once before
     /// calling this method.
@@ -164,6 +178,21 @@ impl Statement<'_> {
 mod test {
     use crate::{Connection, Result};
 
+    #[test]
+    fn test_column_index_cached() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let stmt = db.prepare("SELECT 1 AS a, 2 AS b, 3 AS c")?;
+        assert_eq!(0, stmt.stmt.column_index_scans());
+        assert_eq!(2, stmt.column_index("c")?);
+        assert_eq!(1, stmt.stmt.column_index_scans());
+        // the second lookup is a cache hit, not a new scan
+        assert_eq!(2, stmt.column_index("c")?);
+        assert_eq!(1, stmt.stmt.column_index_scans());
+        assert_eq!(1, stmt.column_index("b")?);
+        assert_eq!(2, stmt.stmt.column_index_scans());
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "column_decltype")]
     fn test_columns() -> Result<()> {
@@ -192,6 +221,22 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "column_decltype")]
+    fn test_columns_match_column_names() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, y TEXT)")?;
+        let query = db.prepare("SELECT x, y AS z, x + 1 FROM foo")?;
+        let columns = query.columns();
+        assert_eq!(query.column_count(), columns.len());
+        for (i, col) in columns.iter().enumerate() {
+            assert_eq!(query.column_name(i)?, col.name());
+        }
+        assert_eq!(Some("TEXT"), columns[1].decl_type());
+        assert_eq!(None, columns[2].decl_type());
+        Ok(())
+    }
+
     #[test]
     fn test_column_name_in_error() -> Result<()> {
         use crate::{types::Type, Error};
@@ -248,4 +293,45 @@ mod test {
         assert_eq!(same_column_name, column_name);
         Ok(())
     }
+
+    #[test]
+    fn test_column_indices() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE person(id INTEGER PRIMARY KEY, name TEXT, parent_id INTEGER);
+             INSERT INTO person VALUES (1, 'parent', NULL), (2, 'child', 1);",
+        )?;
+        let mut stmt =
+            db.prepare("SELECT * FROM person AS c JOIN person AS p ON c.parent_id = p.id")?;
+        assert_eq!(vec![1, 4], stmt.column_indices("name"));
+        assert_eq!(vec![0, 3], stmt.column_indices("ID"));
+        assert!(stmt.column_indices("missing").is_empty());
+        // named access uses the first matching column
+        assert_eq!(1, stmt.column_index("name")?);
+        let (child, parent) = stmt.query_row([], |r| {
+            Ok((r.get::<_, String>("name")?, r.get::<_, String>(4)?))
+        })?;
+        assert_eq!(("child".to_owned(), "parent".to_owned()), (child, parent));
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_index_after_schema_change() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo (a, b); INSERT INTO foo VALUES (1, 2);")?;
+        let mut stmt = db.prepare("SELECT * FROM foo")?;
+        for _ in 0..2 {
+            let (b, a) =
+                stmt.query_row([], |r| Ok((r.get::<_, i64>("b")?, r.get::<_, i64>("A")?)))?;
+            assert_eq!((a, b), (1, 2));
+        }
+        // the statement is recompiled with the new columns on next step
+        db.execute_batch(
+            "DROP TABLE foo; CREATE TABLE foo (c, b, a); INSERT INTO foo VALUES (3, 4, 5);",
+        )?;
+        let (b, a) = stmt.query_row([], |r| Ok((r.get::<_, i64>("b")?, r.get::<_, i64>("A")?)))?;
+        assert_eq!((a, b), (5, 4));
+        assert_eq!(stmt.column_index("c")?, 0);
+        Ok(())
+    }
 }
@@ -0,0 +1,124 @@
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use crate::{ffi, str_to_cstring, Connection, DatabaseName, Result};
+
+/// Schema information about a table column, as returned by
+/// [`Connection::column_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ColumnMetadata {
+    /// Declared type of the column, `None` if it has none.
+    pub decl_type: Option<String>,
+    /// Name of the default collation sequence of the column.
+    pub collation: Option<String>,
+    /// Whether the column has a `NOT NULL` constraint.
+    pub not_null: bool,
+    /// Whether the column is part of the primary key.
+    pub primary_key: bool,
+    /// Whether the column is an `AUTOINCREMENT` primary key.
+    pub auto_increment: bool,
+}
+
+impl Connection {
+    /// Describe `column` of `table`, in database `db` or in the first
+    /// database containing `table` if `None`.
+    ///
+    /// Unlike [`Statement::columns`](crate::Statement::columns), this reads
+    /// the schema directly, without preparing a query. `column` can also be
+    /// one of the `rowid` aliases.
+    ///
+    /// Requires SQLite to be compiled with `SQLITE_ENABLE_COLUMN_METADATA`
+    /// (as the bundled SQLite is).
+    ///
+    /// See [`sqlite3_table_column_metadata`](https://www.sqlite.org/c3ref/table_column_metadata.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `table` or `column` does not exist.
+    pub fn column_metadata(
+        &self,
+        db: Option<DatabaseName<'_>>,
+        table: &str,
+        column: &str,
+    ) -> Result<ColumnMetadata> {
+        let db = db.map(|db| db.as_cstring()).transpose()?;
+        let table = str_to_cstring(table)?;
+        let column = str_to_cstring(column)?;
+        let mut decl_type: *const c_char = ptr::null();
+        let mut collation: *const c_char = ptr::null();
+        let mut not_null: c_int = 0;
+        let mut primary_key: c_int = 0;
+        let mut auto_increment: c_int = 0;
+        let r = unsafe {
+            ffi::sqlite3_table_column_metadata(
+                self.handle(),
+                db.as_ref().map_or(ptr::null(), |db| db.as_ptr()),
+                table.as_ptr(),
+                column.as_ptr(),
+                &mut decl_type,
+                &mut collation,
+                &mut not_null,
+                &mut primary_key,
+                &mut auto_increment,
+            )
+        };
+        self.decode_result(r)?;
+        // the strings are only valid until the next call on this connection
+        let to_string = |s: *const c_char| {
+            if s.is_null() {
+                None
+            } else {
+                Some(unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+            }
+        };
+        Ok(ColumnMetadata {
+            decl_type: to_string(decl_type),
+            collation: to_string(collation),
+            not_null: not_null != 0,
+            primary_key: primary_key != 0,
+            auto_increment: auto_increment != 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColumnMetadata;
+    use crate::{Connection, DatabaseName, Result};
+
+    #[test]
+    fn test_column_metadata() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(
+                id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+                name TEXT COLLATE NOCASE,
+                data
+            )",
+        )?;
+
+        let id = db.column_metadata(None, "foo", "id")?;
+        assert_eq!(
+            ColumnMetadata {
+                decl_type: Some("INTEGER".to_owned()),
+                collation: Some("BINARY".to_owned()),
+                not_null: true,
+                primary_key: true,
+                auto_increment: true,
+            },
+            id
+        );
+        let name = db.column_metadata(Some(DatabaseName::Main), "foo", "name")?;
+        assert_eq!(Some("TEXT"), name.decl_type.as_deref());
+        assert_eq!(Some("NOCASE"), name.collation.as_deref());
+        assert!(!name.not_null && !name.primary_key && !name.auto_increment);
+        let data = db.column_metadata(None, "foo", "data")?;
+        assert_eq!(None, data.decl_type);
+
+        db.column_metadata(None, "foo", "missing").unwrap_err();
+        db.column_metadata(None, "missing", "id").unwrap_err();
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! Configure database connections
 
+use std::ffi::CString;
 use std::os::raw::c_int;
 
 use crate::error::check;
@@ -71,6 +72,69 @@ pub enum DbConfig {
     SQLITE_DBCONFIG_REVERSE_SCANORDER = 1019, // 3.42.0
 }
 
+/// Threading mode, cf [Using SQLite In Multi-Threaded Applications](https://sqlite.org/threadsafe.html).
+///
+/// The single-thread mode is not supported: [`Connection`] is `Send`, so
+/// connections opened on different threads must not share unsynchronized
+/// global state (and [`Connection::open`] refuses to open a connection in
+/// this mode).
+///
+/// Under both modes, a `Connection` is `Send` but not `Sync`: it can be moved
+/// to another thread but not used by two threads at the same time. So the
+/// serialized mode only adds the cost of per-connection mutexes (which are
+/// still needed if raw handles obtained with [`Connection::handle`] are shared
+/// across threads).
+#[cfg(not(feature = "loadable_extension"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ThreadingMode {
+    /// `SQLITE_CONFIG_MULTITHREAD`: no mutex on database connections and
+    /// prepared statements.
+    MultiThread,
+    /// `SQLITE_CONFIG_SERIALIZED`: all mutexes enabled.
+    Serialized,
+}
+
+/// How `NaN` floating-point parameters are bound, see
+/// [`Connection::set_nan_policy`].
+///
+/// Infinities are not affected: SQLite stores `f64::INFINITY` and
+/// `f64::NEG_INFINITY` as such, and they are read back unchanged.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NanPolicy {
+    /// Bind `NaN` as `NULL`, which is what SQLite does with a `NaN` double.
+    #[default]
+    Null,
+    /// Refuse to bind `NaN`, with an [`Error::ToSqlConversionFailure`](crate::Error::ToSqlConversionFailure).
+    Error,
+}
+
+/// Select the process-wide threading mode.
+///
+/// It must be called before SQLite is initialized, that is before the first
+/// connection is opened.
+///
+/// # Safety
+///
+/// The function is not threadsafe: no other SQLite calls may be made while
+/// `config_threading_mode` is running, and multiple threads may not call it
+/// simultaneously.
+///
+/// # Failure
+///
+/// Will return `Err` with `SQLITE_MISUSE` if SQLite is already initialized, or
+/// with `SQLITE_ERROR` if SQLite was compiled in single-thread mode
+/// (`SQLITE_THREADSAFE=0`).
+#[cfg(not(feature = "loadable_extension"))]
+pub unsafe fn config_threading_mode(mode: ThreadingMode) -> Result<()> {
+    let op = match mode {
+        ThreadingMode::MultiThread => ffi::SQLITE_CONFIG_MULTITHREAD,
+        ThreadingMode::Serialized => ffi::SQLITE_CONFIG_SERIALIZED,
+    };
+    check(ffi::sqlite3_config(op))
+}
+
 impl Connection {
     /// Returns the current value of a `config`.
     ///
@@ -86,6 +150,14 @@ impl Connection {
     ///   whether the QPSG is disabled or enabled
     /// - `SQLITE_DBCONFIG_TRIGGER_EQP`: return `false` to indicate
     ///   output-for-trigger are not disabled or `true` if it is
+    /// - `SQLITE_DBCONFIG_RESET_DATABASE`: return `true` if the "reset" flag
+    ///   is set
+    /// - `SQLITE_DBCONFIG_DEFENSIVE`: return `true` if the "defensive" flag is
+    ///   set
+    /// - `SQLITE_DBCONFIG_WRITABLE_SCHEMA`: return `true` if the schema tables
+    ///   are writable
+    /// - `SQLITE_DBCONFIG_TRUSTED_SCHEMA`: return `false` if the schema is not
+    ///   trusted
     #[inline]
     pub fn db_config(&self, config: DbConfig) -> Result<bool> {
         let c = self.db.borrow();
@@ -115,6 +187,18 @@ impl Connection {
     ///   enable QPSG
     /// - `SQLITE_DBCONFIG_TRIGGER_EQP`: `false` to disable output for trigger
     ///   programs, `true` to enable it
+    /// - `SQLITE_DBCONFIG_RESET_DATABASE`: `true` before running `VACUUM` to
+    ///   reset the database to empty, then `false`
+    /// - `SQLITE_DBCONFIG_DEFENSIVE`: `true` to disable the language features
+    ///   that allow ordinary SQL to deliberately corrupt the database file
+    ///   (e.g. writes to the schema tables, or to the shadow tables of virtual
+    ///   tables), which is recommended when opening untrusted databases
+    /// - `SQLITE_DBCONFIG_WRITABLE_SCHEMA`: `true` to allow writes to the
+    ///   schema tables (like `PRAGMA writable_schema=ON`), `false` to disallow
+    ///   them
+    /// - `SQLITE_DBCONFIG_TRUSTED_SCHEMA`: `false` to prevent SQL functions
+    ///   and virtual tables not marked as innocuous from being used by views,
+    ///   triggers, `CHECK` constraints, ... of a potentially malicious schema
     #[inline]
     pub fn set_db_config(&self, config: DbConfig, new_val: bool) -> Result<bool> {
         let c = self.db.borrow_mut();
@@ -129,12 +213,121 @@ impl Connection {
             Ok(val != 0)
         }
     }
+
+    /// Allow or forbid double-quoted string literals (`"text"`) in DML
+    /// statements (`SELECT`, `INSERT`, ...) and in DDL statements (`CREATE
+    /// TABLE`, `CREATE INDEX`, ...).
+    ///
+    /// SQLite historically takes a double-quoted identifier which does not
+    /// match any column as a string literal, so a misspelled column name is
+    /// silently turned into a constant. Disabling both makes such queries
+    /// fail to prepare instead. See
+    /// [the quirk](https://sqlite.org/quirks.html#dblquote) (SQLite 3.29.0 or
+    /// later).
+    ///
+    /// Shorthand for [`Connection::set_db_config`] with
+    /// [`DbConfig::SQLITE_DBCONFIG_DQS_DML`] and
+    /// [`DbConfig::SQLITE_DBCONFIG_DQS_DDL`].
+    #[cfg(feature = "modern_sqlite")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "modern_sqlite")))]
+    pub fn set_double_quoted_string_literals(&self, dml: bool, ddl: bool) -> Result<()> {
+        self.set_db_config(DbConfig::SQLITE_DBCONFIG_DQS_DML, dml)?;
+        self.set_db_config(DbConfig::SQLITE_DBCONFIG_DQS_DDL, ddl)?;
+        Ok(())
+    }
+
+    /// Rename the main database of this connection to `name`, e.g. to use
+    /// `name.table` in queries (`main.table` keeps working).
+    ///
+    /// This should be called right after opening the connection, before
+    /// preparing any statement: statements already prepared (including the
+    /// cached ones) and the schema objects referring to the main database by
+    /// its name are not updated.
+    ///
+    /// See [`SQLITE_DBCONFIG_MAINDBNAME`](https://sqlite.org/c3ref/c_dbconfig_defensive.html#sqlitedbconfigmaindbname)
+    /// (SQLite 3.15.0 or later).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `name` contains a nul byte or if the underlying
+    /// SQLite call fails.
+    pub fn set_main_db_name(&self, name: &str) -> Result<()> {
+        // SQLITE_DBCONFIG_MAINDBNAME, 3.15.0
+        const MAINDBNAME: c_int = 1000;
+        let name = CString::new(name)?;
+        let mut c = self.db.borrow_mut();
+        unsafe {
+            check(ffi::sqlite3_db_config(c.db(), MAINDBNAME, name.as_ptr()))?;
+        }
+        // SQLite keeps the pointer, not a copy
+        c.main_db_name = Some(name);
+        Ok(())
+    }
+
+    /// Bind empty strings (`""`, given as `TEXT`) as `NULL`, for schemas
+    /// where the two are equivalent. Off by default.
+    ///
+    /// This applies to the parameters of all the statements of this
+    /// connection, but not to the strings in the SQL text itself nor to the
+    /// values returned by user-defined functions.
+    #[inline]
+    pub fn set_coerce_empty_string_to_null(&self, on: bool) {
+        self.db.borrow_mut().empty_string_as_null = on;
+    }
+
+    /// Whether empty strings are bound as `NULL`, see
+    /// [`Connection::set_coerce_empty_string_to_null`].
+    #[inline]
+    #[must_use]
+    pub fn coerce_empty_string_to_null(&self) -> bool {
+        self.db.borrow().empty_string_as_null
+    }
+
+    /// Choose how `NaN` parameters are bound, see [`NanPolicy`]. Defaults to
+    /// [`NanPolicy::Null`].
+    ///
+    /// This applies to the parameters of all the statements of this
+    /// connection, but not to the values returned by user-defined functions.
+    #[inline]
+    pub fn set_nan_policy(&self, policy: NanPolicy) {
+        self.db.borrow_mut().nan_policy = policy;
+    }
+
+    /// How `NaN` parameters are bound, see [`Connection::set_nan_policy`].
+    #[inline]
+    #[must_use]
+    pub fn nan_policy(&self) -> NanPolicy {
+        self.db.borrow().nan_policy
+    }
+
+    /// Read `bool`s from `TEXT` values too, for databases storing booleans
+    /// as strings: `'true'`, `'t'`, `'yes'` and `'1'` are read as `true`,
+    /// `'false'`, `'f'`, `'no'` and `'0'` as `false` (ignoring ASCII case).
+    /// Off by default, where only `INTEGER`s are read as `bool`s.
+    ///
+    /// This applies to [`Row::get`](crate::Row::get) (and the methods built
+    /// on it) and [`OwnedRow::get`](crate::OwnedRow::get) for all the
+    /// statements of this connection, but not to the arguments of
+    /// user-defined functions. The setting is read when a query starts, so
+    /// changing it does not affect the rows of a query in progress.
+    #[inline]
+    pub fn set_lenient_bool(&self, on: bool) {
+        self.db.borrow_mut().lenient_bool = on;
+    }
+
+    /// Whether `bool`s are also read from `TEXT` values, see
+    /// [`Connection::set_lenient_bool`].
+    #[inline]
+    #[must_use]
+    pub fn lenient_bool(&self) -> bool {
+        self.db.borrow().lenient_bool
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::DbConfig;
-    use crate::{Connection, Result};
+    use super::{DbConfig, NanPolicy};
+    use crate::{Connection, Error, Result};
 
     #[test]
     fn test_db_config() -> Result<()> {
@@ -161,4 +354,195 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_set_main_db_name() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.set_main_db_name("app")?;
+        db.execute_batch("CREATE TABLE app.foo(x); INSERT INTO foo VALUES (1);")?;
+        assert_eq!(1, db.one_column::<i64>("SELECT x FROM app.foo")?);
+        assert_eq!(1, db.one_column::<i64>("SELECT x FROM main.foo")?);
+        let name: String = db.one_column("SELECT name FROM pragma_database_list WHERE seq = 0")?;
+        assert_eq!("app", name);
+
+        db.set_main_db_name("a\0b").unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_coerce_empty_string_to_null() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(t TEXT)")?;
+        assert!(!db.coerce_empty_string_to_null());
+        db.execute("INSERT INTO foo VALUES (?1)", [""])?;
+        db.set_coerce_empty_string_to_null(true);
+        assert!(db.coerce_empty_string_to_null());
+        db.execute("INSERT INTO foo VALUES (?1)", [""])?;
+        db.execute("INSERT INTO foo VALUES (?1), ('')", ["x"])?;
+
+        let mut stmt = db.prepare("SELECT t FROM foo ORDER BY rowid")?;
+        let values: Vec<Option<String>> =
+            stmt.query_map([], |r| r.get(0))?.collect::<Result<_>>()?;
+        assert_eq!(
+            vec![
+                Some(String::new()),
+                None,
+                Some("x".to_owned()),
+                Some(String::new())
+            ],
+            values
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_enable_fkey() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE parent(id INTEGER PRIMARY KEY);
+             CREATE TABLE child(parent_id INTEGER REFERENCES parent(id));",
+        )?;
+
+        db.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, true)?;
+        db.execute("INSERT INTO child VALUES (1)", []).unwrap_err();
+        assert_eq!(1, db.one_column::<i64>("PRAGMA foreign_keys")?);
+
+        db.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, false)?;
+        db.execute("INSERT INTO child VALUES (1)", [])?;
+        assert_eq!(0, db.one_column::<i64>("PRAGMA foreign_keys")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_defensive() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x); PRAGMA writable_schema = ON;")?;
+        let rename = "UPDATE sqlite_master SET sql = 'CREATE TABLE foo(y)' WHERE name = 'foo'";
+
+        assert!(db.set_db_config(DbConfig::SQLITE_DBCONFIG_DEFENSIVE, true)?);
+        db.execute(rename, []).unwrap_err();
+        #[cfg(feature = "bundled")]
+        {
+            db.execute_batch("CREATE VIRTUAL TABLE ft USING fts5(content)")?;
+            db.execute("INSERT INTO ft_data VALUES (1000, x'00')", [])
+                .unwrap_err();
+        }
+
+        assert!(!db.set_db_config(DbConfig::SQLITE_DBCONFIG_DEFENSIVE, false)?);
+        db.execute(rename, [])?;
+        #[cfg(feature = "bundled")]
+        db.execute("INSERT INTO ft_data VALUES (1000, x'00')", [])?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "modern_sqlite")]
+    fn test_double_quoted_string_literals() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x); INSERT INTO foo VALUES (1);")?;
+        let query = r#"SELECT "nonexistent_col" FROM foo"#;
+        db.set_double_quoted_string_literals(true, true)?;
+        assert_eq!("nonexistent_col", db.one_column::<String>(query)?);
+
+        db.set_double_quoted_string_literals(false, false)?;
+        assert!(!db.db_config(DbConfig::SQLITE_DBCONFIG_DQS_DML)?);
+        assert!(!db.db_config(DbConfig::SQLITE_DBCONFIG_DQS_DDL)?);
+        let err = db.prepare(query).unwrap_err();
+        assert!(
+            err.to_string().contains("no such column: nonexistent_col"),
+            "{err}"
+        );
+        db.execute_batch(r#"CREATE INDEX foo_y ON foo("y")"#)
+            .unwrap_err();
+        // identifiers can still be double-quoted
+        assert_eq!(1, db.one_column::<i64>(r#"SELECT "x" FROM foo"#)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nan_policy() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(r REAL)")?;
+        assert_eq!(NanPolicy::Null, db.nan_policy());
+        let values = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        for policy in [NanPolicy::Null, NanPolicy::Error] {
+            db.set_nan_policy(policy);
+            assert_eq!(policy, db.nan_policy());
+            db.execute_batch("DELETE FROM foo")?;
+            let mut stmt = db.prepare("INSERT INTO foo VALUES (?1)")?;
+            for r in values {
+                match stmt.execute([r]) {
+                    Ok(_) => {}
+                    Err(Error::ToSqlConversionFailure(_))
+                        if policy == NanPolicy::Error && r.is_nan() => {}
+                    Err(err) => return Err(err),
+                }
+            }
+
+            let mut stmt = db.prepare("SELECT r FROM foo ORDER BY rowid")?;
+            let stored: Vec<Option<f64>> =
+                stmt.query_map([], |r| r.get(0))?.collect::<Result<_>>()?;
+            let mut expected = Vec::new();
+            if policy == NanPolicy::Null {
+                expected.push(None);
+            }
+            expected.extend([Some(f64::INFINITY), Some(f64::NEG_INFINITY)]);
+            assert_eq!(expected, stored);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_lenient_bool() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        assert!(!db.lenient_bool());
+        let read = |v: &str| db.query_row("SELECT ?1", [v], |r| r.get::<_, bool>(0));
+        let texts = [
+            ("true", true),
+            ("TRUE", true),
+            ("t", true),
+            ("yes", true),
+            ("1", true),
+            ("false", false),
+            ("False", false),
+            ("f", false),
+            ("no", false),
+            ("0", false),
+        ];
+        for (text, _) in texts {
+            assert!(
+                matches!(read(text), Err(Error::InvalidColumnType(..))),
+                "{text}"
+            );
+        }
+
+        db.set_lenient_bool(true);
+        assert!(db.lenient_bool());
+        for (text, expected) in texts {
+            assert_eq!(expected, read(text)?, "{text}");
+        }
+        assert!(matches!(read("maybe"), Err(Error::InvalidColumnType(..))));
+        // integers and NULLs are read as before
+        assert!(db.one_column::<bool>("SELECT 2")?);
+        assert!(!db.one_column::<bool>("SELECT 0")?);
+        assert_eq!(None, db.one_column::<Option<bool>>("SELECT NULL")?);
+        assert_eq!(Some(false), db.one_column::<Option<bool>>("SELECT 'no'")?);
+        // other types are not affected
+        assert_eq!("yes", db.one_column::<String>("SELECT 'yes'")?);
+
+        // owned rows keep the setting of their query
+        let mut stmt = db.prepare("SELECT 'yes', 'no'")?;
+        let mut rows = stmt.query([])?;
+        db.set_lenient_bool(false);
+        let row = rows.next_owned()?.unwrap();
+        assert!(row.get::<bool>(0)?);
+        assert!(!row.get::<bool>(1)?);
+        drop(rows);
+        let row = stmt.query([])?.next_owned()?.unwrap();
+        assert!(matches!(
+            row.get::<bool>(0),
+            Err(Error::InvalidColumnType(..))
+        ));
+        Ok(())
+    }
 }
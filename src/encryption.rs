@@ -0,0 +1,165 @@
+use crate::pragma::{blob_literal, Sql};
+use crate::{Connection, DatabaseName, Result};
+
+/// An encryption key, given to [`Connection::key`], [`Connection::rekey`] and
+/// [`Connection::attach_with_key`].
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum EncryptionKey<'a> {
+    /// A passphrase, from which the encryption extension derives the key.
+    Passphrase(&'a str),
+    /// The raw bytes of the key (32 bytes for SQLCipher's default AES-256
+    /// cipher), used as they are.
+    Raw(&'a [u8]),
+}
+
+// do not leak keys in logs
+impl std::fmt::Debug for EncryptionKey<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionKey::Passphrase(_) => f.write_str("Passphrase(..)"),
+            EncryptionKey::Raw(_) => f.write_str("Raw(..)"),
+        }
+    }
+}
+
+impl EncryptionKey<'_> {
+    // `PRAGMA key = '...'` or `PRAGMA hexkey = '...'`
+    fn push_pragma(&self, sql: &mut Sql, db: DatabaseName<'_>, pragma_name: &str) -> Result<()> {
+        match *self {
+            EncryptionKey::Passphrase(s) => {
+                sql.push_pragma(Some(db), pragma_name)?;
+                sql.push_equal_sign();
+                sql.push_string_literal(s);
+            }
+            EncryptionKey::Raw(bytes) => {
+                sql.push_pragma(Some(db), &format!("hex{pragma_name}"))?;
+                sql.push_equal_sign();
+                let literal = blob_literal(bytes);
+                // x'...' without the x and the quotes
+                sql.push_string_literal(&literal[2..literal.len() - 1]);
+            }
+        }
+        Ok(())
+    }
+
+    // value of the `KEY` clause of `ATTACH`
+    pub(crate) fn attach_value(&self) -> String {
+        match *self {
+            EncryptionKey::Passphrase(s) => s.to_owned(),
+            EncryptionKey::Raw(bytes) => blob_literal(bytes),
+        }
+    }
+}
+
+impl Connection {
+    /// Give the key of the encrypted database `db`, with `PRAGMA key` (for
+    /// a passphrase) or `PRAGMA hexkey` (for a raw key), as supported by
+    /// [SQLCipher](https://www.zetetic.net/sqlcipher/sqlcipher-api/#key) and
+    /// [SEE](https://sqlite.org/see/doc/trunk/www/readme.wiki).
+    ///
+    /// This must be called right after opening the connection (or attaching
+    /// the database), before accessing the database. A new database is
+    /// encrypted with this key when it is created.
+    ///
+    /// The key is quoted properly, whatever its content. Note that a wrong
+    /// key is only detected when the database is first read, and that a
+    /// SQLite library without encryption support ignores these pragmas.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the underlying SQLite call fails.
+    pub fn key(&self, db: DatabaseName<'_>, key: EncryptionKey<'_>) -> Result<()> {
+        self.key_pragma(db, key, "key")
+    }
+
+    /// Change the key of the encrypted database `db` to `key`, with `PRAGMA
+    /// rekey` (for a passphrase) or `PRAGMA hexrekey` (for a raw key),
+    /// re-encrypting its content.
+    ///
+    /// The database must have been opened with its current key, see
+    /// [`Connection::key`].
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the underlying SQLite call fails.
+    pub fn rekey(&self, db: DatabaseName<'_>, key: EncryptionKey<'_>) -> Result<()> {
+        self.key_pragma(db, key, "rekey")
+    }
+
+    fn key_pragma(&self, db: DatabaseName<'_>, key: EncryptionKey<'_>, pragma: &str) -> Result<()> {
+        let mut sql = Sql::new();
+        key.push_pragma(&mut sql, db, pragma)?;
+        // SQLCipher returns "ok"
+        let mut stmt = self.prepare(&sql)?;
+        let mut rows = stmt.raw_query();
+        while rows.next()?.is_some() {}
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EncryptionKey;
+    use crate::{Connection, DatabaseName, Result};
+
+    fn has_encryption(db: &Connection) -> Result<bool> {
+        let mut found = false;
+        db.pragma_query(None, "cipher_version", |_| {
+            found = true;
+            Ok(())
+        })?;
+        Ok(found)
+    }
+
+    #[test]
+    fn test_key_quoting() -> Result<()> {
+        // without encryption support, the pragmas are ignored, but must parse
+        let db = Connection::open_in_memory()?;
+        db.key(DatabaseName::Main, EncryptionKey::Passphrase("it's; --"))?;
+        db.rekey(DatabaseName::Main, EncryptionKey::Raw(&[0, 0xff]))?;
+        assert_eq!(
+            "Passphrase(..)",
+            format!("{:?}", EncryptionKey::Passphrase("secret"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_rekey_attach() -> Result<()> {
+        if !has_encryption(&Connection::open_in_memory()?)? {
+            return Ok(());
+        }
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("secret.db3");
+        let raw = [7u8; 32];
+        {
+            let db = Connection::open(&path)?;
+            db.key(DatabaseName::Main, EncryptionKey::Passphrase("it's secret"))?;
+            db.execute_batch("CREATE TABLE foo(x); INSERT INTO foo VALUES (42);")?;
+        }
+        {
+            let db = Connection::open(&path)?;
+            db.one_column::<i64>("SELECT x FROM foo").unwrap_err();
+        }
+        {
+            let db = Connection::open(&path)?;
+            db.key(DatabaseName::Main, EncryptionKey::Passphrase("it's secret"))?;
+            assert_eq!(42, db.one_column::<i64>("SELECT x FROM foo")?);
+            db.rekey(DatabaseName::Main, EncryptionKey::Raw(&raw))?;
+        }
+        {
+            let db = Connection::open(&path)?;
+            db.key(DatabaseName::Main, EncryptionKey::Raw(&raw))?;
+            assert_eq!(42, db.one_column::<i64>("SELECT x FROM foo")?);
+        }
+
+        let db = Connection::open_in_memory()?;
+        db.attach_with_key(&path, "secret", EncryptionKey::Passphrase("wrong"))
+            .and_then(|_| db.one_column::<i64>("SELECT x FROM secret.foo"))
+            .unwrap_err();
+        let secret = db.attach_with_key(&path, "other", EncryptionKey::Raw(&raw))?;
+        assert_eq!(42, db.one_column::<i64>("SELECT x FROM other.foo")?);
+        secret.detach()
+    }
+}
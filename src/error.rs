@@ -51,6 +51,12 @@ pub enum Error {
     /// for [`query_row`](crate::Connection::query_row)) did not return any.
     QueryReturnedNoRows,
 
+    /// Error when a statement that was expected to return exactly one row
+    /// (e.g., for
+    /// [`execute_returning_scalar`](crate::Connection::execute_returning_scalar))
+    /// returned more, with the number of rows returned.
+    QueryReturnedMoreThanOneRow(usize),
+
     /// Error when the value of a particular column is requested, but the index
     /// is out of range for the statement.
     InvalidColumnIndex(usize),
@@ -91,6 +97,12 @@ pub enum Error {
     /// [`ToSql`](crate::types::ToSql) trait.
     ToSqlConversionFailure(Box<dyn error::Error + Send + Sync + 'static>),
 
+    /// Error when reading from or writing to an application-provided
+    /// [`Read`](std::io::Read) or [`Write`](std::io::Write), e.g. in
+    /// `Connection::read_blob_to_writer`, or from a file read directly, e.g.
+    /// in `Connection::probe_file`.
+    IoError(std::io::Error),
+
     /// Error when the SQL is not a `SELECT`, is not read-only.
     InvalidQuery,
 
@@ -122,7 +134,9 @@ pub enum Error {
     /// Returned from various functions in the Blob IO positional API. For
     /// example,
     /// [`Blob::raw_read_at_exact`](crate::blob::Blob::raw_read_at_exact) will
-    /// return it if the blob has insufficient data.
+    /// return it if the blob has insufficient data, and
+    /// [`Connection::insert_blob_from_reader`](crate::Connection::insert_blob_from_reader)
+    /// if the reader has insufficient data.
     #[cfg(feature = "blob")]
     #[cfg_attr(docsrs, doc(cfg(feature = "blob")))]
     BlobSizeError,
@@ -148,6 +162,35 @@ pub enum Error {
     #[cfg(feature = "modern_sqlite")] // 3.39.0
     #[cfg_attr(docsrs, doc(cfg(feature = "modern_sqlite")))]
     InvalidDatabaseIndex(usize),
+    /// Error returned by
+    /// [`Connection::with_query_timeout`](crate::Connection::with_query_timeout)
+    /// when the closure was interrupted because it did not finish in time.
+    QueryTimeout,
+    /// Error returned by [`InterruptibleRows`](crate::InterruptibleRows) when
+    /// its flag was set before all rows were retrieved.
+    QueryInterrupted,
+    /// Error returned when a statement was aborted because it ran more virtual
+    /// machine instructions than allowed by
+    /// [`Connection::set_step_budget`](crate::Connection::set_step_budget).
+    #[cfg(feature = "hooks")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "hooks")))]
+    StepBudgetExceeded,
+    /// Error returned by
+    /// [`Connection::execute_checked`](crate::Connection::execute_checked)
+    /// when the database is busy or locked (`SQLITE_BUSY` / `SQLITE_LOCKED`,
+    /// including extended codes), so the operation can be retried.
+    DatabaseBusy(ffi::Error, Option<String>),
+    /// Error returned by
+    /// [`Connection::execute_batch_with_results`](crate::Connection::execute_batch_with_results)
+    /// when one of the statements of the batch fails.
+    BatchStatementFailed {
+        /// 1-based index of the failed statement in the batch
+        index: usize,
+        /// SQL text of the failed statement
+        sql: String,
+        /// error returned by the statement
+        error: Box<Error>,
+    },
 }
 
 impl PartialEq for Error {
@@ -164,6 +207,9 @@ impl PartialEq for Error {
             (Error::InvalidPath(p1), Error::InvalidPath(p2)) => p1 == p2,
             (Error::ExecuteReturnedResults, Error::ExecuteReturnedResults) => true,
             (Error::QueryReturnedNoRows, Error::QueryReturnedNoRows) => true,
+            (Error::QueryReturnedMoreThanOneRow(n1), Error::QueryReturnedMoreThanOneRow(n2)) => {
+                n1 == n2
+            }
             (Error::InvalidColumnIndex(i1), Error::InvalidColumnIndex(i2)) => i1 == i2,
             (Error::InvalidColumnName(n1), Error::InvalidColumnName(n2)) => n1 == n2,
             (Error::InvalidColumnType(i1, n1, t1), Error::InvalidColumnType(i2, n2, t2)) => {
@@ -210,6 +256,23 @@ impl PartialEq for Error {
             (Error::InitError(e1), Error::InitError(e2)) => e1 == e2,
             #[cfg(feature = "modern_sqlite")]
             (Error::InvalidDatabaseIndex(i1), Error::InvalidDatabaseIndex(i2)) => i1 == i2,
+            (Error::QueryTimeout, Error::QueryTimeout) => true,
+            (Error::QueryInterrupted, Error::QueryInterrupted) => true,
+            (Error::DatabaseBusy(e1, s1), Error::DatabaseBusy(e2, s2)) => e1 == e2 && s1 == s2,
+            (
+                Error::BatchStatementFailed {
+                    index: i1,
+                    sql: s1,
+                    error: e1,
+                },
+                Error::BatchStatementFailed {
+                    index: i2,
+                    sql: s2,
+                    error: e2,
+                },
+            ) => i1 == i2 && s1 == s2 && e1 == e2,
+            #[cfg(feature = "hooks")]
+            (Error::StepBudgetExceeded, Error::StepBudgetExceeded) => true,
             (..) => false,
         }
     }
@@ -290,6 +353,9 @@ impl fmt::Display for Error {
                 write!(f, "Execute returned results - did you mean to call query?")
             }
             Error::QueryReturnedNoRows => write!(f, "Query returned no rows"),
+            Error::QueryReturnedMoreThanOneRow(n) => {
+                write!(f, "Query returned {n} rows, expected one")
+            }
             Error::InvalidColumnIndex(i) => write!(f, "Invalid column index: {i}"),
             Error::InvalidColumnName(ref name) => write!(f, "Invalid column name: {name}"),
             Error::InvalidColumnType(i, ref name, ref t) => {
@@ -312,6 +378,7 @@ impl fmt::Display for Error {
             #[cfg(feature = "functions")]
             Error::UserFunctionError(ref err) => err.fmt(f),
             Error::ToSqlConversionFailure(ref err) => err.fmt(f),
+            Error::IoError(ref err) => write!(f, "I/O error: {err}"),
             Error::InvalidQuery => write!(f, "Query is not read-only"),
             #[cfg(feature = "vtab")]
             Error::ModuleError(ref desc) => write!(f, "{desc}"),
@@ -332,6 +399,17 @@ impl fmt::Display for Error {
             Error::InitError(ref err) => err.fmt(f),
             #[cfg(feature = "modern_sqlite")]
             Error::InvalidDatabaseIndex(i) => write!(f, "Invalid database index: {i}"),
+            Error::QueryTimeout => write!(f, "Query timed out"),
+            Error::QueryInterrupted => write!(f, "Query interrupted"),
+            Error::DatabaseBusy(ref err, None) => err.fmt(f),
+            Error::DatabaseBusy(_, Some(ref s)) => write!(f, "{s}"),
+            Error::BatchStatementFailed {
+                index,
+                ref sql,
+                ref error,
+            } => write!(f, "{error} in statement {index} of the batch: {sql}"),
+            #[cfg(feature = "hooks")]
+            Error::StepBudgetExceeded => write!(f, "Step budget exceeded"),
         }
     }
 }
@@ -339,7 +417,7 @@ impl fmt::Display for Error {
 impl error::Error for Error {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match *self {
-            Error::SqliteFailure(ref err, _) => Some(err),
+            Error::SqliteFailure(ref err, _) | Error::DatabaseBusy(ref err, _) => Some(err),
             Error::Utf8Error(ref err) => Some(err),
             Error::NulError(ref err) => Some(err),
 
@@ -348,6 +426,7 @@ impl error::Error for Error {
             | Error::InvalidParameterName(_)
             | Error::ExecuteReturnedResults
             | Error::QueryReturnedNoRows
+            | Error::QueryReturnedMoreThanOneRow(_)
             | Error::InvalidColumnIndex(_)
             | Error::InvalidColumnName(_)
             | Error::InvalidColumnType(..)
@@ -355,7 +434,9 @@ impl error::Error for Error {
             | Error::InvalidParameterCount(..)
             | Error::StatementChangedRows(_)
             | Error::InvalidQuery
-            | Error::MultipleStatement => None,
+            | Error::MultipleStatement
+            | Error::QueryTimeout
+            | Error::QueryInterrupted => None,
 
             #[cfg(feature = "functions")]
             Error::InvalidFunctionParameterType(..) => None,
@@ -365,6 +446,10 @@ impl error::Error for Error {
             #[cfg(feature = "functions")]
             Error::UserFunctionError(ref err) => Some(&**err),
 
+            Error::BatchStatementFailed { ref error, .. } => Some(&**error),
+
+            Error::IoError(ref err) => Some(err),
+
             Error::FromSqlConversionFailure(_, _, ref err)
             | Error::ToSqlConversionFailure(ref err) => Some(&**err),
 
@@ -384,23 +469,26 @@ impl error::Error for Error {
             Error::InitError(ref err) => Some(err),
             #[cfg(feature = "modern_sqlite")]
             Error::InvalidDatabaseIndex(_) => None,
+            #[cfg(feature = "hooks")]
+            Error::StepBudgetExceeded => None,
         }
     }
 }
 
 impl Error {
-    /// Returns the underlying SQLite error if this is [`Error::SqliteFailure`].
+    /// Returns the underlying SQLite error if this is [`Error::SqliteFailure`]
+    /// or [`Error::DatabaseBusy`].
     #[inline]
     #[must_use]
     pub fn sqlite_error(&self) -> Option<&ffi::Error> {
         match self {
-            Self::SqliteFailure(error, _) => Some(error),
+            Self::SqliteFailure(error, _) | Self::DatabaseBusy(error, _) => Some(error),
             _ => None,
         }
     }
 
     /// Returns the underlying SQLite error code if this is
-    /// [`Error::SqliteFailure`].
+    /// [`Error::SqliteFailure`] or [`Error::DatabaseBusy`].
     #[inline]
     #[must_use]
     pub fn sqlite_error_code(&self) -> Option<ffi::ErrorCode> {
@@ -410,6 +498,13 @@ impl Error {
 
 // These are public but not re-exported by lib.rs, so only visible within crate.
 
+// The error returned when the rusqlite API is misused, e.g. given invalid
+// arguments.
+#[cold]
+pub(crate) fn misuse(msg: impl Into<String>) -> Error {
+    Error::SqliteFailure(ffi::Error::new(ffi::SQLITE_MISUSE), Some(msg.into()))
+}
+
 #[cold]
 pub fn error_from_sqlite_code(code: c_int, message: Option<String>) -> Error {
     Error::SqliteFailure(ffi::Error::new(code), message)
@@ -468,7 +563,7 @@ pub fn check(code: c_int) -> Result<()> {
 pub unsafe fn to_sqlite_error(e: &Error, err_msg: *mut *mut std::os::raw::c_char) -> c_int {
     use crate::util::alloc;
     match e {
-        Error::SqliteFailure(err, s) => {
+        Error::SqliteFailure(err, s) | Error::DatabaseBusy(err, s) => {
             if let Some(s) = s {
                 *err_msg = alloc(s);
             }
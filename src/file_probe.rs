@@ -0,0 +1,169 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::{ffi, Connection, Error, Result};
+
+const MAGIC: &[u8; 16] = b"SQLite format 3\0";
+const HEADER_SIZE: usize = 100;
+
+/// What the header of a database file says, as returned by
+/// [`Connection::probe_file`].
+///
+/// See [the database header](https://www.sqlite.org/fileformat.html#the_database_header).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileProbe {
+    /// Size of a database page, in bytes.
+    pub page_size: u32,
+    /// File format write version: 1 for rollback journal, 2 for WAL.
+    pub write_version: u8,
+    /// File format read version: 1 for rollback journal, 2 for WAL.
+    pub read_version: u8,
+    /// Size of the database, in pages (may be out of date if the file was
+    /// last written by SQLite 3.7.0 or older).
+    pub page_count: u32,
+}
+
+impl FileProbe {
+    /// Whether the database is in WAL mode.
+    #[inline]
+    #[must_use]
+    pub fn is_wal(&self) -> bool {
+        self.write_version == 2 || self.read_version == 2
+    }
+}
+
+impl Connection {
+    /// Read the header of the file at `path` and check that it looks like a
+    /// SQLite database, without opening it.
+    ///
+    /// This gives a better diagnostic than the `SQLITE_NOTADB` ("file is not
+    /// a database") error returned when such a file is queried.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err(IoError)` if the file cannot be read, and `Err` with
+    /// `SQLITE_NOTADB` and a message describing the problem if it is empty
+    /// (SQLite would initialize it as a new database), or does not start with
+    /// a valid SQLite header (e.g. because it is encrypted or corrupt).
+    pub fn probe_file<P: AsRef<Path>>(path: P) -> Result<FileProbe> {
+        let path = path.as_ref();
+        let mut header = [0u8; HEADER_SIZE];
+        let len = read_header(path, &mut header).map_err(Error::IoError)?;
+        parse_header(&header[..len])
+    }
+}
+
+fn read_header(path: &Path, header: &mut [u8]) -> io::Result<usize> {
+    let mut file = File::open(path)?;
+    let mut len = 0;
+    while len < header.len() {
+        match file.read(&mut header[len..])? {
+            0 => break,
+            n => len += n,
+        }
+    }
+    Ok(len)
+}
+
+fn parse_header(header: &[u8]) -> Result<FileProbe> {
+    let not_a_db = |msg: &str| {
+        Err(Error::SqliteFailure(
+            ffi::Error::new(ffi::SQLITE_NOTADB),
+            Some(msg.to_owned()),
+        ))
+    };
+    if header.is_empty() {
+        return not_a_db("file is empty");
+    }
+    if !header.starts_with(MAGIC) {
+        return not_a_db("file does not start with the SQLite header (it may be encrypted or not a SQLite database)");
+    }
+    if header.len() < HEADER_SIZE {
+        return not_a_db("file is too short to hold a SQLite header (it may be truncated)");
+    }
+    let page_size = match u16::from_be_bytes([header[16], header[17]]) {
+        1 => 65536,
+        n => u32::from(n),
+    };
+    if !(512..=65536).contains(&page_size) || !page_size.is_power_of_two() {
+        return not_a_db("invalid page size in SQLite header (the file may be corrupt)");
+    }
+    let (write_version, read_version) = (header[18], header[19]);
+    if !(1..=2).contains(&write_version) || !(1..=2).contains(&read_version) {
+        return not_a_db("unsupported file format version in SQLite header");
+    }
+    Ok(FileProbe {
+        page_size,
+        write_version,
+        read_version,
+        page_count: u32::from_be_bytes([header[28], header[29], header[30], header[31]]),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ffi, Connection, Error, Result};
+
+    fn notadb_message(err: Error) -> String {
+        match err {
+            Error::SqliteFailure(
+                ffi::Error {
+                    code: ffi::ErrorCode::NotADatabase,
+                    ..
+                },
+                Some(msg),
+            ) => msg,
+            err => panic!("unexpected error {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_probe_database() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let db = Connection::open(&path)?;
+        db.pragma_update(None, "page_size", 8192)?;
+        db.execute_batch("CREATE TABLE foo(x); INSERT INTO foo VALUES (1);")?;
+        let probe = Connection::probe_file(&path)?;
+        assert_eq!(8192, probe.page_size);
+        assert_eq!(2, probe.page_count);
+        assert!(!probe.is_wal());
+
+        db.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        assert!(Connection::probe_file(&path)?.is_wal());
+        Ok(())
+    }
+
+    #[test]
+    fn test_probe_not_a_database() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("empty");
+        std::fs::write(&path, b"").unwrap();
+        let msg = notadb_message(Connection::probe_file(&path).unwrap_err());
+        assert!(msg.contains("empty"), "{msg}");
+
+        // pseudo-random bytes
+        let mut x: u32 = 12345;
+        let bytes: Vec<u8> = (0..4096)
+            .map(|_| {
+                x = x.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (x >> 16) as u8
+            })
+            .collect();
+        std::fs::write(&path, bytes).unwrap();
+        let msg = notadb_message(Connection::probe_file(&path).unwrap_err());
+        assert!(msg.contains("SQLite header"), "{msg}");
+
+        std::fs::write(&path, b"SQLite format 3\0").unwrap();
+        let msg = notadb_message(Connection::probe_file(&path).unwrap_err());
+        assert!(msg.contains("too short"), "{msg}");
+
+        let missing = temp_dir.path().join("missing");
+        assert!(matches!(
+            Connection::probe_file(missing),
+            Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::NotFound
+        ));
+    }
+}
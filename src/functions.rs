@@ -71,16 +71,18 @@ use crate::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, ValueRef};
 use crate::{str_to_cstring, Connection, Error, InnerConnection, Result};
 
 unsafe fn report_error(ctx: *mut sqlite3_context, err: &Error) {
+    // `sqlite3_result_error` resets the error code to `SQLITE_ERROR`, so it
+    // must be called first
     if let Error::SqliteFailure(ref err, ref s) = *err {
-        ffi::sqlite3_result_error_code(ctx, err.extended_code);
         if let Some(Ok(cstr)) = s.as_ref().map(|s| str_to_cstring(s)) {
             ffi::sqlite3_result_error(ctx, cstr.as_ptr(), -1);
         }
+        ffi::sqlite3_result_error_code(ctx, err.extended_code);
     } else {
-        ffi::sqlite3_result_error_code(ctx, ffi::SQLITE_CONSTRAINT_FUNCTION);
         if let Ok(cstr) = str_to_cstring(&err.to_string()) {
             ffi::sqlite3_result_error(ctx, cstr.as_ptr(), -1);
         }
+        ffi::sqlite3_result_error_code(ctx, ffi::SQLITE_CONSTRAINT_FUNCTION);
     }
 }
 
@@ -379,11 +381,19 @@ bitflags::bitflags! {
         const SQLITE_UTF16    = ffi::SQLITE_UTF16;
         /// Means that the function always gives the same output when the input parameters are the same.
         const SQLITE_DETERMINISTIC = ffi::SQLITE_DETERMINISTIC; // 3.8.3
-        /// Means that the function may only be invoked from top-level SQL.
+        /// Means that the function may only be invoked from top-level SQL, not
+        /// from triggers, views, `CHECK` constraints, generated columns or
+        /// index expressions, whatever `PRAGMA trusted_schema` says. Recommended
+        /// for functions with side effects or which reveal sensitive data, so
+        /// that a malicious schema cannot call them behind the application's
+        /// back.
         const SQLITE_DIRECTONLY    = 0x0000_0008_0000; // 3.30.0
         /// Indicates to SQLite that a function may call `sqlite3_value_subtype()` to inspect the sub-types of its arguments.
         const SQLITE_SUBTYPE       = 0x0000_0010_0000; // 3.30.0
-        /// Means that the function is unlikely to cause problems even if misused.
+        /// Means that the function is unlikely to cause problems even if misused
+        /// (it has no side effects and reveals nothing but its result), so it
+        /// may be used from the schema (triggers, views, ...) even when
+        /// `PRAGMA trusted_schema` is off, unlike functions without this flag.
         const SQLITE_INNOCUOUS     = 0x0000_0020_0000; // 3.31.0
         /// Indicates to SQLite that a function might call `sqlite3_result_subtype()` to cause a sub-type to be associated with its result.
         const SQLITE_RESULT_SUBTYPE     = 0x0000_0100_0000; // 3.45.0
@@ -432,6 +442,11 @@ impl Connection {
     /// }
     /// ```
     ///
+    /// If `x_func` returns an [`Error::SqliteFailure`], the statement calling
+    /// the function fails with the same (extended) result code and message,
+    /// which lets a function enforce a constraint. Any other error fails the
+    /// statement with `SQLITE_CONSTRAINT_FUNCTION`.
+    ///
     /// # Failure
     ///
     /// Will return Err if the function could not be attached to the connection.
@@ -452,6 +467,55 @@ impl Connection {
             .create_scalar_function(fn_name, n_arg, flags, x_func)
     }
 
+    /// Attach several user-defined scalar functions, given as `(fn_name,
+    /// n_arg, flags, x_func)` tuples, to this database connection. See
+    /// [`create_scalar_function`](Connection::create_scalar_function).
+    ///
+    /// Functions of different types can be boxed:
+    ///
+    /// ```rust
+    /// # use rusqlite::{Connection, Result};
+    /// # use rusqlite::functions::{Context, FunctionFlags};
+    /// type ScalarFn = Box<dyn FnMut(&Context<'_>) -> Result<i64> + Send>;
+    ///
+    /// fn add_functions(db: &Connection) -> Result<()> {
+    ///     let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+    ///     db.create_scalar_functions([
+    ///         ("one", 0, flags, Box::new(|_: &Context<'_>| Ok(1_i64)) as ScalarFn),
+    ///         ("double", 1, flags, Box::new(|ctx: &Context<'_>| Ok(2 * ctx.get::<i64>(0)?))),
+    ///     ])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return Err, with the name of the function in the error message, as
+    /// soon as a function could not be attached to the connection. The
+    /// functions before it stay attached.
+    pub fn create_scalar_functions<I, N, F, T>(&self, functions: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (N, c_int, FunctionFlags, F)>,
+        N: AsRef<str>,
+        F: FnMut(&Context<'_>) -> Result<T> + Send + 'static,
+        T: SqlFnOutput,
+    {
+        for (fn_name, n_arg, flags, x_func) in functions {
+            let fn_name = fn_name.as_ref();
+            self.create_scalar_function(fn_name, n_arg, flags, x_func)
+                .map_err(|err| match err {
+                    Error::SqliteFailure(err, msg) => Error::SqliteFailure(
+                        err,
+                        Some(format!(
+                            "cannot create function {fn_name}: {}",
+                            msg.unwrap_or_else(|| err.to_string())
+                        )),
+                    ),
+                    err => err,
+                })?;
+        }
+        Ok(())
+    }
+
     /// Attach a user-defined aggregate function to this
     /// database connection.
     ///
@@ -852,7 +916,7 @@ mod test {
     #[cfg(feature = "window")]
     use crate::functions::WindowAggregate;
     use crate::functions::{Aggregate, Context, FunctionFlags, SqlFnArg, SubType};
-    use crate::{Connection, Error, Result};
+    use crate::{ffi, Connection, Error, Result};
 
     fn half(ctx: &Context<'_>) -> Result<c_double> {
         assert_eq!(ctx.len(), 1, "called with unexpected number of arguments");
@@ -875,6 +939,34 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_create_scalar_functions() -> Result<()> {
+        type ScalarFn = Box<dyn FnMut(&Context<'_>) -> Result<f64> + Send>;
+        let db = Connection::open_in_memory()?;
+        let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+        db.create_scalar_functions([
+            ("half", 1, flags, Box::new(half) as ScalarFn),
+            ("three", 0, flags, Box::new(|_: &Context<'_>| Ok(3.0))),
+            (
+                "plus",
+                2,
+                flags,
+                Box::new(|ctx: &Context<'_>| Ok(ctx.get::<f64>(0)? + ctx.get::<f64>(1)?)),
+            ),
+        ])?;
+        let result: f64 = db.one_column("SELECT plus(half(6), three())")?;
+        assert!((6f64 - result).abs() < f64::EPSILON);
+
+        // too many arguments
+        let zero: fn(&Context<'_>) -> Result<i64> = |_| Ok(0);
+        let err = db
+            .create_scalar_functions([("ok", 0, flags, zero), ("bad", 1000, flags, zero)])
+            .unwrap_err();
+        assert!(err.to_string().contains("bad"), "{err}");
+        assert_eq!(0, db.one_column::<i64>("SELECT ok()")?);
+        Ok(())
+    }
+
     #[test]
     fn test_remove_function() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -893,6 +985,44 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_function_error_code() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.create_scalar_function("positive", 1, FunctionFlags::SQLITE_UTF8, |ctx| {
+            let value = ctx.get::<i64>(0)?;
+            if value > 0 {
+                Ok(value)
+            } else {
+                Err(Error::SqliteFailure(
+                    ffi::Error::new(ffi::SQLITE_CONSTRAINT_CHECK),
+                    Some(format!("{value} is not positive")),
+                ))
+            }
+        })?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+        db.execute("INSERT INTO foo VALUES (positive(?1))", [1])?;
+        match db.execute("INSERT INTO foo VALUES (positive(?1))", [-1]) {
+            Err(Error::SqliteFailure(err, Some(msg))) => {
+                assert_eq!(ffi::ErrorCode::ConstraintViolation, err.code);
+                assert_eq!(ffi::SQLITE_CONSTRAINT_CHECK, err.extended_code);
+                assert_eq!("-1 is not positive", msg);
+            }
+            r => panic!("unexpected result {r:?}"),
+        }
+
+        // other errors are reported as SQLITE_CONSTRAINT_FUNCTION
+        let err = db
+            .one_column::<i64>("SELECT positive('a')")
+            .unwrap_err()
+            .sqlite_error()
+            .copied();
+        assert_eq!(
+            Some(ffi::SQLITE_CONSTRAINT_FUNCTION),
+            err.map(|e| e.extended_code)
+        );
+        Ok(())
+    }
+
     // This implementation of a regexp scalar function uses SQLite's auxiliary data
     // (https://www.sqlite.org/c3ref/get_auxdata.html) to avoid recompiling the regular
     // expression multiple times within one query.
@@ -1130,6 +1260,35 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_direct_only_and_innocuous() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let double = |ctx: &Context<'_>| Ok(2 * ctx.get::<i64>(0)?);
+        db.create_scalar_function("direct", 1, FunctionFlags::SQLITE_DIRECTONLY, double)?;
+        db.create_scalar_function("innocuous", 1, FunctionFlags::SQLITE_INNOCUOUS, double)?;
+        db.create_scalar_function("plain", 1, FunctionFlags::SQLITE_UTF8, double)?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER);
+             CREATE TABLE log(y INTEGER);
+             CREATE TRIGGER foo_insert AFTER INSERT ON foo BEGIN
+                 INSERT INTO log VALUES (direct(NEW.x));
+             END;
+             CREATE VIEW innocuous_view AS SELECT innocuous(x) FROM foo;
+             CREATE VIEW plain_view AS SELECT plain(x) FROM foo;",
+        )?;
+        assert_eq!(4, db.one_column::<i64>("SELECT direct(2)")?);
+        let err = db.execute("INSERT INTO foo VALUES (1)", []).unwrap_err();
+        assert!(err.to_string().contains("unsafe use of direct()"), "{err}");
+
+        db.execute_batch("DROP TRIGGER foo_insert; INSERT INTO foo VALUES (1);")?;
+        assert_eq!(2, db.one_column::<i64>("SELECT * FROM plain_view")?);
+        db.pragma_update(None, "trusted_schema", false)?;
+        assert_eq!(2, db.one_column::<i64>("SELECT * FROM innocuous_view")?);
+        db.one_column::<i64>("SELECT * FROM plain_view")
+            .unwrap_err();
+        Ok(())
+    }
+
     #[test]
     fn test_sub_type() -> Result<()> {
         fn test_getsubtype(ctx: &Context<'_>) -> Result<i32> {
@@ -4,6 +4,8 @@
 use std::os::raw::{c_char, c_int, c_void};
 use std::panic::catch_unwind;
 use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use crate::ffi;
 
@@ -399,6 +401,51 @@ impl Connection {
         self.db.borrow_mut().progress_handler(num_ops, handler);
     }
 
+    /// Limit the number of virtual machine instructions each top-level
+    /// statement can run, or remove the limit with `None`.
+    ///
+    /// A statement running more than `max_vm_steps` instructions (counted with
+    /// a granularity of up to 1000) is aborted with
+    /// [`Error::StepBudgetExceeded`](crate::Error::StepBudgetExceeded). The
+    /// count starts over each time a statement starts running, except for
+    /// statements run by a user-defined function or another callback during
+    /// a statement, which count toward the budget of the latter.
+    ///
+    /// This bounds the cost of untrusted queries, along with the limits set by
+    /// `Connection::set_limit` (with the `limits` feature).
+    ///
+    /// The budget is enforced with a progress handler: this replaces any
+    /// handler set with [`Connection::progress_handler`], and is removed by
+    /// it.
+    pub fn set_step_budget(&self, max_vm_steps: Option<u64>) {
+        let mut db = self.db.borrow_mut();
+        match max_vm_steps {
+            Some(max) => {
+                let granularity = max.clamp(1, 1000);
+                let budget = Arc::new(StepBudget {
+                    max,
+                    used: AtomicU64::new(0),
+                    depth: AtomicUsize::new(0),
+                    exceeded: AtomicBool::new(false),
+                });
+                let counter = Arc::clone(&budget);
+                db.progress_handler(
+                    granularity as c_int,
+                    Some(move || {
+                        let used = counter.used.fetch_add(granularity, Ordering::Relaxed);
+                        let exceeded = used + granularity > counter.max;
+                        if exceeded {
+                            counter.exceeded.store(true, Ordering::Relaxed);
+                        }
+                        exceeded
+                    }),
+                );
+                db.step_budget = Some(budget);
+            }
+            None => db.progress_handler(0, None::<fn() -> bool>),
+        }
+    }
+
     /// Register an authorizer callback that's invoked
     /// as a statement is being prepared.
     #[inline]
@@ -410,14 +457,60 @@ impl Connection {
     }
 }
 
+/// Instructions run by the current top-level statement, for
+/// [`Connection::set_step_budget`].
+#[derive(Debug)]
+pub(crate) struct StepBudget {
+    max: u64,
+    used: AtomicU64,
+    // number of statements being stepped, more than one when a statement is
+    // run by a callback during another one
+    depth: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl StepBudget {
+    /// Called before stepping a statement, `starting` if it is not running
+    /// yet.
+    pub(crate) fn enter(&self, starting: bool) {
+        if self.depth.fetch_add(1, Ordering::Relaxed) == 0 && starting {
+            self.used.store(0, Ordering::Relaxed);
+            self.exceeded.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Called after stepping a statement.
+    pub(crate) fn leave(&self) {
+        self.depth.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+}
+
 impl InnerConnection {
+    /// Remove the hooks registered through this connection only: a
+    /// connection which does not own its handle (e.g. the one given to a
+    /// function by `Context::get_connection`) must not remove the hooks of
+    /// the connection owning it.
     #[inline]
     pub fn remove_hooks(&mut self) {
-        self.update_hook(None::<fn(Action, &str, &str, i64)>);
-        self.commit_hook(None::<fn() -> bool>);
-        self.rollback_hook(None::<fn()>);
-        self.progress_handler(0, None::<fn() -> bool>);
-        self.authorizer(None::<fn(AuthContext<'_>) -> Authorization>);
+        if self.free_update_hook.is_some() {
+            self.update_hook(None::<fn(Action, &str, &str, i64)>);
+        }
+        if self.free_commit_hook.is_some() {
+            self.commit_hook(None::<fn() -> bool>);
+        }
+        if self.free_rollback_hook.is_some() {
+            self.rollback_hook(None::<fn()>);
+        }
+        if self.progress_handler.is_some() {
+            self.progress_handler(0, None::<fn() -> bool>);
+        }
+        if self.authorizer.is_some() {
+            self.authorizer(None::<fn(AuthContext<'_>) -> Authorization>);
+        }
     }
 
     /// ```compile_fail
@@ -636,6 +729,7 @@ impl InnerConnection {
     where
         F: FnMut() -> bool + Send + 'static,
     {
+        self.step_budget = None;
         unsafe extern "C" fn call_boxed_closure<F>(p_arg: *mut c_void) -> c_int
         where
             F: FnMut() -> bool,
@@ -865,6 +959,62 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_step_budget() -> Result<()> {
+        use crate::Error;
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER);
+             WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 1000)
+             INSERT INTO foo SELECT x FROM c;",
+        )?;
+        let cheap = "SELECT count(*) FROM foo WHERE x < 10";
+        let expensive = "SELECT count(*) FROM foo a, foo b, foo c";
+        db.set_step_budget(Some(100_000));
+        for _ in 0..10 {
+            // the budget is per statement
+            assert_eq!(9, db.query_row(cheap, [], |r| r.get::<_, i64>(0))?);
+        }
+        assert_eq!(
+            Some(Error::StepBudgetExceeded),
+            db.query_row(expensive, [], |r| r.get::<_, i64>(0)).err()
+        );
+        // following statements get a new budget
+        assert_eq!(9, db.query_row(cheap, [], |r| r.get::<_, i64>(0))?);
+
+        // statements run by a function count toward the calling statement
+        #[cfg(feature = "functions")]
+        {
+            db.create_scalar_function(
+                "count_foo",
+                0,
+                crate::functions::FunctionFlags::SQLITE_UTF8,
+                |ctx| {
+                    let db = unsafe { ctx.get_connection()? };
+                    db.query_row("SELECT count(*) FROM foo WHERE x > 0", [], |r| {
+                        r.get::<_, i64>(0)
+                    })
+                },
+            )?;
+            assert_eq!(
+                1000,
+                db.query_row("SELECT count_foo()", [], |r| r.get::<_, i64>(0))?
+            );
+            assert_eq!(
+                Some(Error::StepBudgetExceeded),
+                db.query_row("SELECT sum(count_foo()) FROM foo", [], |r| r
+                    .get::<_, i64>(0))
+                    .err()
+            );
+        }
+
+        db.set_step_budget(None);
+        let n: i64 = db.query_row("SELECT count(*) FROM foo a, foo b", [], |r| r.get(0))?;
+        assert_eq!(1_000_000, n);
+        Ok(())
+    }
+
     #[test]
     fn test_authorizer() -> Result<()> {
         use super::{AuthAction, AuthContext, Authorization};
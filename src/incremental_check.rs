@@ -0,0 +1,199 @@
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::{quote_identifier, Connection, Error, Result};
+
+/// State of an integrity check run in slices, see
+/// [`Connection::integrity_check_incremental`].
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalCheck {
+    // tables to check, listed by the first slice
+    tables: Option<Vec<String>>,
+    // number of tables checked
+    checked: usize,
+    problems: Vec<String>,
+}
+
+impl IncrementalCheck {
+    /// A check which has not started yet.
+    #[inline]
+    #[must_use]
+    pub fn new() -> IncrementalCheck {
+        IncrementalCheck::default()
+    }
+
+    /// Whether all the tables have been checked.
+    #[inline]
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.tables
+            .as_ref()
+            .is_some_and(|t| self.checked == t.len())
+    }
+
+    /// Number of tables checked so far, and total number of tables (0 until
+    /// the first slice is run).
+    #[inline]
+    #[must_use]
+    pub fn progress(&self) -> (usize, usize) {
+        (self.checked, self.tables.as_ref().map_or(0, Vec::len))
+    }
+
+    /// Problems found so far, empty if the checked tables are healthy.
+    #[inline]
+    #[must_use]
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+}
+
+impl Connection {
+    /// Run the next slice of an integrity check of the main database, which
+    /// runs `PRAGMA quick_check` table by table, doing at most about
+    /// `max_vm_steps` virtual machine instructions (counted with a
+    /// granularity of up to 1000). Returns whether the check is complete.
+    ///
+    /// A background maintenance task can so check a large database without
+    /// blocking the connection for long, calling this until it returns
+    /// `true`, then reading [`IncrementalCheck::problems`]. The tables
+    /// checked when a slice runs out of budget are kept; the table being
+    /// checked is checked again from the start by the next slice. To abort
+    /// the check, simply drop `check`.
+    ///
+    /// Unlike a single `PRAGMA quick_check`, pages which do not belong to any
+    /// table or index (e.g. the free pages) are not checked. Tables created
+    /// or dropped once the check has started are not taken into account.
+    /// Requires SQLite 3.33.0 or later.
+    ///
+    /// The budget is enforced with a progress handler: this replaces any
+    /// handler or step budget set on the connection, and none is left once
+    /// this returns.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the underlying SQLite calls fail, or
+    /// [`Error::StepBudgetExceeded`] if not even one table could be checked
+    /// within `max_vm_steps` (the budget is too small for that table).
+    pub fn integrity_check_incremental(
+        &self,
+        check: &mut IncrementalCheck,
+        max_vm_steps: u64,
+    ) -> Result<bool> {
+        if check.tables.is_none() {
+            let mut stmt = self.prepare(
+                "SELECT name FROM main.sqlite_master WHERE type = 'table' \
+                 AND sql NOT LIKE 'CREATE VIRTUAL TABLE%' ORDER BY rowid",
+            )?;
+            let tables = stmt.query_map([], |row| row.get(0))?;
+            check.tables = Some(tables.collect::<Result<_>>()?);
+        }
+
+        let granularity = max_vm_steps.clamp(1, 1000);
+        let exceeded = Arc::new(AtomicBool::new(false));
+        {
+            let used = AtomicU64::new(0);
+            let exceeded = Arc::clone(&exceeded);
+            self.progress_handler(
+                granularity as c_int,
+                Some(move || {
+                    let used = used.fetch_add(granularity, Ordering::Relaxed) + granularity;
+                    let stop = used > max_vm_steps;
+                    if stop {
+                        exceeded.store(true, Ordering::Relaxed);
+                    }
+                    stop
+                }),
+            );
+        }
+        let r = self.check_tables(check, &exceeded);
+        self.progress_handler(0, None::<fn() -> bool>);
+        r
+    }
+
+    fn check_tables(&self, check: &mut IncrementalCheck, exceeded: &AtomicBool) -> Result<bool> {
+        let tables = check.tables.as_deref().unwrap_or_default();
+        let first = check.checked;
+        while let Some(table) = tables.get(check.checked) {
+            let sql = format!("PRAGMA main.quick_check({})", quote_identifier(table));
+            let problems = self.prepare(&sql).and_then(|mut stmt| {
+                stmt.query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<_>>>()
+            });
+            match problems {
+                Ok(problems) => {
+                    // a healthy table yields a single "ok" row
+                    if problems != ["ok"] {
+                        check.problems.extend(problems);
+                    }
+                    check.checked += 1;
+                }
+                Err(_) if exceeded.load(Ordering::Relaxed) => {
+                    if check.checked == first {
+                        return Err(Error::StepBudgetExceeded);
+                    }
+                    return Ok(false);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IncrementalCheck;
+    use crate::{Connection, Error, Result};
+
+    fn populated() -> Result<Connection> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE a(x INTEGER PRIMARY KEY, y TEXT);
+             CREATE INDEX a_y ON a(y);
+             CREATE TABLE b(z BLOB);
+             CREATE TABLE c(w);
+             WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000)
+             INSERT INTO a SELECT i, hex(randomblob(20)) FROM n;
+             INSERT INTO b SELECT randomblob(500) FROM a;
+             INSERT INTO c VALUES (1);",
+        )?;
+        Ok(db)
+    }
+
+    #[test]
+    fn test_integrity_check_incremental() -> Result<()> {
+        let db = populated()?;
+        let mut check = IncrementalCheck::new();
+        assert!(!check.is_complete());
+        assert_eq!((0, 0), check.progress());
+
+        // a budget too small for any table
+        assert_eq!(
+            Err(Error::StepBudgetExceeded),
+            db.integrity_check_incremental(&mut check, 10)
+        );
+        assert_eq!((0, 3), check.progress());
+
+        // slices big enough for one table but, typically, not for all of them
+        let mut slices = 0;
+        let budget = 200_000;
+        loop {
+            slices += 1;
+            match db.integrity_check_incremental(&mut check, budget) {
+                Ok(true) => break,
+                Ok(false) => assert!(check.progress().0 > 0),
+                Err(e) => return Err(e),
+            }
+            assert!(slices < 10);
+        }
+        assert!(check.is_complete());
+        assert_eq!((3, 3), check.progress());
+        assert!(check.problems().is_empty());
+
+        // no progress handler is left
+        db.execute_batch("INSERT INTO c SELECT y FROM a")?;
+        assert!(db.integrity_check_incremental(&mut IncrementalCheck::new(), u64::MAX)?);
+        Ok(())
+    }
+}
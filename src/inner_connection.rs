@@ -32,9 +32,29 @@ pub struct InnerConnection {
     #[cfg(feature = "hooks")]
     pub progress_handler: Option<Box<dyn FnMut() -> bool + Send>>,
     #[cfg(feature = "hooks")]
+    pub step_budget: Option<Arc<crate::hooks::StepBudget>>,
+    #[cfg(feature = "hooks")]
     pub authorizer: Option<crate::hooks::BoxedAuthorizer>,
     #[cfg(feature = "preupdate_hook")]
     pub free_preupdate_hook: Option<unsafe fn(*mut ::std::os::raw::c_void)>,
+    #[cfg(feature = "collation")]
+    pub collation_needed: Option<crate::collation::BoxedCollationNeeded>,
+    // name of the main database given to `SQLITE_DBCONFIG_MAINDBNAME`, which
+    // must stay valid until the connection is closed
+    pub main_db_name: Option<std::ffi::CString>,
+    // `SQLITE_CHECKPOINT_*` mode of the checkpoint run when closing
+    pub checkpoint_on_close: Option<c_int>,
+    // bind empty strings as NULL
+    pub empty_string_as_null: bool,
+    // how NaN doubles are bound
+    pub nan_policy: crate::config::NanPolicy,
+    // read 'true', 'no', ... as booleans
+    pub lenient_bool: bool,
+    // replace the SQL of tracing spans
+    #[cfg(feature = "tracing")]
+    pub tracing_redact_sql: bool,
+    #[cfg(feature = "metrics")]
+    pub metrics: crate::metrics::ConnectionMetrics,
     owned: bool,
 }
 
@@ -56,9 +76,22 @@ impl InnerConnection {
             #[cfg(feature = "hooks")]
             progress_handler: None,
             #[cfg(feature = "hooks")]
+            step_budget: None,
+            #[cfg(feature = "hooks")]
             authorizer: None,
             #[cfg(feature = "preupdate_hook")]
             free_preupdate_hook: None,
+            #[cfg(feature = "collation")]
+            collation_needed: None,
+            main_db_name: None,
+            checkpoint_on_close: None,
+            empty_string_as_null: false,
+            nan_policy: crate::config::NanPolicy::Null,
+            lenient_bool: false,
+            #[cfg(feature = "tracing")]
+            tracing_redact_sql: false,
+            #[cfg(feature = "metrics")]
+            metrics: crate::metrics::ConnectionMetrics::default(),
             owned,
         }
     }
@@ -134,6 +167,12 @@ impl InnerConnection {
 
     #[inline]
     pub fn decode_result(&self, code: c_int) -> Result<()> {
+        #[cfg(feature = "hooks")]
+        if code & 0xff == ffi::SQLITE_INTERRUPT
+            && self.step_budget.as_ref().is_some_and(|b| b.is_exceeded())
+        {
+            return Err(Error::StepBudgetExceeded);
+        }
         unsafe { InnerConnection::decode_result_raw(self.db(), code) }
     }
 
@@ -153,6 +192,8 @@ impl InnerConnection {
         }
         self.remove_hooks();
         self.remove_preupdate_hook();
+        #[cfg(feature = "collation")]
+        self.remove_collation_needed()?;
         let mut shared_handle = self.interrupt_lock.lock().unwrap();
         assert!(
             !shared_handle.is_null(),
@@ -163,6 +204,16 @@ impl InnerConnection {
             return Ok(());
         }
         unsafe {
+            if let Some(mode) = self.checkpoint_on_close {
+                // best effort: closing must not fail because of a busy WAL
+                ffi::sqlite3_wal_checkpoint_v2(
+                    self.db,
+                    ptr::null(),
+                    mode,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                );
+            }
             let r = ffi::sqlite3_close(self.db);
             // Need to use _raw because _guard has a reference out, and
             // decode_result takes &mut self.
@@ -226,6 +277,9 @@ impl InnerConnection {
         let mut c_stmt: *mut ffi::sqlite3_stmt = ptr::null_mut();
         let (c_sql, len, _) = str_for_sqlite(sql.as_bytes())?;
         let mut c_tail: *const c_char = ptr::null();
+        // e.g. left by a statement recompiled while stepping
+        #[cfg(feature = "collation")]
+        self.take_collation_needed_error();
         #[cfg(not(feature = "unlock_notify"))]
         let r = unsafe { self.prepare_(c_sql, len, flags, &mut c_stmt, &mut c_tail) };
         #[cfg(feature = "unlock_notify")]
@@ -246,6 +300,12 @@ impl InnerConnection {
         };
         // If there is an error, *ppStmt is set to NULL.
         if r != ffi::SQLITE_OK {
+            // the error of the collation needed callback tells more than "no
+            // such collation sequence"
+            #[cfg(feature = "collation")]
+            if let Some(err) = self.take_collation_needed_error() {
+                return Err(err);
+            }
             return Err(unsafe { error_with_offset(self.db, r, sql) });
         }
         // If the input text contains no SQL (if the input is an empty string or a
@@ -0,0 +1,196 @@
+//! `tracing` spans for the statements being prepared, executed and queried
+use std::time::Instant;
+
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::Connection;
+
+const REDACTED: &str = "<redacted>";
+
+// A span, along with the time it was created at, reported in its
+// `elapsed_us` field when it is finished.
+pub(crate) struct Timed {
+    span: Span,
+    start: Instant,
+}
+
+impl Timed {
+    #[inline]
+    pub(crate) fn prepare(conn: &Connection, sql: &str) -> Timed {
+        let sql = sql_field(conn, sql);
+        Timed::new(tracing::debug_span!(
+            "rusqlite::prepare",
+            sql,
+            elapsed_us = Empty
+        ))
+    }
+
+    #[inline]
+    pub(crate) fn execute(conn: &Connection, sql: &str) -> Timed {
+        let sql = sql_field(conn, sql);
+        Timed::new(tracing::debug_span!(
+            "rusqlite::execute",
+            sql,
+            elapsed_us = Empty
+        ))
+    }
+
+    #[inline]
+    pub(crate) fn query(conn: &Connection, sql: &str) -> Timed {
+        let sql = sql_field(conn, sql);
+        Timed::new(tracing::debug_span!(
+            "rusqlite::query",
+            sql,
+            elapsed_us = Empty
+        ))
+    }
+
+    fn new(span: Span) -> Timed {
+        Timed {
+            span,
+            start: Instant::now(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.span.in_scope(f)
+    }
+
+    pub(crate) fn finish(self) {
+        let elapsed = u64::try_from(self.start.elapsed().as_micros()).unwrap_or(u64::MAX);
+        self.span.record("elapsed_us", elapsed);
+    }
+}
+
+fn sql_field<'s>(conn: &Connection, sql: &'s str) -> &'s str {
+    if conn.db.borrow().tracing_redact_sql {
+        REDACTED
+    } else {
+        sql
+    }
+}
+
+impl Connection {
+    /// Replace the SQL text of the `tracing` spans with `<redacted>`, for
+    /// statements which may embed sensitive values. Off by default.
+    ///
+    /// With the `tracing` feature, a `debug` span is emitted each time a
+    /// statement is prepared (`rusqlite::prepare`), executed
+    /// (`rusqlite::execute`) or queried (`rusqlite::query`). Spans have a
+    /// `sql` field, and an `elapsed_us` field recording the time spent, in
+    /// microseconds, when they close. Query spans last until all the rows
+    /// have been retrieved, or until the [`Rows`](crate::Rows) are dropped.
+    /// Parameter values are never recorded.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+    #[inline]
+    pub fn set_tracing_redact_sql(&self, on: bool) {
+        self.db.borrow_mut().tracing_redact_sql = on;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::{Connection, Result};
+
+    #[derive(Debug, Default)]
+    struct Recorded {
+        name: &'static str,
+        fields: HashMap<&'static str, String>,
+    }
+
+    impl Visit for Recorded {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields.insert(field.name(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields.insert(field.name(), value.to_owned());
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<Recorded>>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut recorded = Recorded {
+                name: span.metadata().name(),
+                ..Recorded::default()
+            };
+            span.record(&mut recorded);
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(recorded);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            values.record(&mut spans[span.into_u64() as usize - 1]);
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_tracing_spans() -> Result<()> {
+        let recorder = Recorder::default();
+        let spans = recorder.spans.clone();
+        tracing::subscriber::with_default(recorder, || -> Result<()> {
+            let db = Connection::open_in_memory()?;
+            db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+            spans.lock().unwrap().clear();
+
+            db.execute("INSERT INTO foo VALUES (?1)", [1])?;
+            let mut stmt = db.prepare("SELECT x FROM foo")?;
+            let xs: Vec<i64> = stmt.query_map([], |r| r.get(0))?.collect::<Result<_>>()?;
+            assert_eq!(vec![1], xs);
+
+            db.set_tracing_redact_sql(true);
+            db.execute("INSERT INTO foo VALUES ('secret')", [])?;
+            Ok(())
+        })?;
+
+        let spans = spans.lock().unwrap();
+        let summary: Vec<_> = spans
+            .iter()
+            .map(|s| (s.name, s.fields["sql"].as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("rusqlite::prepare", "INSERT INTO foo VALUES (?1)"),
+                ("rusqlite::execute", "INSERT INTO foo VALUES (?1)"),
+                ("rusqlite::prepare", "SELECT x FROM foo"),
+                ("rusqlite::query", "SELECT x FROM foo"),
+                ("rusqlite::prepare", "<redacted>"),
+                ("rusqlite::execute", "<redacted>"),
+            ],
+            summary
+        );
+        for span in spans.iter() {
+            span.fields["elapsed_us"].parse::<u64>().unwrap();
+        }
+        Ok(())
+    }
+}
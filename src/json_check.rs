@@ -0,0 +1,61 @@
+use crate::{quote_identifier, Connection, Result};
+
+impl Connection {
+    /// Find the rows of `table` (in the main database) whose `column` holds
+    /// malformed JSON, and return their rowid along with the offending text,
+    /// in rowid order.
+    ///
+    /// A value is malformed if `json_valid` says so: `NULL`s and numbers are
+    /// not reported.
+    ///
+    /// Requires SQLite 3.38.0 or later, or an older SQLite built with the
+    /// JSON1 extension.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `table` or `column` does not exist, if `table`
+    /// has no rowid, or if a malformed value is not valid UTF-8.
+    pub fn find_invalid_json(&self, table: &str, column: &str) -> Result<Vec<(i64, String)>> {
+        // qualified, so that an unknown column is not taken for a string
+        // literal
+        let column = format!("t.{}", quote_identifier(column));
+        let sql = format!(
+            "SELECT t.rowid, {column} FROM main.{} AS t \
+             WHERE {column} IS NOT NULL AND json_valid({column}) = 0 ORDER BY t.rowid",
+            quote_identifier(table)
+        );
+        let mut stmt = self.prepare(&sql)?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Connection, Result};
+
+    #[test]
+    fn test_find_invalid_json() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            r#"CREATE TABLE "order"(id INTEGER PRIMARY KEY, "data" TEXT);
+               INSERT INTO "order" VALUES
+                   (1, '{"a": 1}'),
+                   (2, '{"a": '),
+                   (3, NULL),
+                   (4, '[1, 2, 3]'),
+                   (5, 'not json'),
+                   (6, 42);"#,
+        )?;
+        assert_eq!(
+            vec![(2, r#"{"a": "#.to_owned()), (5, "not json".to_owned())],
+            db.find_invalid_json("order", "data")?
+        );
+
+        db.execute_batch(r#"DELETE FROM "order" WHERE id IN (2, 5)"#)?;
+        assert!(db.find_invalid_json("order", "data")?.is_empty());
+        db.find_invalid_json("order", "missing").unwrap_err();
+        db.find_invalid_json("missing", "data").unwrap_err();
+        Ok(())
+    }
+}
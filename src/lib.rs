@@ -72,19 +72,42 @@ use crate::inner_connection::InnerConnection;
 use crate::raw_statement::RawStatement;
 use crate::types::ValueRef;
 
+pub use crate::attach::AttachGuard;
+pub use crate::bound_statement::BoundStatement;
 pub use crate::cache::CachedStatement;
 #[cfg(feature = "column_decltype")]
 pub use crate::column::Column;
+#[cfg(feature = "column_metadata")]
+pub use crate::column_metadata::ColumnMetadata;
+#[cfg(feature = "encryption")]
+pub use crate::encryption::EncryptionKey;
 pub use crate::error::{to_sqlite_error, Error};
 pub use crate::ffi::ErrorCode;
+pub use crate::file_probe::FileProbe;
+#[cfg(feature = "hooks")]
+pub use crate::incremental_check::IncrementalCheck;
 #[cfg(feature = "load_extension")]
 pub use crate::load_extension_guard::LoadExtensionGuard;
-pub use crate::params::{params_from_iter, Params, ParamsFromIter};
-pub use crate::row::{AndThenRows, Map, MappedRows, Row, RowIndex, Rows};
-pub use crate::statement::{Statement, StatementStatus};
+pub use crate::multi_statement::MultiStatement;
+pub use crate::open_statements::OpenStatement;
+pub use crate::params::{expand_in_clause, params_from_iter, Params, ParamsFromIter};
+pub use crate::pragma::{
+    blob_literal, quote_identifier, quote_string_literal, AutoVacuum, CacheSize, CheckpointMode,
+    ColumnInfo, Encoding, FkViolation, FunctionInfo, IndexColumn, IndexInfo, OptimizeFlags,
+    Stat1Row, TempStore, WalCheckpoint,
+};
+pub use crate::row::{
+    AndThenRows, Chunks, ColumnarResult, FromRow, InterruptibleRows, Map, MappedRows, OwnedRow,
+    Row, RowIndex, Rows,
+};
+pub use crate::schema::{SchemaObject, SchemaObjectType};
+pub use crate::statement::{BytecodeOp, Statement, StatementStatus};
+pub use crate::table_diff::TableDiff;
 #[cfg(feature = "modern_sqlite")]
 pub use crate::transaction::TransactionState;
-pub use crate::transaction::{DropBehavior, Savepoint, Transaction, TransactionBehavior};
+pub use crate::transaction::{
+    DropBehavior, Savepoint, Transaction, TransactionBehavior, TransactionUnit,
+};
 pub use crate::types::ToSql;
 pub use crate::version::*;
 #[cfg(feature = "rusqlite-macros")]
@@ -93,6 +116,7 @@ pub use rusqlite_macros::__bind;
 
 mod error;
 
+mod attach;
 #[cfg(not(feature = "loadable_extension"))]
 pub mod auto_extension;
 #[cfg(feature = "backup")]
@@ -101,38 +125,70 @@ pub mod backup;
 #[cfg(feature = "blob")]
 #[cfg_attr(docsrs, doc(cfg(feature = "blob")))]
 pub mod blob;
+mod bound_statement;
 mod busy;
 mod cache;
 #[cfg(feature = "collation")]
 #[cfg_attr(docsrs, doc(cfg(feature = "collation")))]
 mod collation;
 mod column;
+#[cfg(feature = "column_metadata")]
+#[cfg_attr(docsrs, doc(cfg(feature = "column_metadata")))]
+mod column_metadata;
 pub mod config;
 #[cfg(any(feature = "functions", feature = "vtab"))]
 mod context;
+#[cfg(feature = "encryption")]
+#[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+mod encryption;
+mod file_probe;
 #[cfg(feature = "functions")]
 #[cfg_attr(docsrs, doc(cfg(feature = "functions")))]
 pub mod functions;
 #[cfg(feature = "hooks")]
 #[cfg_attr(docsrs, doc(cfg(feature = "hooks")))]
 pub mod hooks;
+#[cfg(feature = "hooks")]
+mod incremental_check;
 mod inner_connection;
+#[cfg(feature = "tracing")]
+mod instrument;
+mod json_check;
 #[cfg(feature = "limits")]
 #[cfg_attr(docsrs, doc(cfg(feature = "limits")))]
 pub mod limits;
 #[cfg(feature = "load_extension")]
 mod load_extension_guard;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub mod metrics;
+#[cfg(feature = "migrations")]
+#[cfg_attr(docsrs, doc(cfg(feature = "migrations")))]
+pub mod migrations;
+mod multi_statement;
+mod open_statements;
 mod params;
+#[cfg(feature = "pool")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
+pub mod pool;
 mod pragma;
 mod raw_statement;
 mod row;
+mod schema;
 #[cfg(feature = "serialize")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serialize")))]
 pub mod serialize;
 #[cfg(feature = "session")]
 #[cfg_attr(docsrs, doc(cfg(feature = "session")))]
 pub mod session;
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub mod snapshot;
 mod statement;
+#[cfg(feature = "stream")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+pub mod stream;
+mod table_diff;
 #[cfg(feature = "trace")]
 #[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
 pub mod trace;
@@ -140,6 +196,7 @@ mod transaction;
 pub mod types;
 #[cfg(feature = "unlock_notify")]
 mod unlock_notify;
+mod upsert;
 mod version;
 #[cfg(feature = "vtab")]
 #[cfg_attr(docsrs, doc(cfg(feature = "vtab")))]
@@ -222,6 +279,61 @@ macro_rules! named_params {
     };
 }
 
+/// A macro declaring a struct along with a `params` method returning its
+/// fields, in declaration order, as positional parameters.
+///
+/// The generated method returns a `[&dyn ToSql; N]`, where `N` is the number
+/// of fields: use `&value.params()[..]` to get the same `&[&dyn ToSql]` as
+/// [`params!`]. Only structs with named fields and without generic
+/// parameters are supported.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use rusqlite::{Result, Connection, params_from_struct};
+///
+/// params_from_struct! {
+///     #[derive(Debug)]
+///     struct Person {
+///         name: String,
+///         age_in_years: u8,
+///         data: Option<Vec<u8>>,
+///     }
+/// }
+///
+/// fn add_person(conn: &Connection, person: &Person) -> Result<()> {
+///     conn.execute(
+///         "INSERT INTO person(name, age_in_years, data) VALUES (?1, ?2, ?3)",
+///         &person.params()[..],
+///     )?;
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! params_from_struct {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        impl $name {
+            /// The fields of this struct, in declaration order, as positional
+            /// parameters.
+            #[allow(dead_code)]
+            #[must_use]
+            $vis fn params(&self) -> [&dyn $crate::ToSql; <[&str]>::len(&[$(stringify!($field)),*])] {
+                [$(&self.$field as &dyn $crate::ToSql),*]
+            }
+        }
+    };
+}
+
 /// Captured identifiers in SQL
 ///
 /// * only SQLite `$x` / `@x` / `:x` syntax works (Rust `&x` syntax does not
@@ -340,6 +452,37 @@ fn path_to_cstring(p: &Path) -> Result<CString> {
     Ok(CString::new(s)?)
 }
 
+// The first complete statement of `sql` (up to and including its `;`), or all
+// of `sql` if there is none.
+fn statement_text(sql: &str) -> &str {
+    for (i, _) in sql.match_indices(';') {
+        let text = &sql[..=i];
+        let complete = match CString::new(text) {
+            Ok(c) => unsafe { ffi::sqlite3_complete(c.as_ptr()) != 0 },
+            Err(_) => false,
+        };
+        if complete {
+            return text;
+        }
+    }
+    sql
+}
+
+/// Attempts to free as much heap memory as possible held by SQLite but not
+/// essential to its operation (e.g. cache pages of all connections).
+///
+/// Returns the number of bytes actually freed, which may be zero unless SQLite
+/// was compiled with `SQLITE_ENABLE_MEMORY_MANAGEMENT`.
+///
+/// This calls [`sqlite3_release_memory`](https://www.sqlite.org/c3ref/release_memory.html).
+/// See also [`Connection::release_memory`] for a per-connection variant.
+#[inline]
+#[cfg(feature = "release_memory")]
+#[cfg_attr(docsrs, doc(cfg(feature = "release_memory")))]
+pub fn release_memory() -> i32 {
+    unsafe { ffi::sqlite3_release_memory(c_int::MAX) }
+}
+
 /// Name for a database within a SQLite connection.
 #[derive(Copy, Clone, Debug)]
 pub enum DatabaseName<'a> {
@@ -500,6 +643,42 @@ impl Connection {
         })
     }
 
+    /// Open a new connection to a SQLite database, then configure it with
+    /// `setup` (e.g. to set `busy_timeout`, `journal_mode` or
+    /// `foreign_keys`).
+    ///
+    /// Either a fully configured connection is returned, or the connection is
+    /// closed and the error returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, OpenFlags, Result};
+    /// # use std::time::Duration;
+    /// fn open_app_db() -> Result<Connection> {
+    ///     Connection::open_with_setup("app.db3", OpenFlags::default(), |conn| {
+    ///         conn.busy_timeout(Duration::from_secs(5))?;
+    ///         conn.pragma_update(None, "journal_mode", "WAL")?;
+    ///         conn.pragma_update(None, "foreign_keys", true)
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if opening fails (see
+    /// [`Connection::open_with_flags`]) or if `setup` fails.
+    #[inline]
+    pub fn open_with_setup<P, F>(path: P, flags: OpenFlags, setup: F) -> Result<Connection>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&Connection) -> Result<()>,
+    {
+        let conn = Connection::open_with_flags(path, flags)?;
+        setup(&conn)?;
+        Ok(conn)
+    }
+
     /// Open a new connection to an in-memory SQLite database.
     ///
     /// [Database Connection](http://www.sqlite.org/c3ref/open.html) for a description of valid
@@ -566,6 +745,147 @@ impl Connection {
         Ok(())
     }
 
+    /// Like [`Connection::execute_batch`], but return the number of rows
+    /// modified, inserted or deleted by each statement of `sql` (including
+    /// the changes made by triggers), and report which statement failed.
+    ///
+    /// Empty statements (e.g. `;;`) are skipped and do not count.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Error, Result};
+    /// fn apply(conn: &Connection, script: &str) -> Result<()> {
+    ///     match conn.execute_batch_with_results(script) {
+    ///         Ok(changes) => println!("{} statements applied", changes.len()),
+    ///         Err(Error::BatchStatementFailed { index, sql, error }) => {
+    ///             eprintln!("statement {index} failed: {error}\n{sql}");
+    ///         }
+    ///         Err(e) => return Err(e),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::BatchStatementFailed`], with the 1-based index
+    /// and the text of the statement, if a statement cannot be prepared or
+    /// fails. The statements before it stay applied.
+    #[inline]
+    pub fn execute_batch_with_results(&self, sql: &str) -> Result<Vec<usize>> {
+        self.run_batch(sql, |_, _| {})
+    }
+
+    /// Like [`Connection::execute_batch`], but call `progress` before running
+    /// each statement of `sql`, with its 1-based index and its text
+    /// (including the comments before it), e.g. to report the progress of
+    /// loading a large SQL dump.
+    ///
+    /// Empty statements (e.g. `;;`) are skipped and do not count.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn load_dump(conn: &Connection, dump: &str) -> Result<()> {
+    ///     conn.execute_batch_with_progress(dump, |i, _| {
+    ///         if i % 100 == 0 {
+    ///             println!("applying statement {i}");
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return [`Error::BatchStatementFailed`] if a statement cannot be
+    /// prepared or fails, see [`Connection::execute_batch_with_results`].
+    #[inline]
+    pub fn execute_batch_with_progress<F>(&self, sql: &str, progress: F) -> Result<()>
+    where
+        F: FnMut(usize, &str),
+    {
+        self.run_batch(sql, progress).map(|_| ())
+    }
+
+    fn run_batch<F>(&self, sql: &str, mut before_step: F) -> Result<Vec<usize>>
+    where
+        F: FnMut(usize, &str),
+    {
+        let mut results = Vec::new();
+        let mut sql = sql;
+        while !sql.is_empty() {
+            let failed = |text: &str, error: Error| Error::BatchStatementFailed {
+                index: results.len() + 1,
+                sql: text.trim().to_owned(),
+                error: Box::new(error),
+            };
+            let stmt = match self.prepare(sql) {
+                Ok(stmt) => stmt,
+                Err(e) => return Err(failed(statement_text(sql), e)),
+            };
+            let tail = stmt.stmt.tail();
+            let text = if tail == 0 || tail >= sql.len() {
+                sql
+            } else {
+                &sql[..tail]
+            };
+            if !stmt.stmt.is_null() {
+                before_step(results.len() + 1, text.trim());
+                let before = self.total_changes();
+                match stmt.step() {
+                    Ok(true) if cfg!(feature = "extra_check") => {
+                        return Err(failed(text, Error::ExecuteReturnedResults));
+                    }
+                    Ok(_) => {}
+                    Err(e) => return Err(failed(text, e)),
+                }
+                results.push((self.total_changes() - before) as usize);
+            }
+            if text.len() == sql.len() {
+                break;
+            }
+            sql = &sql[tail..];
+        }
+        Ok(results)
+    }
+
+    /// Prepare (without executing) each statement of `sql` and check whether
+    /// all of them are read-only, that is, do not directly modify the
+    /// database.
+    ///
+    /// This is a lightweight way to reject writes from untrusted input; see
+    /// [`Statement::readonly`] for the exact semantics of each check.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn run_report(conn: &Connection, sql: &str) -> Result<()> {
+    ///     if !conn.prepare_readonly_check(sql)? {
+    ///         return Err(rusqlite::Error::InvalidQuery);
+    ///     }
+    ///     // ...
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `sql` cannot be converted to a C-compatible string
+    /// or if any statement fails to prepare. Because nothing is executed,
+    /// this includes statements referring to objects created earlier in `sql`.
+    pub fn prepare_readonly_check(&self, sql: &str) -> Result<bool> {
+        let mut batch = Batch::new(self, sql);
+        while let Some(stmt) = batch.next()? {
+            if !stmt.readonly() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     /// Convenience method to prepare and execute a single SQL statement.
     ///
     /// On success, returns the number of rows that were changed or inserted or
@@ -622,6 +942,45 @@ impl Connection {
             .and_then(|mut stmt| stmt.check_no_tail().and_then(|()| stmt.execute(params)))
     }
 
+    /// Like [`Connection::execute`], but lock contention is reported as
+    /// [`Error::DatabaseBusy`] instead of [`Error::SqliteFailure`], so that
+    /// it can be told apart from constraint violations, syntax errors, etc.
+    ///
+    /// Note that the [busy timeout](Connection::busy_timeout) still applies
+    /// before `SQLITE_BUSY` is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Error, Result};
+    /// fn bump(conn: &Connection) -> Result<usize> {
+    ///     loop {
+    ///         match conn.execute_checked("UPDATE counter SET n = n + 1", []) {
+    ///             Err(Error::DatabaseBusy(..)) => continue,
+    ///             r => return r,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err(Error::DatabaseBusy(..))` if the database is busy or
+    /// locked, or any other error [`Connection::execute`] returns.
+    pub fn execute_checked<P: Params>(&self, sql: &str, params: P) -> Result<usize> {
+        self.execute(sql, params).map_err(|e| match e {
+            Error::SqliteFailure(err, msg)
+                if matches!(
+                    err.code,
+                    ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked
+                ) =>
+            {
+                Error::DatabaseBusy(err, msg)
+            }
+            e => e,
+        })
+    }
+
     /// Returns the path to the database file, if one exists and is known.
     ///
     /// Returns `Some("")` for a temporary or in-memory database.
@@ -647,8 +1006,13 @@ impl Connection {
     /// connection.
     ///
     /// This calls [`sqlite3_db_release_memory`](https://www.sqlite.org/c3ref/db_release_memory.html).
+    /// Unlike the global [`release_memory`], this does not return the number
+    /// of bytes freed: `sqlite3_db_release_memory` only returns a status code
+    /// (always `SQLITE_OK` in current SQLite versions), and no other API
+    /// reports the memory freed for a single connection.
     #[inline]
     #[cfg(feature = "release_memory")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "release_memory")))]
     pub fn release_memory(&self) -> Result<()> {
         self.db.borrow_mut().release_memory()
     }
@@ -745,6 +1109,55 @@ impl Connection {
         rows.get_expected_row().map_err(E::from).and_then(f)
     }
 
+    /// Convenience method to run a statement returning exactly one value,
+    /// like `INSERT ... RETURNING id` or `UPDATE ... RETURNING updated_at`,
+    /// and return the value of the first column of its single row.
+    ///
+    /// The statement runs within a savepoint, so its changes are rolled back
+    /// if it fails or returns more than one row.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn create_user(conn: &Connection, name: &str) -> Result<i64> {
+    ///     conn.execute_returning_scalar("INSERT INTO user(name) VALUES (?1) RETURNING id", [name])
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err(QueryReturnedNoRows)` if the statement returns no
+    /// row, and `Err(QueryReturnedMoreThanOneRow)` if it returns more than
+    /// one (the statement is run to completion to count them, then its
+    /// changes are rolled back). Will also return `Err` if `sql` cannot be
+    /// converted to a C-compatible string, if the value cannot be converted
+    /// to `T`, or if the underlying SQLite call fails.
+    pub fn execute_returning_scalar<T, P>(&self, sql: &str, params: P) -> Result<T>
+    where
+        T: types::FromSql,
+        P: Params,
+    {
+        let sp = Savepoint::new_(self)?;
+        let value = {
+            let mut stmt = self.prepare(sql)?;
+            stmt.check_no_tail()?;
+            let mut rows = stmt.query(params)?;
+            let value = rows.get_expected_row()?.get(0)?;
+            let mut n = 1;
+            while rows.next()?.is_some() {
+                n += 1;
+            }
+            if n > 1 {
+                return Err(Error::QueryReturnedMoreThanOneRow(n));
+            }
+            value
+        };
+        // dropping the savepoint on errors rolls it back
+        sp.commit()?;
+        Ok(value)
+    }
+
     /// Prepare a SQL statement for execution.
     ///
     /// ## Example
@@ -776,18 +1189,70 @@ impl Connection {
     /// or if the underlying SQLite call fails.
     #[inline]
     pub fn prepare_with_flags(&self, sql: &str, flags: PrepFlags) -> Result<Statement<'_>> {
+        #[cfg(feature = "metrics")]
+        self.record_metrics(|m| m.prepares += 1);
+        #[cfg(feature = "tracing")]
+        {
+            let span = instrument::Timed::prepare(self, sql);
+            let stmt = span.in_scope(|| self.db.borrow_mut().prepare(self, sql, flags));
+            span.finish();
+            stmt
+        }
+        #[cfg(not(feature = "tracing"))]
         self.db.borrow_mut().prepare(self, sql, flags)
     }
 
+    /// Check that `sql` is valid, by preparing (and then finalizing) each of
+    /// its statements without running them.
+    ///
+    /// Besides syntax errors, this catches references to unknown tables,
+    /// columns or functions. As nothing is run, a statement referring to a
+    /// table created by a previous statement of `sql` is reported as invalid
+    /// if the table does not exist yet.
+    ///
+    /// # Failure
+    ///
+    /// Will return the error of the first invalid statement. With the
+    /// `modern_sqlite` feature, the error is an `Error::SqlInputError` when
+    /// SQLite reports where the problem is, with the offset of the invalid
+    /// token in `sql` as a whole.
+    pub fn validate_sql(&self, sql: &str) -> Result<()> {
+        let mut start = 0;
+        while start < sql.len() {
+            let stmt = self.prepare(&sql[start..]).map_err(|e| match e {
+                #[cfg(feature = "modern_sqlite")]
+                Error::SqlInputError {
+                    error, msg, offset, ..
+                } => Error::SqlInputError {
+                    error,
+                    msg,
+                    sql: sql.to_owned(),
+                    offset: offset + start as c_int,
+                },
+                e => e,
+            })?;
+            match stmt.stmt.tail() {
+                0 => break,
+                tail => start += tail,
+            }
+        }
+        Ok(())
+    }
+
     /// Close the SQLite connection.
     ///
     /// This is functionally equivalent to the `Drop` implementation for
     /// `Connection` except that on failure, it returns an error and the
     /// connection itself (presumably so closing can be attempted again).
+    /// `Drop` ignores the error, leaving the connection open.
     ///
     /// # Failure
     ///
-    /// Will return `Err` if the underlying SQLite call fails.
+    /// Will return `Err` if the underlying SQLite call fails, e.g.
+    /// `SQLITE_BUSY` if statements, BLOB handles or backups created through
+    /// the raw [`handle`](Connection::handle) have not been finalized: the
+    /// cached statements are finalized, but the hooks and callbacks set on
+    /// the connection have been removed when it is returned.
     #[inline]
     pub fn close(self) -> Result<(), (Connection, Error)> {
         self.flush_prepared_statement_cache();
@@ -991,6 +1456,64 @@ impl Connection {
         self.db.borrow().get_interrupt_handle()
     }
 
+    /// Run `f` with a time limit: if it has not returned after `timeout`, the
+    /// statement it is running is interrupted (see
+    /// [`InterruptHandle::interrupt`]) and `Err(Error::QueryTimeout)` is
+    /// returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Error, Result};
+    /// # use std::time::Duration;
+    /// fn count_or_give_up(conn: &Connection) -> Result<Option<i64>> {
+    ///     match conn.with_query_timeout(Duration::from_secs(1), |conn| {
+    ///         conn.query_row("SELECT COUNT(*) FROM big_table", [], |r| r.get(0))
+    ///     }) {
+    ///         Ok(n) => Ok(Some(n)),
+    ///         Err(Error::QueryTimeout) => Ok(None),
+    ///         Err(e) => Err(e),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err(Error::QueryTimeout)` if `f` failed because it was
+    /// interrupted after `timeout`, or any other error returned by `f`.
+    pub fn with_query_timeout<T, F>(&self, timeout: std::time::Duration, f: F) -> Result<T>
+    where
+        F: FnOnce(&Connection) -> Result<T>,
+    {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::mpsc;
+
+        let handle = self.get_interrupt_handle();
+        let timed_out = Arc::new(AtomicBool::new(false));
+        let (done, done_rx) = mpsc::channel::<()>();
+        let timer = {
+            let timed_out = Arc::clone(&timed_out);
+            std::thread::spawn(move || {
+                if let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(timeout) {
+                    timed_out.store(true, Ordering::SeqCst);
+                    handle.interrupt();
+                }
+            })
+        };
+        let r = f(self);
+        drop(done);
+        timer.join().unwrap();
+        match r {
+            Err(e)
+                if timed_out.load(Ordering::SeqCst)
+                    && e.sqlite_error_code() == Some(ErrorCode::OperationInterrupted) =>
+            {
+                Err(Error::QueryTimeout)
+            }
+            r => r,
+        }
+    }
+
     #[inline]
     fn decode_result(&self, code: c_int) -> Result<()> {
         self.db.borrow().decode_result(code)
@@ -1016,6 +1539,32 @@ impl Connection {
         self.db.borrow().total_changes()
     }
 
+    /// Run `f` and return its result along with the number of rows it
+    /// modified, inserted or deleted, as counted by
+    /// [`total_changes`](Connection::total_changes).
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn migrate(conn: &Connection) -> Result<bool> {
+    ///     let ((), changed) = conn.with_change_tracking(|conn| {
+    ///         conn.execute_batch("UPDATE user SET email = lower(email)")
+    ///     })?;
+    ///     Ok(changed > 0)
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `f` fails.
+    pub fn with_change_tracking<T, F>(&self, f: F) -> Result<(T, u64)>
+    where
+        F: FnOnce(&Connection) -> Result<T>,
+    {
+        let before = self.total_changes();
+        let t = f(self)?;
+        Ok((t, self.total_changes() - before))
+    }
+
     /// Test for auto-commit mode.
     /// Autocommit mode is on by default.
     #[inline]
@@ -1029,7 +1578,17 @@ impl Connection {
         self.db.borrow().is_busy()
     }
 
-    /// Flush caches to disk mid-transaction
+    /// Flush caches to disk mid-transaction.
+    ///
+    /// Dirty pages of all attached databases are written to disk without
+    /// committing the current transaction, which stays open.
+    ///
+    /// See [`sqlite3_db_cacheflush`](https://www.sqlite.org/c3ref/db_cacheflush.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if an I/O error (or `SQLITE_BUSY`) is encountered
+    /// while flushing.
     pub fn cache_flush(&self) -> Result<()> {
         self.db.borrow_mut().cache_flush()
     }
@@ -1219,6 +1778,146 @@ impl Default for OpenFlags {
     }
 }
 
+impl OpenFlags {
+    /// Start composing flags with an [`OpenFlagsBuilder`], which rejects
+    /// nonsensical combinations.
+    #[inline]
+    pub fn builder() -> OpenFlagsBuilder {
+        OpenFlagsBuilder(OpenFlags::empty())
+    }
+}
+
+/// Builder for [`OpenFlags`].
+///
+/// ```rust,no_run
+/// # use rusqlite::{Connection, OpenFlags, Result};
+/// fn open_shared(uri: &str) -> Result<Connection> {
+///     let flags = OpenFlags::builder()
+///         .read_write()
+///         .create()
+///         .uri()
+///         .shared_cache()
+///         .build()?;
+///     Connection::open_with_flags(uri, flags)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[must_use]
+pub struct OpenFlagsBuilder(OpenFlags);
+
+impl OpenFlagsBuilder {
+    /// [`OpenFlags::SQLITE_OPEN_READ_ONLY`]
+    #[inline]
+    pub fn read_only(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_READ_ONLY)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_READ_WRITE`]
+    #[inline]
+    pub fn read_write(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_READ_WRITE)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_CREATE`], requires
+    /// [`read_write`](OpenFlagsBuilder::read_write).
+    #[inline]
+    pub fn create(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_CREATE)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_URI`]
+    #[inline]
+    pub fn uri(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_URI)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_MEMORY`]
+    #[inline]
+    pub fn memory(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_MEMORY)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_NO_MUTEX`]
+    #[inline]
+    pub fn no_mutex(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_NO_MUTEX)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_FULL_MUTEX`]
+    #[inline]
+    pub fn full_mutex(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_FULL_MUTEX)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_SHARED_CACHE`]
+    #[inline]
+    pub fn shared_cache(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_SHARED_CACHE)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_PRIVATE_CACHE`]
+    #[inline]
+    pub fn private_cache(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_PRIVATE_CACHE)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_NOFOLLOW`]
+    #[inline]
+    pub fn no_follow(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_NOFOLLOW)
+    }
+
+    /// [`OpenFlags::SQLITE_OPEN_EXRESCODE`]
+    #[inline]
+    pub fn extended_result_codes(self) -> Self {
+        self.with(OpenFlags::SQLITE_OPEN_EXRESCODE)
+    }
+
+    #[inline]
+    fn with(self, flags: OpenFlags) -> Self {
+        OpenFlagsBuilder(self.0 | flags)
+    }
+
+    /// Check and return the flags.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` with `SQLITE_MISUSE` unless exactly one of
+    /// `read_only` / `read_write` is set, if `create` is set without
+    /// `read_write`, or if both mutex or both cache flags are set.
+    pub fn build(self) -> Result<OpenFlags> {
+        let flags = self.0;
+        let check = |invalid: bool, msg: &str| {
+            if invalid {
+                Err(error::misuse(msg))
+            } else {
+                Ok(())
+            }
+        };
+        let read_only = flags.contains(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        let read_write = flags.contains(OpenFlags::SQLITE_OPEN_READ_WRITE);
+        check(
+            read_only == read_write,
+            "exactly one of read_only or read_write must be set",
+        )?;
+        check(
+            read_only && flags.contains(OpenFlags::SQLITE_OPEN_CREATE),
+            "create requires read_write",
+        )?;
+        check(
+            flags.contains(OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_FULL_MUTEX),
+            "no_mutex and full_mutex are mutually exclusive",
+        )?;
+        check(
+            flags.contains(
+                OpenFlags::SQLITE_OPEN_SHARED_CACHE | OpenFlags::SQLITE_OPEN_PRIVATE_CACHE,
+            ),
+            "shared_cache and private_cache are mutually exclusive",
+        )?;
+        Ok(flags)
+    }
+}
+
 bitflags::bitflags! {
     /// Prepare flags. See
     /// [sqlite3_prepare_v3](https://sqlite.org/c3ref/c_prepare_normalize.html) for details.
@@ -1329,6 +2028,36 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_execute_checked_busy() -> Result<()> {
+        use std::time::Duration;
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("busy.db3");
+        let db1 = Connection::open(&path)?;
+        db1.execute_batch("CREATE TABLE foo(x INTEGER PRIMARY KEY);")?;
+        let db2 = Connection::open(&path)?;
+        db2.busy_timeout(Duration::from_millis(0))?;
+
+        db1.execute_batch("BEGIN IMMEDIATE; INSERT INTO foo VALUES(1);")?;
+        let err = db2
+            .execute_checked("INSERT INTO foo VALUES(?1)", [2])
+            .unwrap_err();
+        assert!(matches!(err, Error::DatabaseBusy(..)), "{err:?}");
+        assert_eq!(err.sqlite_error_code(), Some(ErrorCode::DatabaseBusy));
+        db1.execute_batch("COMMIT")?;
+
+        assert_eq!(1, db2.execute_checked("INSERT INTO foo VALUES(?1)", [2])?);
+        let err = db2
+            .execute_checked("INSERT INTO foo VALUES(?1)", [2])
+            .unwrap_err();
+        assert_eq!(
+            err.sqlite_error_code(),
+            Some(ErrorCode::ConstraintViolation)
+        );
+        assert!(!matches!(err, Error::DatabaseBusy(..)));
+        Ok(())
+    }
+
     #[test]
     fn test_persistence() -> Result<()> {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1421,6 +2150,39 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_sql() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+        db.validate_sql("SELECT x FROM foo WHERE x > ?1")?;
+        db.validate_sql("INSERT INTO foo VALUES (1); -- comment\n DELETE FROM foo;")?;
+        db.validate_sql("")?;
+        // nothing was run
+        assert_eq!(0, db.one_column::<i64>("SELECT count(*) FROM foo")?);
+
+        let sql = "SELECT 1; SELEC x FROM foo";
+        let err = db.validate_sql(sql).unwrap_err();
+        #[cfg(feature = "modern_sqlite")]
+        assert_eq!(
+            Error::SqlInputError {
+                error: ffi::Error::new(ffi::SQLITE_ERROR),
+                msg: "near \"SELEC\": syntax error".to_owned(),
+                sql: sql.to_owned(),
+                offset: 10,
+            },
+            err
+        );
+        #[cfg(not(feature = "modern_sqlite"))]
+        assert!(err.to_string().contains("syntax error"), "{err}");
+
+        let err = db.validate_sql("SELECT x FROM missing").unwrap_err();
+        assert!(err.to_string().contains("no such table: missing"), "{err}");
+        db.validate_sql("SELECT y FROM foo").unwrap_err();
+        db.validate_sql("SELECT 1; CREATE TABLE bar(y); INSERT INTO bar VALUES (1)")
+            .unwrap_err();
+        Ok(())
+    }
+
     #[test]
     fn test_close_retry() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1463,6 +2225,34 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_close_with_open_blob() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(b BLOB); INSERT INTO foo VALUES (x'0102');")?;
+        let raw_blob = unsafe {
+            let mut blob: *mut ffi::sqlite3_blob = std::ptr::null_mut();
+            let rc = ffi::sqlite3_blob_open(
+                db.handle(),
+                c"main".as_ptr(),
+                c"foo".as_ptr(),
+                c"b".as_ptr(),
+                1,
+                0,
+                &mut blob,
+            );
+            assert_eq!(ffi::SQLITE_OK, rc);
+            blob
+        };
+
+        let (db, err) = db.close().unwrap_err();
+        assert_eq!(Some(ErrorCode::DatabaseBusy), err.sqlite_error_code());
+        // the connection is still usable
+        assert_eq!(1, db.one_column::<i64>("SELECT count(*) FROM foo")?);
+
+        assert_eq!(ffi::SQLITE_OK, unsafe { ffi::sqlite3_blob_close(raw_blob) });
+        db.close().map_err(|(_, e)| e)
+    }
+
     #[test]
     fn test_open_with_flags() {
         for bad_flags in &[
@@ -1474,6 +2264,75 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_open_with_setup() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let mut calls = 0;
+        let db = Connection::open_with_setup(&path, OpenFlags::default(), |conn| {
+            calls += 1;
+            conn.pragma_update(None, "user_version", 42)
+        })?;
+        assert_eq!(1, calls);
+        let v: i32 = db.pragma_query_value(None, "user_version", |r| r.get(0))?;
+        assert_eq!(42, v);
+
+        let err = Connection::open_with_setup(&path, OpenFlags::default(), |conn| {
+            conn.execute_batch("not valid sql")
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("syntax error"), "{err:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_flags_builder() -> Result<()> {
+        let flags = OpenFlags::builder()
+            .read_write()
+            .create()
+            .uri()
+            .no_mutex()
+            .build()?;
+        assert_eq!(flags, OpenFlags::default());
+
+        let flags = OpenFlags::builder()
+            .read_write()
+            .create()
+            .uri()
+            .shared_cache()
+            .extended_result_codes()
+            .build()?;
+        let db1 = Connection::open_with_flags("file:builder_test?mode=memory", flags)?;
+        let db2 = Connection::open_with_flags("file:builder_test?mode=memory", flags)?;
+        db1.execute_batch("CREATE TABLE foo(x)")?;
+        db2.execute_batch("INSERT INTO foo VALUES (1)")?;
+
+        let flags = OpenFlags::builder().read_only().memory().build()?;
+        assert_eq!(
+            flags,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_MEMORY
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_flags_builder_invalid() {
+        for invalid in [
+            OpenFlags::builder(),
+            OpenFlags::builder().create(),
+            OpenFlags::builder().read_only().create(),
+            OpenFlags::builder().read_only().read_write(),
+            OpenFlags::builder().read_write().no_mutex().full_mutex(),
+            OpenFlags::builder()
+                .read_write()
+                .shared_cache()
+                .private_cache(),
+        ] {
+            let err = invalid.build().unwrap_err();
+            assert_eq!(err.sqlite_error_code(), Some(ErrorCode::ApiMisuse));
+        }
+    }
+
     #[test]
     fn test_execute_batch() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1492,6 +2351,151 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_execute_batch_with_results() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let changes = db.execute_batch_with_results(
+            "CREATE TABLE foo(x INTEGER);
+             INSERT INTO foo VALUES (1), (2);;
+             UPDATE foo SET x = x + 1",
+        )?;
+        assert_eq!(vec![0, 2, 2], changes);
+
+        let err = db
+            .execute_batch_with_results(
+                "INSERT INTO foo VALUES (3);
+                 DELETE FROM foo WHERE x = 2;
+                 INSERT INTO missing VALUES (4);
+                 INSERT INTO foo VALUES (5);",
+            )
+            .unwrap_err();
+        match err {
+            Error::BatchStatementFailed { index, ref sql, .. } => {
+                assert_eq!(3, index);
+                assert_eq!("INSERT INTO missing VALUES (4);", sql);
+            }
+            ref err => panic!("unexpected error {err:?}"),
+        }
+        assert!(err.to_string().contains("statement 3"), "{err}");
+        // the statements before the failed one were applied
+        let xs: i64 = db.one_column("SELECT count(*) FROM foo")?;
+        assert_eq!(2, xs);
+
+        let err = db
+            .execute_batch_with_results(
+                "DELETE FROM foo; INSERT INTO foo VALUES ('a;b'),; SELECT 2",
+            )
+            .unwrap_err();
+        match err {
+            Error::BatchStatementFailed { index, sql, .. } => {
+                assert_eq!(2, index);
+                assert_eq!("INSERT INTO foo VALUES ('a;b'),;", sql);
+            }
+            err => panic!("unexpected error {err:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_execute_batch_with_progress() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut calls = Vec::new();
+        db.execute_batch_with_progress(
+            "CREATE TABLE foo(x INTEGER);
+             INSERT INTO foo VALUES (1);
+             -- comment
+             ;
+             INSERT INTO foo VALUES (2);
+             UPDATE foo SET x = x + 1",
+            |i, sql| calls.push((i, sql.to_owned())),
+        )?;
+        assert_eq!(
+            vec![
+                (1, "CREATE TABLE foo(x INTEGER);".to_owned()),
+                (2, "INSERT INTO foo VALUES (1);".to_owned()),
+                // with the comments and empty statements before it
+                (
+                    3,
+                    "-- comment\n             ;\n             INSERT INTO foo VALUES (2);"
+                        .to_owned()
+                ),
+                (4, "UPDATE foo SET x = x + 1".to_owned()),
+            ],
+            calls
+        );
+
+        let mut n = 0;
+        db.execute_batch_with_progress(
+            "DELETE FROM foo; INSERT INTO missing VALUES (1)",
+            |_, _| {
+                n += 1;
+            },
+        )
+        .unwrap_err();
+        // not called for a statement which cannot be prepared
+        assert_eq!(1, n);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "modern_sqlite")] // RETURNING: 3.35.0
+    fn test_execute_returning_scalar() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE user(id INTEGER PRIMARY KEY, name TEXT)")?;
+        let id: i64 =
+            db.execute_returning_scalar("INSERT INTO user(name) VALUES (?1) RETURNING id", ["a"])?;
+        assert_eq!(1, id);
+        let id: i64 =
+            db.execute_returning_scalar("INSERT INTO user(name) VALUES (?1) RETURNING id", ["b"])?;
+        assert_eq!(2, id);
+        let name: String = db.execute_returning_scalar(
+            "UPDATE user SET name = upper(name) WHERE id = ?1 RETURNING name",
+            [2],
+        )?;
+        assert_eq!("B", name);
+
+        assert_eq!(
+            Err(Error::QueryReturnedNoRows),
+            db.execute_returning_scalar::<i64, _>(
+                "UPDATE user SET name = 'c' WHERE id = 3 RETURNING id",
+                []
+            )
+        );
+        assert_eq!(
+            Err(Error::QueryReturnedMoreThanOneRow(2)),
+            db.execute_returning_scalar::<i64, _>("UPDATE user SET name = 'd' RETURNING id", [])
+        );
+        // the changes were rolled back
+        let n: i64 = db.one_column("SELECT count(*) FROM user WHERE name = 'd'")?;
+        assert_eq!(0, n);
+
+        // within a transaction, only the statement is rolled back
+        let tx = db.unchecked_transaction()?;
+        db.execute("INSERT INTO user(name) VALUES ('e')", [])?;
+        db.execute_returning_scalar::<i64, _>("DELETE FROM user RETURNING id", [])
+            .unwrap_err();
+        tx.commit()?;
+        let n: i64 = db.one_column("SELECT count(*) FROM user")?;
+        assert_eq!(3, n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare_readonly_check() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER);")?;
+        assert!(db.prepare_readonly_check("SELECT x FROM foo")?);
+        assert!(db.prepare_readonly_check("SELECT 1; SELECT x FROM foo;")?);
+        assert!(!db.prepare_readonly_check("INSERT INTO foo VALUES(1)")?);
+        assert!(!db.prepare_readonly_check("SELECT 1; DELETE FROM foo")?);
+        assert!(!db.prepare_readonly_check("PRAGMA user_version = 1")?);
+        db.prepare_readonly_check("SELECT * FROM missing")
+            .unwrap_err();
+        // nothing was executed
+        assert_eq!(0, db.one_column::<i64>("PRAGMA user_version")?);
+        Ok(())
+    }
+
     #[test]
     fn test_execute() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1730,6 +2734,27 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_with_change_tracking() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (0);")?;
+        let (n, changes) = db.with_change_tracking(|db| {
+            let mut stmt = db.prepare("INSERT INTO foo VALUES (?1)")?;
+            for i in 1..=3 {
+                stmt.execute([i])?;
+            }
+            db.one_column::<i64>("SELECT count(*) FROM foo")
+        })?;
+        assert_eq!((4, 3), (n, changes));
+
+        let ((), changes) =
+            db.with_change_tracking(|db| db.execute_batch("DELETE FROM foo WHERE 0"))?;
+        assert_eq!(0, changes);
+        let r = db.with_change_tracking(|db| db.execute_batch("INSERT INTO missing VALUES (1)"));
+        r.unwrap_err();
+        Ok(())
+    }
+
     #[test]
     fn test_total_changes() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1846,6 +2871,28 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_with_query_timeout() -> Result<()> {
+        use std::time::Duration;
+        let db = Connection::open_in_memory()?;
+        let r: Result<i64> = db.with_query_timeout(Duration::from_millis(50), |db| {
+            db.one_column(
+                "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c)
+                 SELECT COUNT(*) FROM c",
+            )
+        });
+        assert_eq!(r, Err(Error::QueryTimeout));
+
+        let n: i64 =
+            db.with_query_timeout(Duration::from_secs(10), |db| db.one_column("SELECT 1"))?;
+        assert_eq!(n, 1);
+        let r: Result<i64> = db.with_query_timeout(Duration::from_secs(10), |db| {
+            db.one_column("SELECT * FROM missing")
+        });
+        assert!(r.is_err() && r != Err(Error::QueryTimeout));
+        Ok(())
+    }
+
     #[test]
     fn test_interrupt_close() {
         let db = checked_memory_handle();
@@ -2170,6 +3217,63 @@ mod test {
         )
     }
 
+    #[test]
+    fn test_params_from_struct() -> Result<()> {
+        params_from_struct! {
+            #[derive(Debug, Default)]
+            struct Person {
+                name: String,
+                /// in years
+                age: u8,
+                data: Option<Vec<u8>>,
+            }
+        }
+        params_from_struct! {
+            struct Empty {}
+        }
+
+        let person = Person {
+            name: "Ada".to_owned(),
+            age: 36,
+            data: Some(vec![1, 2]),
+        };
+        let params = person.params();
+        assert_eq!(3, params.len());
+        assert_eq!(0, Empty {}.params().len());
+        let values: Vec<_> = params.iter().map(|p| p.to_sql().unwrap()).collect();
+        assert_eq!(
+            vec![
+                crate::types::ToSqlOutput::from("Ada"),
+                crate::types::ToSqlOutput::from(36u8),
+                crate::types::ToSqlOutput::from(&[1u8, 2][..]),
+            ],
+            values
+        );
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE person(name TEXT, age INTEGER, data BLOB)")?;
+        db.execute(
+            "INSERT INTO person(name, age, data) VALUES (?1, ?2, ?3)",
+            &person.params()[..],
+        )?;
+        db.execute(
+            "INSERT INTO person(name, age, data) VALUES (?1, ?2, ?3)",
+            Person::default().params(),
+        )?;
+        let mut stmt = db.prepare("SELECT name, age, data FROM person ORDER BY rowid")?;
+        let rows: Vec<(String, u8, Option<Vec<u8>>)> = stmt
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+            .collect::<Result<_>>()?;
+        assert_eq!(
+            vec![
+                ("Ada".to_owned(), 36, Some(vec![1, 2])),
+                (String::new(), 0, None)
+            ],
+            rows
+        );
+        Ok(())
+    }
+
     #[test]
     #[cfg(not(feature = "extra_check"))]
     fn test_alter_table() -> Result<()> {
@@ -2211,6 +3315,38 @@ mod test {
         db.cache_flush()
     }
 
+    #[test]
+    fn test_cache_flush_in_transaction() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let mut db = Connection::open(&path)?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER);")?;
+
+        let tx = db.transaction()?;
+        tx.execute("INSERT INTO foo VALUES(?1)", [42])?;
+        tx.cache_flush()?;
+        assert!(!tx.is_autocommit());
+        tx.rollback()?;
+
+        assert_eq!(0, db.one_column::<i64>("SELECT COUNT(*) FROM foo")?);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "release_memory")]
+    fn test_release_memory() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x BLOB);
+             WITH RECURSIVE c(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM c WHERE i < 1000)
+             INSERT INTO foo SELECT randomblob(1024) FROM c;",
+        )?;
+        assert_eq!(1000, db.one_column::<i64>("SELECT COUNT(*) FROM foo")?);
+        db.release_memory()?;
+        assert!(super::release_memory() >= 0);
+        Ok(())
+    }
+
     #[test]
     fn db_readonly() -> Result<()> {
         let db = Connection::open_in_memory()?;
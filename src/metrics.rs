@@ -0,0 +1,93 @@
+//! Per-connection counters of prepared, executed and queried statements
+use std::time::Duration;
+
+use crate::Connection;
+
+/// A snapshot of the counters of a connection, as returned by
+/// [`Connection::metrics`].
+///
+/// Counters start at zero when the connection is opened, or when
+/// [`Connection::reset_metrics`] is called.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConnectionMetrics {
+    /// Number of statements compiled by SQLite, including those prepared for
+    /// the statement cache.
+    pub prepares: u64,
+    /// Number of [`Connection::prepare_cached`] calls which reused a cached
+    /// statement.
+    pub cache_hits: u64,
+    /// Number of [`Connection::prepare_cached`] calls which had to prepare a
+    /// new statement.
+    pub cache_misses: u64,
+    /// Number of statement executions ([`Statement::execute`](crate::Statement::execute)
+    /// and the functions built on it).
+    pub executes: u64,
+    /// Number of queries ([`Statement::query`](crate::Statement::query) and
+    /// the functions built on it).
+    pub queries: u64,
+    /// Total time spent in `sqlite3_step`.
+    pub step_time: Duration,
+}
+
+impl Connection {
+    /// Current value of the counters of this connection.
+    ///
+    /// The counters are plain integers updated by the calls made on this
+    /// connection (which cannot be used by two threads at the same time), so
+    /// maintaining them is cheap.
+    #[inline]
+    #[must_use]
+    pub fn metrics(&self) -> ConnectionMetrics {
+        self.db.borrow().metrics
+    }
+
+    /// Set all the counters of this connection back to zero.
+    #[inline]
+    pub fn reset_metrics(&self) {
+        self.db.borrow_mut().metrics = ConnectionMetrics::default();
+    }
+
+    #[inline]
+    pub(crate) fn record_metrics(&self, f: impl FnOnce(&mut ConnectionMetrics)) {
+        f(&mut self.db.borrow_mut().metrics);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::ConnectionMetrics;
+    use crate::{Connection, Result};
+
+    #[test]
+    fn test_metrics() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        assert_eq!(ConnectionMetrics::default(), db.metrics());
+        db.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+        db.reset_metrics();
+
+        for i in 0..3 {
+            db.prepare_cached("INSERT INTO foo VALUES (?1)")?
+                .execute([i])?;
+        }
+        db.execute("UPDATE foo SET x = x + 1", [])?;
+        let mut stmt = db.prepare("SELECT x FROM foo")?;
+        assert_eq!(3, stmt.query_map([], |r| r.get::<_, i64>(0))?.count());
+        let n: i64 = db.one_column("SELECT count(*) FROM foo")?;
+        assert_eq!(3, n);
+
+        let metrics = db.metrics();
+        assert_eq!(4, metrics.prepares);
+        assert_eq!(2, metrics.cache_hits);
+        assert_eq!(1, metrics.cache_misses);
+        assert_eq!(4, metrics.executes);
+        assert_eq!(2, metrics.queries);
+        assert!(metrics.step_time > Duration::ZERO);
+
+        db.reset_metrics();
+        assert_eq!(ConnectionMetrics::default(), db.metrics());
+        Ok(())
+    }
+}
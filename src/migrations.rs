@@ -0,0 +1,234 @@
+//! Minimal schema migrations, tracked with `PRAGMA user_version`.
+//!
+//! ```rust,no_run
+//! use rusqlite::migrations::Migrations;
+//! use rusqlite::{Connection, Result};
+//!
+//! fn main() -> Result<()> {
+//!     let migrations = Migrations::new()
+//!         .add(1, "CREATE TABLE user(id INTEGER PRIMARY KEY)", "DROP TABLE user")
+//!         .add(
+//!             2,
+//!             "ALTER TABLE user ADD COLUMN name TEXT",
+//!             "ALTER TABLE user DROP COLUMN name",
+//!         );
+//!     let conn = Connection::open("my_db.db3")?;
+//!     conn.run_migrations(&migrations)
+//! }
+//! ```
+use crate::error::misuse;
+use crate::{Connection, Result};
+
+#[derive(Debug)]
+struct Migration {
+    version: u32,
+    up: String,
+    down: String,
+}
+
+/// An ordered list of schema migrations.
+///
+/// The version of the database schema is stored in `PRAGMA user_version`, `0`
+/// meaning that no migration has been applied. Each migration is applied (or
+/// rolled back) in its own transaction, along with the `user_version` update.
+#[derive(Debug, Default)]
+pub struct Migrations {
+    steps: Vec<Migration>,
+}
+
+impl Migrations {
+    /// An empty list of migrations.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Migrations {
+        Migrations::default()
+    }
+
+    /// Add a migration to schema `version`: `up` is the SQL (possibly
+    /// several statements) applying it, `down` the SQL reverting it.
+    ///
+    /// `down` may be empty for a migration which cannot be reverted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` is 0, is greater than `i32::MAX`, or is not
+    /// greater than the version of the previously added migration.
+    #[must_use]
+    pub fn add<U, D>(mut self, version: u32, up: U, down: D) -> Migrations
+    where
+        U: Into<String>,
+        D: Into<String>,
+    {
+        assert!(
+            version > self.latest() && i32::try_from(version).is_ok(),
+            "migration versions must be increasing, positive i32 values"
+        );
+        self.steps.push(Migration {
+            version,
+            up: up.into(),
+            down: down.into(),
+        });
+        self
+    }
+
+    /// Version of the last migration, or 0 if there is none.
+    #[inline]
+    #[must_use]
+    pub fn latest(&self) -> u32 {
+        self.steps.last().map_or(0, |m| m.version)
+    }
+}
+
+impl Connection {
+    /// Apply all the migrations of `migrations` not applied yet.
+    ///
+    /// # Failure
+    ///
+    /// See [`Connection::migrate_to`].
+    #[inline]
+    pub fn run_migrations(&self, migrations: &Migrations) -> Result<()> {
+        self.migrate_to(migrations, migrations.latest())
+    }
+
+    /// Apply or roll back migrations until the schema is at `version`.
+    ///
+    /// Must not be called within a transaction. If a migration fails, the
+    /// previous ones stay applied (or rolled back).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `version` or the current schema version is
+    /// neither 0 nor the version of one of `migrations`, if a migration to
+    /// roll back has no `down` SQL, or if a migration fails.
+    pub fn migrate_to(&self, migrations: &Migrations, version: u32) -> Result<()> {
+        let steps = &migrations.steps;
+        let position = |v: u32| -> Result<usize> {
+            if v == 0 {
+                return Ok(0);
+            }
+            steps
+                .iter()
+                .position(|m| m.version == v)
+                .map(|i| i + 1)
+                .ok_or_else(|| misuse(format!("unknown schema version {v}")))
+        };
+        let current: u32 = self.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        let (from, to) = (position(current)?, position(version)?);
+
+        for m in steps.iter().take(to).skip(from) {
+            self.apply(&m.up, m.version)?;
+        }
+        for i in (to..from).rev() {
+            let m = &steps[i];
+            if m.down.trim().is_empty() {
+                return Err(misuse(format!(
+                    "migration {} cannot be rolled back",
+                    m.version
+                )));
+            }
+            let previous = if i == 0 { 0 } else { steps[i - 1].version };
+            self.apply(&m.down, previous)?;
+        }
+        Ok(())
+    }
+
+    fn apply(&self, sql: &str, user_version: u32) -> Result<()> {
+        let tx = self.unchecked_transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", user_version)?;
+        tx.commit()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Migrations;
+    use crate::{Connection, Result};
+
+    fn migrations() -> Migrations {
+        Migrations::new()
+            .add(1, "CREATE TABLE foo(x INTEGER)", "DROP TABLE foo")
+            .add(
+                2,
+                "CREATE TABLE bar(y TEXT); INSERT INTO foo VALUES (1);",
+                "DROP TABLE bar; DELETE FROM foo;",
+            )
+            .add(5, "CREATE INDEX foo_x ON foo(x)", "DROP INDEX foo_x")
+    }
+
+    fn user_version(db: &Connection) -> Result<u32> {
+        db.pragma_query_value(None, "user_version", |row| row.get(0))
+    }
+
+    fn objects(db: &Connection) -> Result<Vec<String>> {
+        let mut stmt = db.prepare("SELECT name FROM sqlite_master ORDER BY name")?;
+        let names = stmt.query_map([], |row| row.get(0))?;
+        names.collect()
+    }
+
+    #[test]
+    fn test_incremental() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let m = migrations();
+        assert_eq!(5, m.latest());
+
+        db.migrate_to(&m, 1)?;
+        assert_eq!(1, user_version(&db)?);
+        assert_eq!(vec!["foo"], objects(&db)?);
+
+        db.run_migrations(&m)?;
+        assert_eq!(5, user_version(&db)?);
+        assert_eq!(vec!["bar", "foo", "foo_x"], objects(&db)?);
+        let n: i64 = db.one_column("SELECT count(*) FROM foo")?;
+        assert_eq!(1, n);
+
+        // already up to date
+        db.run_migrations(&m)?;
+        assert_eq!(5, user_version(&db)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rollback() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let m = migrations();
+        db.run_migrations(&m)?;
+
+        db.migrate_to(&m, 1)?;
+        assert_eq!(1, user_version(&db)?);
+        assert_eq!(vec!["foo"], objects(&db)?);
+        let n: i64 = db.one_column("SELECT count(*) FROM foo")?;
+        assert_eq!(0, n);
+
+        db.migrate_to(&m, 0)?;
+        assert_eq!(0, user_version(&db)?);
+        assert!(objects(&db)?.is_empty());
+
+        db.migrate_to(&m, 3).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_migration() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let m = Migrations::new().add(1, "CREATE TABLE foo(x)", "").add(
+            2,
+            "CREATE TABLE bar(y); INSERT INTO missing VALUES (1);",
+            "",
+        );
+        db.run_migrations(&m).unwrap_err();
+        // the failed migration was rolled back as a whole
+        assert_eq!(1, user_version(&db)?);
+        assert_eq!(vec!["foo"], objects(&db)?);
+        // no down migration
+        db.migrate_to(&m, 0).unwrap_err();
+        assert_eq!(1, user_version(&db)?);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "migration versions must be increasing")]
+    fn test_unordered_versions() {
+        let _ = Migrations::new().add(2, "", "").add(1, "", "");
+    }
+}
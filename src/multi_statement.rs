@@ -0,0 +1,160 @@
+use crate::error::misuse;
+use crate::{Batch, Connection, Error, Result, Rows, Statement, ToSql};
+
+/// The result sets of a script made of several SQL statements, as returned by
+/// [`Connection::query_multi`].
+///
+/// Statements are prepared and run one at a time, in order. Statements which
+/// return no columns (`INSERT`, `CREATE TABLE`, ...) are executed as soon as
+/// they are reached and do not produce a result set.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rusqlite::{Connection, Result};
+/// fn print_script(conn: &Connection, script: &str) -> Result<()> {
+///     let mut results = conn.query_multi(script, &[]);
+///     while let Some(mut rows) = results.next()? {
+///         while let Some(row) = rows.next()? {
+///             println!("{:?}", row);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct MultiStatement<'conn, 'sql, 'p> {
+    batch: Batch<'conn, 'sql>,
+    params: &'p [(&'p str, &'p dyn ToSql)],
+    current: Option<Statement<'conn>>,
+}
+
+impl<'conn, 'sql, 'p> MultiStatement<'conn, 'sql, 'p> {
+    /// Run the statements up to the next one returning columns, and return
+    /// its rows.
+    ///
+    /// Returns `Ok(None)` once all the statements have been run. The rows of
+    /// the previous result set, if not entirely retrieved, are discarded.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a statement cannot be prepared, if one of its
+    /// parameters is missing from the parameters, or if a statement without
+    /// columns fails.
+    #[allow(clippy::should_implement_trait)] // lending iterator
+    pub fn next(&mut self) -> Result<Option<Rows<'_>>> {
+        self.current = None;
+        while let Some(mut stmt) = self.batch.next()? {
+            self.bind(&mut stmt)?;
+            if stmt.column_count() == 0 {
+                stmt.raw_execute()?;
+                continue;
+            }
+            return Ok(Some(self.current.insert(stmt).raw_query()));
+        }
+        Ok(None)
+    }
+
+    fn bind(&self, stmt: &mut Statement<'conn>) -> Result<()> {
+        for i in 1..=stmt.parameter_count() {
+            let name = match stmt.parameter_name(i) {
+                Some(name) if !name.starts_with('?') => name,
+                // `?` or `?NNN` could refer to any statement
+                _ => {
+                    return Err(misuse(format!(
+                        "Positional parameter {i} is ambiguous in a multi-statement query, use a \
+                         named parameter instead"
+                    )))
+                }
+            };
+            match self.params.iter().find(|(n, _)| *n == name) {
+                Some((_, value)) => stmt.raw_bind_parameter(i, value)?,
+                None => return Err(Error::InvalidParameterName(name.to_owned())),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for MultiStatement<'_, '_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiStatement")
+            .field("batch", &self.batch)
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Connection {
+    /// Prepare the statements of `sql` one after the other, and iterate over
+    /// their result sets. See [`MultiStatement`].
+    ///
+    /// Only named parameters (`:name`, `@name` or `$name`) are supported, as
+    /// positional ones would be ambiguous across statements. Each statement
+    /// binds the `params` it references; the same parameter can be used by
+    /// several statements.
+    ///
+    /// Errors in the statements are returned by [`MultiStatement::next`].
+    #[must_use]
+    pub fn query_multi<'conn, 'sql, 'p>(
+        &'conn self,
+        sql: &'sql str,
+        params: &'p [(&'p str, &'p dyn ToSql)],
+    ) -> MultiStatement<'conn, 'sql, 'p> {
+        MultiStatement {
+            batch: Batch::new(self, sql),
+            params,
+            current: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{named_params, Connection, Error, Result};
+
+    #[test]
+    fn test_query_multi() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut results = db.query_multi(
+            "CREATE TABLE foo(x INTEGER);
+             INSERT INTO foo VALUES (1), (2), (:x);
+             SELECT x FROM foo WHERE x > :min ORDER BY x;
+             SELECT 'a', :x;",
+            named_params! {":x": 3, ":min": 1},
+        );
+
+        {
+            let mut rows = results.next()?.unwrap();
+            let mut xs = Vec::new();
+            while let Some(row) = rows.next()? {
+                xs.push(row.get::<_, i64>(0)?);
+            }
+            assert_eq!(vec![2, 3], xs);
+        }
+        {
+            let mut rows = results.next()?.unwrap();
+            let row = rows.next()?.unwrap();
+            assert_eq!("a", row.get::<_, String>(0)?);
+            assert_eq!(3, row.get::<_, i64>(1)?);
+            assert!(rows.next()?.is_none());
+        }
+
+        assert!(results.next()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_multi_parameters() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut results = db.query_multi("SELECT 1; SELECT ?1", &[]);
+        assert!(results.next()?.is_some());
+        assert!(results.next().is_err());
+
+        let mut results = db.query_multi("SELECT :a", &[]);
+        assert_eq!(
+            Some(Error::InvalidParameterName(":a".to_owned())),
+            results.next().err()
+        );
+        Ok(())
+    }
+}
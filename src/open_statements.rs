@@ -0,0 +1,145 @@
+use std::ffi::CStr;
+use std::ptr;
+
+use crate::{ffi, Connection};
+
+/// A statement prepared on a connection and not finalized yet, as returned
+/// by [`Connection::open_statements`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OpenStatement {
+    /// SQL text of the statement.
+    pub sql: String,
+    /// Whether the statement has been stepped but not reset or run to
+    /// completion, e.g. because its rows were not all retrieved.
+    pub busy: bool,
+}
+
+impl Connection {
+    /// Number of statements prepared on this connection and not finalized
+    /// yet, including the ones in the prepared statement cache.
+    ///
+    /// This also counts the statements SQLite itself or an extension keeps
+    /// prepared, e.g. for an FTS5 or R-Tree virtual table.
+    #[must_use]
+    pub fn prepared_statement_count(&self) -> usize {
+        let c = self.db.borrow();
+        let mut n = 0;
+        unsafe {
+            let mut stmt = ffi::sqlite3_next_stmt(c.db(), ptr::null_mut());
+            while !stmt.is_null() {
+                n += 1;
+                stmt = ffi::sqlite3_next_stmt(c.db(), stmt);
+            }
+        }
+        n
+    }
+
+    /// List the statements prepared on this connection and not finalized
+    /// yet, including the ones in the prepared statement cache.
+    ///
+    /// This also lists the statements SQLite itself or an extension keeps
+    /// prepared, e.g. for an FTS5 or R-Tree virtual table.
+    ///
+    /// This helps finding the statements which keep a connection from being
+    /// closed ([`Connection::close`] returns `SQLITE_BUSY` while a statement
+    /// exists) or keep a read transaction open (the `busy` ones).
+    ///
+    /// See [`sqlite3_next_stmt`](https://sqlite.org/c3ref/next_stmt.html).
+    #[must_use]
+    pub fn open_statements(&self) -> Vec<OpenStatement> {
+        let c = self.db.borrow();
+        let mut statements = Vec::new();
+        unsafe {
+            let mut stmt = ffi::sqlite3_next_stmt(c.db(), ptr::null_mut());
+            while !stmt.is_null() {
+                let sql = ffi::sqlite3_sql(stmt);
+                statements.push(OpenStatement {
+                    sql: if sql.is_null() {
+                        String::new()
+                    } else {
+                        CStr::from_ptr(sql).to_string_lossy().into_owned()
+                    },
+                    busy: ffi::sqlite3_stmt_busy(stmt) != 0,
+                });
+                stmt = ffi::sqlite3_next_stmt(c.db(), stmt);
+            }
+        }
+        statements
+    }
+
+    /// Flush the prepared statement cache, then finalize all the remaining
+    /// statements of this connection, and return how many there were.
+    ///
+    /// As it takes `&mut self`, no [`Statement`](crate::Statement) can be
+    /// alive: the statements finalized here were leaked (e.g. with
+    /// `std::mem::forget`) or prepared through the raw
+    /// [`handle`](Connection::handle).
+    ///
+    /// # Safety
+    ///
+    /// Every statement still open after flushing the cache must be owned by
+    /// the caller, who must not use it afterwards. In particular, this must
+    /// not be called once a virtual table (FTS5, R-Tree, ...) or an
+    /// extension may have prepared statements of its own: finalizing those
+    /// leads to a use after free the next time SQLite uses them.
+    pub unsafe fn finalize_all(&mut self) -> usize {
+        self.flush_prepared_statement_cache();
+        let c = self.db.borrow();
+        let mut n = 0;
+        loop {
+            let stmt = ffi::sqlite3_next_stmt(c.db(), ptr::null_mut());
+            if stmt.is_null() {
+                break;
+            }
+            ffi::sqlite3_finalize(stmt);
+            n += 1;
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OpenStatement;
+    use crate::{Connection, Result};
+
+    #[test]
+    fn test_open_statements() -> Result<()> {
+        let mut db = Connection::open_in_memory()?;
+        assert_eq!(0, db.prepared_statement_count());
+        {
+            let mut stmt = db.prepare("SELECT 1 UNION ALL SELECT 2")?;
+            let other = db.prepare("SELECT 3")?;
+            let mut rows = stmt.query([])?;
+            rows.next()?;
+            assert_eq!(2, db.prepared_statement_count());
+            let mut open = db.open_statements();
+            open.sort_by(|a, b| a.sql.cmp(&b.sql));
+            assert_eq!(
+                vec![
+                    OpenStatement {
+                        sql: "SELECT 1 UNION ALL SELECT 2".to_owned(),
+                        busy: true,
+                    },
+                    OpenStatement {
+                        sql: "SELECT 3".to_owned(),
+                        busy: false,
+                    },
+                ],
+                open
+            );
+            drop(other);
+        }
+        assert_eq!(0, db.prepared_statement_count());
+
+        db.prepare_cached("SELECT 4")?;
+        std::mem::forget(db.prepare("SELECT 5")?);
+        std::mem::forget(db.prepare("SELECT 6")?);
+        assert_eq!(3, db.prepared_statement_count());
+        // Both leaked statements are ours.
+        assert_eq!(2, unsafe { db.finalize_all() });
+        assert_eq!(0, db.prepared_statement_count());
+        db.close().map_err(|(_, e)| e)
+    }
+}
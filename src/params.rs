@@ -436,6 +436,72 @@ where
     ParamsFromIter(iter)
 }
 
+/// Rewrite each `/*?*/` marker in `sql` into `placeholder_count` comma
+/// separated anonymous parameters, to bind a list of values whose length is
+/// only known at runtime with [`params_from_iter`].
+///
+/// If `placeholder_count` is 0, the marker is removed: SQLite accepts an
+/// empty list, so `x IN (/*?*/)` matches no row and `x NOT IN (/*?*/)`
+/// matches every row.
+///
+/// ```rust,no_run
+/// use rusqlite::{expand_in_clause, params_from_iter, Connection, Result};
+///
+/// fn names(conn: &Connection, ids: &[i64]) -> Result<Vec<String>> {
+///     let sql = expand_in_clause("SELECT name FROM user WHERE id IN (/*?*/)", ids.len());
+///     let mut stmt = conn.prepare(&sql)?;
+///     let rows = stmt.query_map(params_from_iter(ids), |row| row.get(0))?;
+///     rows.collect()
+/// }
+/// ```
+///
+/// Markers within string literals, quoted identifiers and comments are left
+/// alone. All the markers of `sql` are expanded to the same number of
+/// parameters: for two lists of different lengths, expand the SQL in two
+/// parts, or call this once per list with different markers.
+///
+/// The parameters are anonymous, so they are numbered in order with any other
+/// `?` of `sql`, and mixing them with numbered parameters (`?NNN`) is
+/// error-prone. It is also up to the caller to stay below
+/// [`SQLITE_LIMIT_VARIABLE_NUMBER`](https://www.sqlite.org/limits.html#max_variable_number).
+#[must_use]
+pub fn expand_in_clause(sql: &str, placeholder_count: usize) -> String {
+    const MARKER: &str = "/*?*/";
+    let mut vars = "?,".repeat(placeholder_count);
+    vars.pop();
+
+    let bytes = sql.as_bytes();
+    let mut expanded = String::with_capacity(sql.len());
+    // start of the text not copied yet
+    let mut copied = 0;
+    // index just past the first `close` from `from`, or the end of `sql`
+    let end_of = |close: &[u8], from: usize| {
+        bytes[from..]
+            .windows(close.len())
+            .position(|w| w == close)
+            .map_or(bytes.len(), |p| from + p + close.len())
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        i = match bytes[i] {
+            b'/' if sql[i..].starts_with(MARKER) => {
+                expanded.push_str(&sql[copied..i]);
+                expanded.push_str(&vars);
+                copied = i + MARKER.len();
+                copied
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => end_of(b"*/", i + 2),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => end_of(b"\n", i + 2),
+            // doubled quotes are two consecutive literals for this purpose
+            q @ (b'\'' | b'"' | b'`') => end_of(&[q], i + 1),
+            b'[' => end_of(b"]", i + 1),
+            _ => i + 1,
+        };
+    }
+    expanded.push_str(&sql[copied..]);
+    expanded
+}
+
 impl<I> Sealed for ParamsFromIter<I>
 where
     I: IntoIterator,
@@ -453,3 +519,56 @@ where
         stmt.bind_parameters(self.0)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{expand_in_clause, params_from_iter};
+    use crate::{Connection, Result};
+
+    #[test]
+    fn test_expand_in_clause() -> Result<()> {
+        let sql = "SELECT x FROM foo WHERE x IN (/*?*/) ORDER BY x";
+        assert_eq!(
+            expand_in_clause(sql, 3),
+            "SELECT x FROM foo WHERE x IN (?,?,?) ORDER BY x"
+        );
+        assert_eq!(
+            expand_in_clause(sql, 0),
+            "SELECT x FROM foo WHERE x IN () ORDER BY x"
+        );
+        // markers in literals, identifiers and comments are kept
+        let sql = "SELECT '/*?*/', 'it''s /*?*/', \"/*?*/\", [/*?*/] -- /*?*/\n\
+                   FROM t /* /*?*/ WHERE x IN (/*?*/)";
+        assert_eq!(
+            "SELECT '/*?*/', 'it''s /*?*/', \"/*?*/\", [/*?*/] -- /*?*/\n\
+             FROM t /* /*?*/ WHERE x IN (?,?)",
+            expand_in_clause(sql, 2)
+        );
+        assert_eq!("'/*?*/", expand_in_clause("'/*?*/", 1));
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (1), (2), (3), (4);",
+        )?;
+        let query = |sql: &str, ids: &[i64]| -> Result<Vec<i64>> {
+            let mut stmt = db.prepare(&expand_in_clause(sql, ids.len()))?;
+            let rows = stmt.query_map(params_from_iter(ids), |r| r.get(0))?;
+            rows.collect()
+        };
+        let sql = "SELECT x FROM foo WHERE x IN (/*?*/) ORDER BY x";
+        assert_eq!(query(sql, &[4, 2, 5])?, [2, 4]);
+        assert_eq!(query(sql, &[1])?, [1]);
+        assert!(query(sql, &[])?.is_empty());
+        let sql = "SELECT x FROM foo WHERE x NOT IN (/*?*/) ORDER BY x";
+        assert_eq!(query(sql, &[2, 3])?, [1, 4]);
+        assert_eq!(query(sql, &[])?, [1, 2, 3, 4]);
+
+        let sql = expand_in_clause(
+            "SELECT COUNT(*) FROM foo WHERE x NOT IN (/*?*/) AND x > ?",
+            2,
+        );
+        let count: i64 = db.query_row(&sql, params_from_iter([1, 2, 1]), |r| r.get(0))?;
+        assert_eq!(count, 2);
+        Ok(())
+    }
+}
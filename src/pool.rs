@@ -0,0 +1,262 @@
+//! Minimal connection pool.
+//!
+//! For anything beyond simple cases (timeouts, health checks, ...), a
+//! dedicated pooling crate like `r2d2` is a better fit.
+//!
+//! ```rust,no_run
+//! use rusqlite::pool::ConnectionPool;
+//! use rusqlite::Result;
+//!
+//! fn main() -> Result<()> {
+//!     let pool = ConnectionPool::open("my_db.db3", 4)?
+//!         .on_acquire(|conn| conn.pragma_update(None, "foreign_keys", true));
+//!     std::thread::scope(|s| {
+//!         for _ in 0..8 {
+//!             s.spawn(|| {
+//!                 let conn = pool.get()?;
+//!                 conn.execute("INSERT INTO log DEFAULT VALUES", [])
+//!             });
+//!         }
+//!     });
+//!     Ok(())
+//! }
+//! ```
+use std::fmt;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+
+use crate::{Connection, OpenFlags, Result};
+
+type AcquireHook = Box<dyn Fn(&Connection) -> Result<()> + Send + Sync>;
+
+struct State {
+    idle: Vec<Connection>,
+    // number of connections opened by the pool (idle or in use)
+    opened: usize,
+}
+
+/// A fixed-size pool of connections to the same database.
+///
+/// Connections are opened lazily, up to `max_size`. [`ConnectionPool::get`]
+/// blocks until a connection is available.
+///
+/// Each pooled connection is a separate [`Connection`], so pooling
+/// connections to `":memory:"` gives each one its own private database.
+pub struct ConnectionPool {
+    path: PathBuf,
+    flags: OpenFlags,
+    max_size: usize,
+    on_acquire: Option<AcquireHook>,
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+impl ConnectionPool {
+    /// Create a pool of at most `max_size` connections to the database at
+    /// `path`, opened with the default [`OpenFlags`].
+    ///
+    /// One connection is opened immediately so that an invalid `path` is
+    /// reported here rather than on first use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_size` is 0.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the first connection cannot be opened.
+    #[inline]
+    pub fn open<P: AsRef<Path>>(path: P, max_size: usize) -> Result<ConnectionPool> {
+        ConnectionPool::open_with_flags(path, OpenFlags::default(), max_size)
+    }
+
+    /// Create a pool of at most `max_size` connections to the database at
+    /// `path`, opened with `flags`.
+    ///
+    /// See [`ConnectionPool::open`].
+    pub fn open_with_flags<P: AsRef<Path>>(
+        path: P,
+        flags: OpenFlags,
+        max_size: usize,
+    ) -> Result<ConnectionPool> {
+        assert!(max_size > 0, "a pool needs at least one connection");
+        let path = path.as_ref().to_owned();
+        let conn = Connection::open_with_flags(&path, flags)?;
+        Ok(ConnectionPool {
+            path,
+            flags,
+            max_size,
+            on_acquire: None,
+            state: Mutex::new(State {
+                idle: vec![conn],
+                opened: 1,
+            }),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Register a hook called each time a connection is handed out by
+    /// [`ConnectionPool::get`], e.g. to set pragmas.
+    ///
+    /// If the hook fails, the connection is returned to the pool and `get`
+    /// returns the error.
+    #[must_use]
+    pub fn on_acquire<F>(mut self, hook: F) -> ConnectionPool
+    where
+        F: Fn(&Connection) -> Result<()> + Send + Sync + 'static,
+    {
+        self.on_acquire = Some(Box::new(hook));
+        self
+    }
+
+    /// Maximum number of connections.
+    #[inline]
+    #[must_use]
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Get a connection from the pool, opening a new one if none is idle and
+    /// fewer than `max_size` are open, or waiting for one to be returned
+    /// otherwise.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a new connection cannot be opened or if the
+    /// `on_acquire` hook fails.
+    pub fn get(&self) -> Result<PooledConnection<'_>> {
+        let mut state = self.state.lock().unwrap();
+        let conn = loop {
+            if let Some(conn) = state.idle.pop() {
+                // release the lock before running the hook: on failure, the
+                // guard puts the connection back.
+                drop(state);
+                break conn;
+            }
+            if state.opened < self.max_size {
+                state.opened += 1;
+                drop(state);
+                match Connection::open_with_flags(&self.path, self.flags) {
+                    Ok(conn) => break conn,
+                    Err(e) => {
+                        self.state.lock().unwrap().opened -= 1;
+                        self.available.notify_one();
+                        return Err(e);
+                    }
+                }
+            }
+            state = self.available.wait(state).unwrap();
+        };
+        let conn = PooledConnection {
+            pool: self,
+            conn: Some(conn),
+        };
+        if let Some(ref hook) = self.on_acquire {
+            hook(&conn)?;
+        }
+        Ok(conn)
+    }
+
+    fn put_back(&self, conn: Connection) {
+        self.state.lock().unwrap().idle.push(conn);
+        self.available.notify_one();
+    }
+}
+
+impl fmt::Debug for ConnectionPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionPool")
+            .field("path", &self.path)
+            .field("max_size", &self.max_size)
+            .finish()
+    }
+}
+
+/// A connection borrowed from a [`ConnectionPool`], returned to it on drop.
+pub struct PooledConnection<'pool> {
+    pool: &'pool ConnectionPool,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    #[inline]
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.put_back(conn);
+        }
+    }
+}
+
+impl fmt::Debug for PooledConnection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledConnection").field(&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConnectionPool;
+    use crate::Result;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_pool_threads() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let pool = ConnectionPool::open(&path, 3)?
+            .on_acquire(|conn| conn.pragma_update(None, "foreign_keys", true));
+        pool.get()?.execute_batch("CREATE TABLE foo(x INTEGER)")?;
+
+        let in_use = Mutex::new(HashSet::new());
+        let used = Mutex::new(HashSet::new());
+        thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| -> Result<()> {
+                        for _ in 0..5 {
+                            let conn = pool.get()?;
+                            let id = unsafe { conn.handle() } as usize;
+                            assert!(in_use.lock().unwrap().insert(id), "double use");
+                            used.lock().unwrap().insert(id);
+                            let fk: bool =
+                                conn.query_row("PRAGMA foreign_keys", [], |r| r.get(0))?;
+                            assert!(fk);
+                            conn.query_row("SELECT COUNT(*) FROM foo", [], |_| Ok(()))?;
+                            thread::sleep(Duration::from_millis(1));
+                            assert!(in_use.lock().unwrap().remove(&id));
+                        }
+                        Ok(())
+                    })
+                })
+                .collect();
+            for h in handles {
+                h.join().unwrap().unwrap();
+            }
+        });
+        assert!(used.into_inner().unwrap().len() <= pool.max_size());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_hook_error() -> Result<()> {
+        let pool = ConnectionPool::open(":memory:", 1)?
+            .on_acquire(|conn| conn.execute_batch("not valid sql"));
+        pool.get().unwrap_err();
+        // the connection was returned to the pool
+        pool.get().unwrap_err();
+        Ok(())
+    }
+}
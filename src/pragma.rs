@@ -2,10 +2,10 @@
 
 use std::ops::Deref;
 
-use crate::error::Error;
+use crate::error::{misuse, Error};
 use crate::ffi;
 use crate::types::{ToSql, ToSqlOutput, ValueRef};
-use crate::{Connection, DatabaseName, Result, Row};
+use crate::{Connection, DatabaseName, Result, Row, Savepoint};
 
 pub struct Sql {
     buf: String,
@@ -156,6 +156,63 @@ impl Sql {
     }
 }
 
+/// Quote `name` as a SQL identifier (table, column, ...), for use where a
+/// parameter cannot be, e.g. in dynamically built DDL.
+///
+/// The name is always wrapped in double quotes, with embedded double quotes
+/// doubled, so it can also be a keyword.
+///
+/// ```rust
+/// # use rusqlite::quote_identifier;
+/// assert_eq!(r#""order""#, quote_identifier("order"));
+/// assert_eq!(r#""my ""table""""#, quote_identifier(r#"my "table""#));
+/// ```
+#[must_use]
+pub fn quote_identifier(name: &str) -> String {
+    let mut sql = Sql::new();
+    sql.wrap_and_escape(name, '"');
+    sql.buf
+}
+
+/// Quote `s` as a SQL string literal, wrapped in single quotes, with embedded
+/// single quotes doubled.
+///
+/// Prefer binding parameters for values; this is only for the places where
+/// SQLite does not accept them.
+///
+/// ```rust
+/// # use rusqlite::quote_string_literal;
+/// assert_eq!("'it''s'", quote_string_literal("it's"));
+/// ```
+#[must_use]
+pub fn quote_string_literal(s: &str) -> String {
+    let mut sql = Sql::new();
+    sql.push_string_literal(s);
+    sql.buf
+}
+
+/// Format `bytes` as a SQL BLOB literal, `x'...'` with two hex digits per
+/// byte, for the places where SQLite does not accept parameters (e.g. a
+/// `DEFAULT` clause).
+///
+/// ```rust
+/// # use rusqlite::blob_literal;
+/// assert_eq!("x'00ff'", blob_literal(&[0x00, 0xff]));
+/// assert_eq!("x''", blob_literal(&[]));
+/// ```
+#[must_use]
+pub fn blob_literal(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(3 + 2 * bytes.len());
+    s.push_str("x'");
+    for &b in bytes {
+        s.push(char::from(HEX[usize::from(b >> 4)]));
+        s.push(char::from(HEX[usize::from(b & 0xf)]));
+    }
+    s.push('\'');
+    s
+}
+
 impl Deref for Sql {
     type Target = str;
 
@@ -164,6 +221,208 @@ impl Deref for Sql {
     }
 }
 
+bitflags::bitflags! {
+    /// Optimizations attempted by [`Connection::optimize_with_mask`]. See
+    /// [`PRAGMA optimize`](https://sqlite.org/pragma.html#pragma_optimize) for
+    /// details.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    pub struct OptimizeFlags: u32 {
+        /// Run `ANALYZE` on tables that might benefit from it.
+        const ANALYZE = 0x02;
+        /// Consider all tables, not only those used by this connection
+        /// (typically combined with `ANALYZE` right after opening).
+        const ALL_TABLES = 0x10;
+        /// Limit the work done by `ANALYZE` to keep it fast (SQLite 3.46.0
+        /// or later).
+        const LIMIT_ANALYSIS = 0x1_0000;
+    }
+}
+
+impl Default for OptimizeFlags {
+    /// The optimizations done by [`Connection::optimize`].
+    #[inline]
+    fn default() -> OptimizeFlags {
+        OptimizeFlags::ANALYZE | OptimizeFlags::LIMIT_ANALYSIS
+    }
+}
+
+/// Size of the page cache, as set by [`Connection::set_cache_size`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheSize {
+    /// Maximum number of database pages held in memory.
+    Pages(u32),
+    /// Maximum amount of memory used by the cache, in KiB (whatever the page
+    /// size).
+    KiB(u32),
+}
+
+/// Where temporary tables and indices are stored, as set by
+/// [`Connection::set_temp_store`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TempStore {
+    /// Use the compile-time default (`SQLITE_TEMP_STORE`).
+    Default,
+    /// Use a temporary file.
+    File,
+    /// Use memory.
+    Memory,
+}
+
+/// Auto-vacuum mode of a database, see [`Connection::set_auto_vacuum`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutoVacuum {
+    /// Free pages are kept in the file, which never shrinks (without
+    /// `VACUUM`).
+    None,
+    /// Free pages are removed from the file at each commit.
+    Full,
+    /// Free pages are removed by [`Connection::incremental_vacuum`] only.
+    Incremental,
+}
+
+/// Text encoding of a database, see [`Connection::encoding`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-8.
+    Utf8,
+    /// UTF-16, little-endian.
+    Utf16le,
+    /// UTF-16, big-endian.
+    Utf16be,
+}
+
+/// Checkpoint mode, see [`Connection::wal_checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointMode {
+    /// Checkpoint as many frames as possible without waiting for readers or
+    /// writers.
+    Passive,
+    /// Wait for writers, then checkpoint all frames.
+    Full,
+    /// Like `Full`, then wait for readers so that the next writer restarts
+    /// the WAL file from the beginning.
+    Restart,
+    /// Like `Restart`, and truncate the WAL file to zero bytes.
+    Truncate,
+}
+
+/// Result of [`Connection::wal_checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WalCheckpoint {
+    /// Whether the checkpoint could not complete because of other
+    /// connections (`SQLITE_BUSY`).
+    pub busy: bool,
+    /// Number of frames in the WAL file, -1 if not in WAL mode.
+    pub log_frames: i64,
+    /// Number of frames checkpointed, -1 if not in WAL mode.
+    pub checkpointed_frames: i64,
+}
+
+/// A row of the `sqlite_stat1` table, as returned by
+/// [`Connection::read_stat1`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stat1Row {
+    /// Name of the analyzed table.
+    pub table: String,
+    /// Name of the analyzed index, `None` for the row giving the number of
+    /// rows of a table without index.
+    pub index: Option<String>,
+    /// Statistics: the number of rows, followed, for an index, by the average
+    /// number of rows selected by each prefix of its columns.
+    pub stat: String,
+}
+
+/// A foreign key violation, as reported by
+/// [`Connection::foreign_key_check`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FkViolation {
+    /// Name of the table containing the violating row.
+    pub table: String,
+    /// Rowid of the violating row, `None` for a `WITHOUT ROWID` table.
+    pub rowid: Option<i64>,
+    /// Name of the table referenced by the foreign key.
+    pub referenced_table: String,
+    /// Index of the violated foreign key, as in `PRAGMA
+    /// foreign_key_list(table)`.
+    pub fk_index: usize,
+}
+
+/// A table column, as reported by [`Connection::table_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ColumnInfo {
+    /// Index of the column in the table.
+    pub cid: usize,
+    /// Name of the column.
+    pub name: String,
+    /// Declared type of the column, empty if none.
+    pub decl_type: String,
+    /// Whether the column has a `NOT NULL` constraint.
+    pub not_null: bool,
+    /// SQL text of the default value, if any.
+    pub default: Option<String>,
+    /// 1-based position of the column in the primary key, 0 if the column is
+    /// not part of it.
+    pub pk: usize,
+    /// Only reported by [`Connection::table_xinfo`] (always 0 otherwise): 0
+    /// for a normal column, 1 for a hidden column of a virtual table, 2 for a
+    /// virtual generated column, 3 for a stored generated column.
+    pub hidden: i32,
+}
+
+/// An index of a table, as reported by [`Connection::index_list`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IndexInfo {
+    /// Name of the index.
+    pub name: String,
+    /// Whether the index is `UNIQUE`.
+    pub unique: bool,
+    /// How the index was created: `"c"` by a `CREATE INDEX` statement, `"u"`
+    /// by a `UNIQUE` constraint, `"pk"` by a `PRIMARY KEY` constraint.
+    pub origin: String,
+    /// Whether the index is partial.
+    pub partial: bool,
+}
+
+/// A column of an index, as reported by [`Connection::index_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IndexColumn {
+    /// Position of the column in the index.
+    pub seqno: usize,
+    /// Index of the column in the indexed table, `None` for the rowid or an
+    /// expression.
+    pub cid: Option<usize>,
+    /// Name of the indexed column, `None` for an expression.
+    pub name: Option<String>,
+}
+
+/// An SQL function, as reported by [`Connection::list_functions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FunctionInfo {
+    /// Name of the function.
+    pub name: String,
+    /// Whether the function is built into SQLite (or an extension compiled
+    /// in), rather than registered by the application.
+    pub builtin: bool,
+    /// Kind of function: `"s"` for scalar, `"a"` for aggregate, `"w"` for
+    /// window.
+    pub kind: String,
+    /// Text encoding the function prefers: `"utf8"`, `"utf16le"` or
+    /// `"utf16be"`.
+    pub encoding: String,
+    /// Number of arguments, -1 for a variable number.
+    pub n_arg: i32,
+    /// `SQLITE_DETERMINISTIC`, `SQLITE_DIRECTONLY`, ... flags of the
+    /// function (see `FunctionFlags` with the `functions` feature).
+    pub flags: i32,
+}
+
 impl Connection {
     /// Query the current value of `pragma_name`.
     ///
@@ -293,6 +552,604 @@ impl Connection {
         sql.push_value(&pragma_value)?;
         self.query_row(&sql, [], f)
     }
+
+    /// Run `PRAGMA integrity_check`, reporting at most `max_errors` problems
+    /// (100 by default).
+    ///
+    /// Returns the list of problems found, which is empty if the database is
+    /// healthy.
+    ///
+    /// See [`PRAGMA integrity_check`](https://sqlite.org/pragma.html#pragma_integrity_check).
+    pub fn integrity_check(&self, max_errors: Option<usize>) -> Result<Vec<String>> {
+        self.check_pragma("integrity_check", max_errors)
+    }
+
+    /// Run `PRAGMA quick_check`: like [`Connection::integrity_check`] but
+    /// faster, as it does not check that indexes match their tables.
+    ///
+    /// See [`PRAGMA quick_check`](https://sqlite.org/pragma.html#pragma_quick_check).
+    pub fn quick_check(&self) -> Result<Vec<String>> {
+        self.check_pragma("quick_check", None)
+    }
+
+    /// Run `PRAGMA foreign_key_check` on `table`, or on all tables if `None`,
+    /// and return the rows violating a foreign key constraint.
+    ///
+    /// Useful after a bulk import with foreign key enforcement turned off.
+    ///
+    /// See [`PRAGMA foreign_key_check`](https://sqlite.org/pragma.html#pragma_foreign_key_check).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `table` does not exist.
+    pub fn foreign_key_check(&self, table: Option<&str>) -> Result<Vec<FkViolation>> {
+        let mut sql = Sql::new();
+        sql.push_pragma(None, "foreign_key_check")?;
+        if let Some(table) = table {
+            sql.open_brace();
+            sql.push_identifier(table);
+            sql.close_brace();
+        }
+        let mut stmt = self.prepare(&sql)?;
+        let violations = stmt.query_map([], |row| {
+            Ok(FkViolation {
+                table: row.get(0)?,
+                rowid: row.get(1)?,
+                referenced_table: row.get(2)?,
+                fk_index: row.get(3)?,
+            })
+        })?;
+        violations.collect()
+    }
+
+    /// Describe the columns of `table`.
+    ///
+    /// See [`PRAGMA table_info`](https://sqlite.org/pragma.html#pragma_table_info).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the pragma cannot be run. Note that an unknown
+    /// `table` yields an empty list.
+    pub fn table_info(&self, table: &str) -> Result<Vec<ColumnInfo>> {
+        self.column_info("table_info", table)
+    }
+
+    /// Like [`Connection::table_info`], but also describe hidden columns (of
+    /// virtual tables) and generated columns, see [`ColumnInfo::hidden`].
+    ///
+    /// Requires SQLite 3.26.0 or later.
+    ///
+    /// See [`PRAGMA table_xinfo`](https://sqlite.org/pragma.html#pragma_table_xinfo).
+    pub fn table_xinfo(&self, table: &str) -> Result<Vec<ColumnInfo>> {
+        self.column_info("table_xinfo", table)
+    }
+
+    fn column_info(&self, pragma_name: &str, table: &str) -> Result<Vec<ColumnInfo>> {
+        let mut columns = Vec::new();
+        self.pragma(None, pragma_name, table, |row| {
+            columns.push(ColumnInfo {
+                cid: row.get(0)?,
+                name: row.get(1)?,
+                decl_type: row.get(2)?,
+                not_null: row.get(3)?,
+                default: row.get(4)?,
+                pk: row.get(5)?,
+                hidden: if row.as_ref().column_count() > 6 {
+                    row.get(6)?
+                } else {
+                    0
+                },
+            });
+            Ok(())
+        })?;
+        Ok(columns)
+    }
+
+    /// List the indexes of `table`.
+    ///
+    /// See [`PRAGMA index_list`](https://sqlite.org/pragma.html#pragma_index_list).
+    pub fn index_list(&self, table: &str) -> Result<Vec<IndexInfo>> {
+        let mut indexes = Vec::new();
+        self.pragma(None, "index_list", table, |row| {
+            indexes.push(IndexInfo {
+                name: row.get(1)?,
+                unique: row.get(2)?,
+                origin: row.get(3)?,
+                partial: row.get(4)?,
+            });
+            Ok(())
+        })?;
+        Ok(indexes)
+    }
+
+    /// Describe the columns of `index`.
+    ///
+    /// See [`PRAGMA index_info`](https://sqlite.org/pragma.html#pragma_index_info).
+    pub fn index_info(&self, index: &str) -> Result<Vec<IndexColumn>> {
+        let mut columns = Vec::new();
+        self.pragma(None, "index_info", index, |row| {
+            columns.push(IndexColumn {
+                seqno: row.get(0)?,
+                cid: row
+                    .get::<_, Option<i64>>(1)?
+                    .and_then(|cid| usize::try_from(cid).ok()),
+                name: row.get(2)?,
+            });
+            Ok(())
+        })?;
+        Ok(columns)
+    }
+
+    /// List the SQL functions available on this connection, built-in and
+    /// user-defined, with one entry per variant (number of arguments,
+    /// encoding) of each function.
+    ///
+    /// See [`PRAGMA function_list`](https://sqlite.org/pragma.html#pragma_function_list)
+    /// (SQLite 3.30.0 or later).
+    pub fn list_functions(&self) -> Result<Vec<FunctionInfo>> {
+        let mut functions = Vec::new();
+        self.pragma_query(None, "function_list", |row| {
+            functions.push(FunctionInfo {
+                name: row.get(0)?,
+                builtin: row.get(1)?,
+                kind: row.get(2)?,
+                encoding: row.get(3)?,
+                n_arg: row.get(4)?,
+                flags: row.get(5)?,
+            });
+            Ok(())
+        })?;
+        Ok(functions)
+    }
+
+    /// List the names of the collating sequences available on this
+    /// connection, built-in and user-defined.
+    ///
+    /// See [`PRAGMA collation_list`](https://sqlite.org/pragma.html#pragma_collation_list).
+    pub fn list_collations(&self) -> Result<Vec<String>> {
+        self.pragma_names("collation_list", 1)
+    }
+
+    /// List the names of the virtual table modules available on this
+    /// connection, built-in and user-defined.
+    ///
+    /// See [`PRAGMA module_list`](https://sqlite.org/pragma.html#pragma_module_list)
+    /// (SQLite 3.30.0 or later).
+    pub fn list_modules(&self) -> Result<Vec<String>> {
+        self.pragma_names("module_list", 0)
+    }
+
+    fn pragma_names(&self, pragma_name: &str, column: usize) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        self.pragma_query(None, pragma_name, |row| {
+            names.push(row.get(column)?);
+            Ok(())
+        })?;
+        Ok(names)
+    }
+
+    /// Run `PRAGMA optimize`, which gathers statistics (`ANALYZE`) only for
+    /// the tables that are likely to benefit from it.
+    ///
+    /// Connections should call this just before closing, and long-lived
+    /// connections should also call it periodically (e.g. every few hours).
+    /// It is usually fast, and a no-op when statistics are up to date.
+    ///
+    /// See [`PRAGMA optimize`](https://sqlite.org/pragma.html#pragma_optimize).
+    #[inline]
+    pub fn optimize(&self) -> Result<()> {
+        self.execute_batch("PRAGMA optimize")
+    }
+
+    /// Like [`Connection::optimize`], but with the `flags` selecting which
+    /// optimizations are attempted.
+    pub fn optimize_with_mask(&self, flags: OptimizeFlags) -> Result<()> {
+        let mut sql = Sql::new();
+        sql.push_pragma(None, "optimize")?;
+        sql.open_brace();
+        sql.push_int(i64::from(flags.bits()));
+        sql.close_brace();
+        self.execute_batch(&sql)
+    }
+
+    /// Run `ANALYZE` on all attached databases, or only on `target`, which
+    /// can be the name of a schema, a table or an index.
+    ///
+    /// The statistics gathered are used by the query planner and can be
+    /// inspected with [`Connection::read_stat1`].
+    ///
+    /// See [`ANALYZE`](https://sqlite.org/lang_analyze.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `target` does not exist.
+    pub fn analyze(&self, target: Option<&str>) -> Result<()> {
+        let mut sql = Sql::new();
+        sql.push_keyword("ANALYZE")?;
+        if let Some(target) = target {
+            sql.push_space();
+            sql.push_identifier(target);
+        }
+        self.execute_batch(&sql)
+    }
+
+    /// Read the content of the `sqlite_stat1` table of the main database,
+    /// populated by [`Connection::analyze`].
+    ///
+    /// Returns an empty list if `ANALYZE` was never run.
+    ///
+    /// See [the `sqlite_stat1` table](https://sqlite.org/fileformat2.html#stat1tab).
+    pub fn read_stat1(&self) -> Result<Vec<Stat1Row>> {
+        if !self.has_table("sqlite_stat1")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = self.prepare("SELECT tbl, idx, stat FROM sqlite_stat1")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Stat1Row {
+                table: row.get(0)?,
+                index: row.get(1)?,
+                stat: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Drop all the tables of the main database (along with their indexes
+    /// and triggers), except SQLite internal `sqlite_*` tables.
+    ///
+    /// Foreign key constraints are not enforced during the operation. Views
+    /// are kept, even though they may reference dropped tables.
+    ///
+    /// Useful to reset a database between test cases.
+    pub fn clear_all_tables(&self) -> Result<()> {
+        self.without_foreign_keys(|| {
+            for table in self.user_tables()? {
+                // dropping a virtual table may already have dropped its
+                // shadow tables
+                let mut sql = Sql::new();
+                sql.push_keyword("DROP")?;
+                sql.push_space();
+                sql.push_keyword("TABLE")?;
+                sql.push_space();
+                sql.push_keyword("IF")?;
+                sql.push_space();
+                sql.push_keyword("EXISTS")?;
+                sql.push_space();
+                sql.push_identifier(&table);
+                self.execute_batch(&sql)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Delete all rows of all the tables of the main database, but keep the
+    /// schema. `AUTOINCREMENT` counters are reset too.
+    ///
+    /// Foreign key constraints are not enforced during the operation.
+    pub fn truncate_all_tables(&self) -> Result<()> {
+        self.without_foreign_keys(|| {
+            for table in self.user_tables()? {
+                let mut sql = Sql::new();
+                sql.push_keyword("DELETE")?;
+                sql.push_space();
+                sql.push_keyword("FROM")?;
+                sql.push_space();
+                sql.push_identifier(&table);
+                self.execute_batch(&sql)?;
+            }
+            if self.has_table("sqlite_sequence")? {
+                self.execute_batch("DELETE FROM sqlite_sequence")?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Set the maximum size of the page cache of the main database.
+    ///
+    /// See [`PRAGMA cache_size`](https://sqlite.org/pragma.html#pragma_cache_size).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the size is greater than `i32::MAX`, or is
+    /// `CacheSize::KiB(0)` (which SQLite would take as 0 pages).
+    pub fn set_cache_size(&self, size: CacheSize) -> Result<()> {
+        let value = match size {
+            CacheSize::Pages(n) => i32::try_from(n).ok(),
+            // a negative value is a number of KiB
+            CacheSize::KiB(n) if n > 0 => i32::try_from(n).ok().map(|n| -n),
+            CacheSize::KiB(_) => None,
+        };
+        let value = value.ok_or_else(|| misuse(format!("Invalid cache size {size:?}")))?;
+        self.pragma_update(None, "cache_size", value)
+    }
+
+    /// Maximum size of the page cache of the main database.
+    pub fn cache_size(&self) -> Result<CacheSize> {
+        let value: i64 = self.pragma_query_value(None, "cache_size", |row| row.get(0))?;
+        Ok(if value < 0 {
+            CacheSize::KiB(u32::try_from(-value).unwrap_or(u32::MAX))
+        } else {
+            CacheSize::Pages(u32::try_from(value).unwrap_or(u32::MAX))
+        })
+    }
+
+    /// Set where temporary tables and indices are stored.
+    ///
+    /// See [`PRAGMA temp_store`](https://sqlite.org/pragma.html#pragma_temp_store).
+    pub fn set_temp_store(&self, store: TempStore) -> Result<()> {
+        let value = match store {
+            TempStore::Default => 0,
+            TempStore::File => 1,
+            TempStore::Memory => 2,
+        };
+        self.pragma_update(None, "temp_store", value)
+    }
+
+    /// Where temporary tables and indices are stored.
+    pub fn temp_store(&self) -> Result<TempStore> {
+        let value: i64 = self.pragma_query_value(None, "temp_store", |row| row.get(0))?;
+        Ok(match value {
+            1 => TempStore::File,
+            2 => TempStore::Memory,
+            _ => TempStore::Default,
+        })
+    }
+
+    /// Set the maximum number of bytes of the main database accessed with
+    /// memory-mapped I/O, 0 disabling it, and return the effective value.
+    ///
+    /// The effective value may be smaller than `bytes`: it is capped by the
+    /// `SQLITE_MAX_MMAP_SIZE` compile-time option, which is 0 on platforms
+    /// without memory-mapped I/O.
+    ///
+    /// See [`PRAGMA mmap_size`](https://sqlite.org/pragma.html#pragma_mmap_size).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `bytes` is greater than `i64::MAX`.
+    pub fn set_mmap_size(&self, bytes: u64) -> Result<u64> {
+        let value =
+            i64::try_from(bytes).map_err(|_| misuse(format!("Invalid mmap size {bytes}")))?;
+        self.pragma(None, "mmap_size", value, |_| Ok(()))?;
+        self.mmap_size()
+    }
+
+    /// Maximum number of bytes of the main database accessed with
+    /// memory-mapped I/O.
+    pub fn mmap_size(&self) -> Result<u64> {
+        let value: i64 = self.pragma_query_value(None, "mmap_size", |row| row.get(0))?;
+        Ok(u64::try_from(value).unwrap_or(0))
+    }
+
+    /// Set the page size of the main database, in bytes.
+    ///
+    /// The page size can only be changed before the database is created (i.e.
+    /// before its first table is), or by a `VACUUM` run afterwards
+    /// (except in WAL mode).
+    ///
+    /// See [`PRAGMA page_size`](https://sqlite.org/pragma.html#pragma_page_size).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `bytes` is not a power of two between 512 and
+    /// 65536, or if the database already exists and its page size is
+    /// different: the new size is then only applied by the next `VACUUM`.
+    pub fn set_page_size(&self, bytes: u32) -> Result<()> {
+        if !(512..=65536).contains(&bytes) || !bytes.is_power_of_two() {
+            return Err(misuse(format!(
+                "Invalid page size {bytes}, it must be a power of two between 512 and 65536"
+            )));
+        }
+        self.pragma_update(None, "page_size", bytes)?;
+        let current = self.page_size()?;
+        if current != bytes {
+            return Err(misuse(format!(
+                "Page size is still {current}: it can only be changed to {bytes} before the \
+                 first table is created, or by VACUUM"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Page size of the main database, in bytes.
+    pub fn page_size(&self) -> Result<u32> {
+        self.pragma_query_value(None, "page_size", |row| row.get(0))
+    }
+
+    /// Text encoding of the main database.
+    ///
+    /// Strings are converted to and from UTF-8 by SQLite whatever the
+    /// encoding, but a database written with a UTF-16 encoding stores them
+    /// as UTF-16.
+    ///
+    /// See [`PRAGMA encoding`](https://sqlite.org/pragma.html#pragma_encoding).
+    pub fn encoding(&self) -> Result<Encoding> {
+        let value: String = self.pragma_query_value(None, "encoding", |row| row.get(0))?;
+        match value.as_str() {
+            "UTF-8" => Ok(Encoding::Utf8),
+            "UTF-16le" => Ok(Encoding::Utf16le),
+            "UTF-16be" => Ok(Encoding::Utf16be),
+            _ => Err(Error::SqliteFailure(
+                ffi::Error::new(ffi::SQLITE_MISMATCH),
+                Some(format!("Unknown encoding {value}")),
+            )),
+        }
+    }
+
+    /// Set the text encoding of the main database.
+    ///
+    /// The encoding is fixed once the database has been created (i.e. once
+    /// its first table is), and is shared by all the attached databases.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the database already exists with another
+    /// encoding.
+    pub fn set_encoding(&self, encoding: Encoding) -> Result<()> {
+        let value = match encoding {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16le => "UTF-16le",
+            Encoding::Utf16be => "UTF-16be",
+        };
+        self.pragma_update(None, "encoding", value)?;
+        let current = self.encoding()?;
+        if current != encoding {
+            return Err(misuse(format!(
+                "Encoding is still {current:?}: it can only be changed before the database is \
+                 created"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Auto-vacuum mode of the main database.
+    ///
+    /// See [`PRAGMA auto_vacuum`](https://sqlite.org/pragma.html#pragma_auto_vacuum).
+    pub fn auto_vacuum(&self) -> Result<AutoVacuum> {
+        let value: i64 = self.pragma_query_value(None, "auto_vacuum", |row| row.get(0))?;
+        Ok(match value {
+            1 => AutoVacuum::Full,
+            2 => AutoVacuum::Incremental,
+            _ => AutoVacuum::None,
+        })
+    }
+
+    /// Set the auto-vacuum mode of the main database.
+    ///
+    /// The mode is stored in the database file, so it only takes effect on a
+    /// new database, before any table is created, or after a `VACUUM`
+    /// (except when switching between `Full` and `Incremental`, which is
+    /// always possible). Otherwise the mode is left unchanged, without error:
+    /// check it with [`Connection::auto_vacuum`].
+    pub fn set_auto_vacuum(&self, mode: AutoVacuum) -> Result<()> {
+        let value = match mode {
+            AutoVacuum::None => 0,
+            AutoVacuum::Full => 1,
+            AutoVacuum::Incremental => 2,
+        };
+        self.pragma_update(None, "auto_vacuum", value)
+    }
+
+    /// Remove up to `pages` free pages (all of them if `None`) from the main
+    /// database file, which must be in [`AutoVacuum::Incremental`] mode (this
+    /// is a no-op otherwise).
+    ///
+    /// See [`PRAGMA incremental_vacuum`](https://sqlite.org/pragma.html#pragma_incremental_vacuum).
+    pub fn incremental_vacuum(&self, pages: Option<u32>) -> Result<()> {
+        let mut sql = Sql::new();
+        sql.push_pragma(None, "incremental_vacuum")?;
+        if let Some(pages) = pages {
+            sql.open_brace();
+            sql.push_int(i64::from(pages));
+            sql.close_brace();
+        }
+        // the pragma frees one page per step
+        let mut stmt = self.prepare(&sql)?;
+        let mut rows = stmt.query([])?;
+        while rows.next()?.is_some() {}
+        Ok(())
+    }
+
+    /// Run a checkpoint of the WAL file of the main database, in `mode`.
+    ///
+    /// See [`PRAGMA wal_checkpoint`](https://sqlite.org/pragma.html#pragma_wal_checkpoint).
+    pub fn wal_checkpoint(&self, mode: CheckpointMode) -> Result<WalCheckpoint> {
+        let mode = match mode {
+            CheckpointMode::Passive => "PASSIVE",
+            CheckpointMode::Full => "FULL",
+            CheckpointMode::Restart => "RESTART",
+            CheckpointMode::Truncate => "TRUNCATE",
+        };
+        let mut sql = Sql::new();
+        sql.push_pragma(None, "wal_checkpoint")?;
+        sql.open_brace();
+        sql.push_keyword(mode)?;
+        sql.close_brace();
+        self.query_row(&sql, [], |row| {
+            Ok(WalCheckpoint {
+                busy: row.get::<_, i64>(0)? != 0,
+                log_frames: row.get(1)?,
+                checkpointed_frames: row.get(2)?,
+            })
+        })
+    }
+
+    /// Run a checkpoint of the WAL files of all the databases, in `mode`,
+    /// when this connection is closed (or dropped), or do not if `None` (the
+    /// default).
+    ///
+    /// SQLite already checkpoints and deletes the WAL file when the last
+    /// connection to a database is closed; this also flushes (and, with
+    /// [`CheckpointMode::Truncate`], truncates) it when other connections
+    /// stay open or the WAL file is persistent. The checkpoint is best
+    /// effort: it is skipped without error if the database is busy.
+    pub fn set_checkpoint_on_close(&self, mode: Option<CheckpointMode>) {
+        self.db.borrow_mut().checkpoint_on_close = mode.map(|mode| match mode {
+            CheckpointMode::Passive => ffi::SQLITE_CHECKPOINT_PASSIVE,
+            CheckpointMode::Full => ffi::SQLITE_CHECKPOINT_FULL,
+            CheckpointMode::Restart => ffi::SQLITE_CHECKPOINT_RESTART,
+            CheckpointMode::Truncate => ffi::SQLITE_CHECKPOINT_TRUNCATE,
+        });
+    }
+
+    fn has_table(&self, name: &str) -> Result<bool> {
+        self.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [name],
+            |row| row.get(0),
+        )
+    }
+
+    fn user_tables(&self) -> Result<Vec<String>> {
+        let mut stmt = self.prepare(
+            "SELECT name FROM sqlite_master \
+             WHERE type = 'table' AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'",
+        )?;
+        let tables = stmt.query_map([], |row| row.get(0))?;
+        tables.collect()
+    }
+
+    /// Run `f` in a savepoint, with foreign key constraints disabled (or
+    /// deferred if already in a transaction, where they cannot be disabled).
+    fn without_foreign_keys<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce() -> Result<()>,
+    {
+        let enabled: bool = self.pragma_query_value(None, "foreign_keys", |row| row.get(0))?;
+        if enabled {
+            self.pragma_update(None, "foreign_keys", false)?;
+        }
+        let r = Savepoint::new_(self).and_then(|sp| {
+            self.pragma_update(None, "defer_foreign_keys", true)?;
+            f()?;
+            sp.commit()
+        });
+        if enabled {
+            let restored = self.pragma_update(None, "foreign_keys", true);
+            r.and(restored)
+        } else {
+            r
+        }
+    }
+
+    fn check_pragma(&self, pragma_name: &str, max_errors: Option<usize>) -> Result<Vec<String>> {
+        let mut sql = Sql::new();
+        sql.push_pragma(None, pragma_name)?;
+        if let Some(max_errors) = max_errors {
+            sql.open_brace();
+            sql.push_int(i64::try_from(max_errors).unwrap_or(i64::MAX));
+            sql.close_brace();
+        }
+        let mut stmt = self.prepare(&sql)?;
+        let mut problems = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        // a healthy database yields a single "ok" row
+        if problems.len() == 1 && problems[0] == "ok" {
+            problems.clear();
+        }
+        Ok(problems)
+    }
 }
 
 fn is_identifier(s: &str) -> bool {
@@ -324,10 +1181,409 @@ fn is_identifier_continue(c: char) -> bool {
 
 #[cfg(test)]
 mod test {
-    use super::Sql;
+    use super::{AutoVacuum, CacheSize, CheckpointMode, Encoding, OptimizeFlags, Sql, TempStore};
     use crate::pragma;
     use crate::{Connection, DatabaseName, Result};
 
+    #[test]
+    fn optimize() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER, y TEXT);
+             CREATE INDEX foo_x ON foo(x);
+             WITH RECURSIVE c(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM c LIMIT 1000)
+             INSERT INTO foo SELECT i % 10, 'y' FROM c;",
+        )?;
+        db.query_row("SELECT count(*) FROM foo WHERE x = 3", [], |_| Ok(()))?;
+        db.optimize()?;
+        db.optimize_with_mask(OptimizeFlags::default() | OptimizeFlags::ALL_TABLES)?;
+        // ANALYZE ran on `foo`
+        let n: i64 = db.one_column("SELECT count(*) FROM sqlite_stat1 WHERE tbl = 'foo'")?;
+        assert!(n > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn analyze() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        assert!(db.read_stat1()?.is_empty());
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER, y TEXT);
+             CREATE INDEX foo_x ON foo(x);
+             CREATE TABLE bar(z);
+             WITH RECURSIVE c(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM c LIMIT 100)
+             INSERT INTO foo SELECT i % 10, 'y' FROM c;
+             INSERT INTO bar VALUES (1);",
+        )?;
+        db.analyze(Some("foo"))?;
+        let stats = db.read_stat1()?;
+        assert_eq!(1, stats.len());
+        assert_eq!("foo", stats[0].table);
+        assert_eq!(Some("foo_x"), stats[0].index.as_deref());
+        assert_eq!("100 10", stats[0].stat);
+
+        db.analyze(None)?;
+        let stats = db.read_stat1()?;
+        assert!(stats.iter().any(|s| s.table == "bar" && s.index.is_none()));
+        db.analyze(Some("missing")).unwrap_err();
+        Ok(())
+    }
+
+    fn linked_tables() -> Result<Connection> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             CREATE TABLE parent(id INTEGER PRIMARY KEY AUTOINCREMENT);
+             CREATE TABLE child(id INTEGER PRIMARY KEY, parent_id REFERENCES parent(id));
+             CREATE TABLE grandchild(child_id REFERENCES child(id));
+             CREATE INDEX grandchild_child ON grandchild(child_id);
+             CREATE VIEW v AS SELECT * FROM parent;
+             INSERT INTO parent VALUES (1), (2);
+             INSERT INTO child VALUES (10, 1), (20, 2);
+             INSERT INTO grandchild VALUES (10), (20);",
+        )?;
+        Ok(db)
+    }
+
+    #[test]
+    fn clear_all_tables() -> Result<()> {
+        let db = linked_tables()?;
+        db.clear_all_tables()?;
+        let n: i64 = db.one_column(
+            "SELECT count(*) FROM sqlite_master WHERE type IN ('table', 'index') \
+             AND name NOT LIKE 'sqlite%'",
+        )?;
+        assert_eq!(0, n);
+        // foreign keys are enforced again
+        let fk: bool = db.pragma_query_value(None, "foreign_keys", |r| r.get(0))?;
+        assert!(fk);
+
+        // inside a transaction
+        let mut db = linked_tables()?;
+        let tx = db.transaction()?;
+        tx.clear_all_tables()?;
+        tx.commit()?;
+        assert!(!db.has_table("parent")?);
+        Ok(())
+    }
+
+    #[test]
+    fn truncate_all_tables() -> Result<()> {
+        let db = linked_tables()?;
+        db.truncate_all_tables()?;
+        for table in ["parent", "child", "grandchild"] {
+            let n: i64 = db.one_column(&format!("SELECT count(*) FROM {table}"))?;
+            assert_eq!(0, n, "{table}");
+        }
+        // AUTOINCREMENT starts over
+        db.execute("INSERT INTO parent DEFAULT VALUES", [])?;
+        assert_eq!(1, db.last_insert_rowid());
+        let fk: bool = db.pragma_query_value(None, "foreign_keys", |r| r.get(0))?;
+        assert!(fk);
+        Ok(())
+    }
+
+    #[test]
+    fn cache_size() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.set_cache_size(CacheSize::Pages(500))?;
+        assert_eq!(CacheSize::Pages(500), db.cache_size()?);
+        db.set_cache_size(CacheSize::KiB(8192))?;
+        assert_eq!(CacheSize::KiB(8192), db.cache_size()?);
+        let n: i64 = db.pragma_query_value(None, "cache_size", |r| r.get(0))?;
+        assert_eq!(-8192, n);
+
+        db.set_cache_size(CacheSize::KiB(0)).unwrap_err();
+        db.set_cache_size(CacheSize::Pages(u32::MAX)).unwrap_err();
+        assert_eq!(CacheSize::KiB(8192), db.cache_size()?);
+        Ok(())
+    }
+
+    #[test]
+    fn temp_store() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        for store in [TempStore::Memory, TempStore::File, TempStore::Default] {
+            db.set_temp_store(store)?;
+            assert_eq!(store, db.temp_store()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn mmap_size() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Connection::open(temp_dir.path().join("test.db3"))?;
+        let size = db.set_mmap_size(1 << 20)?;
+        assert!(size <= 1 << 20);
+        assert_eq!(size, db.mmap_size()?);
+        assert_eq!(size, db.set_mmap_size(1 << 20)?);
+        assert_eq!(0, db.set_mmap_size(0)?);
+        db.set_mmap_size(u64::MAX).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn page_size() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Connection::open(temp_dir.path().join("test.db3"))?;
+        db.set_page_size(8192)?;
+        assert_eq!(8192, db.page_size()?);
+        db.set_page_size(1000).unwrap_err();
+        db.set_page_size(256).unwrap_err();
+        db.set_page_size(1 << 17).unwrap_err();
+
+        db.execute_batch("CREATE TABLE foo(x)")?;
+        db.set_page_size(4096).unwrap_err();
+        assert_eq!(8192, db.page_size()?);
+        db.execute_batch("VACUUM")?;
+        assert_eq!(4096, db.page_size()?);
+        Ok(())
+    }
+
+    #[test]
+    fn encoding() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        assert_eq!(Encoding::Utf8, db.encoding()?);
+
+        let db = Connection::open_in_memory()?;
+        db.set_encoding(Encoding::Utf16le)?;
+        db.execute_batch("CREATE TABLE foo(t TEXT); INSERT INTO foo VALUES ('héllo');")?;
+        assert_eq!(Encoding::Utf16le, db.encoding()?);
+        let t: String = db.one_column("SELECT t FROM foo")?;
+        assert_eq!("héllo", t);
+        let len: i64 = db.one_column("SELECT length(CAST(t AS BLOB)) FROM foo")?;
+        assert_eq!(10, len);
+
+        db.set_encoding(Encoding::Utf8).unwrap_err();
+        db.set_encoding(Encoding::Utf16le)?;
+        Ok(())
+    }
+
+    #[test]
+    fn auto_vacuum() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Connection::open(temp_dir.path().join("test.db3"))?;
+        assert_eq!(AutoVacuum::None, db.auto_vacuum()?);
+        db.set_auto_vacuum(AutoVacuum::Incremental)?;
+        assert_eq!(AutoVacuum::Incremental, db.auto_vacuum()?);
+
+        db.execute_batch(
+            "CREATE TABLE foo(x BLOB);
+             WITH RECURSIVE c(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM c WHERE i < 100)
+             INSERT INTO foo SELECT zeroblob(4096) FROM c;
+             DELETE FROM foo;",
+        )?;
+        let free_pages = |db: &Connection| -> Result<i64> {
+            db.pragma_query_value(None, "freelist_count", |r| r.get(0))
+        };
+        let free = free_pages(&db)?;
+        assert!(free >= 100, "{free}");
+        db.incremental_vacuum(Some(10))?;
+        assert_eq!(free - 10, free_pages(&db)?);
+        db.incremental_vacuum(None)?;
+        assert_eq!(0, free_pages(&db)?);
+
+        // cannot switch to or from `None` once tables exist
+        db.set_auto_vacuum(AutoVacuum::None)?;
+        assert_eq!(AutoVacuum::Incremental, db.auto_vacuum()?);
+        db.set_auto_vacuum(AutoVacuum::Full)?;
+        assert_eq!(AutoVacuum::Full, db.auto_vacuum()?);
+        Ok(())
+    }
+
+    #[test]
+    fn wal_checkpoint() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = Connection::open(temp_dir.path().join("test.db3"))?;
+        let not_wal = db.wal_checkpoint(CheckpointMode::Passive)?;
+        assert_eq!(-1, not_wal.log_frames);
+
+        db.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        db.execute_batch("CREATE TABLE foo(x); INSERT INTO foo VALUES (1);")?;
+        let checkpoint = db.wal_checkpoint(CheckpointMode::Full)?;
+        assert!(!checkpoint.busy);
+        assert!(checkpoint.log_frames > 0);
+        assert_eq!(checkpoint.log_frames, checkpoint.checkpointed_frames);
+        let checkpoint = db.wal_checkpoint(CheckpointMode::Truncate)?;
+        assert_eq!(0, checkpoint.log_frames);
+        Ok(())
+    }
+
+    #[test]
+    fn checkpoint_on_close() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let wal = temp_dir.path().join("test.db3-wal");
+        // keeps the WAL file from being deleted on close
+        let other = Connection::open(&path)?;
+        other.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))?;
+        other.execute_batch("CREATE TABLE foo(x)")?;
+
+        let db = Connection::open(&path)?;
+        db.execute("INSERT INTO foo VALUES (1)", [])?;
+        db.close().map_err(|(_, e)| e)?;
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+
+        let db = Connection::open(&path)?;
+        db.set_checkpoint_on_close(Some(CheckpointMode::Truncate));
+        db.execute("INSERT INTO foo VALUES (2)", [])?;
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+        drop(db);
+        assert_eq!(0, std::fs::metadata(&wal).unwrap().len());
+        assert_eq!(2, other.one_column::<i64>("SELECT count(*) FROM foo")?);
+        Ok(())
+    }
+
+    #[test]
+    fn integrity_check() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x); CREATE INDEX foo_x ON foo(x);")?;
+        assert!(db.integrity_check(None)?.is_empty());
+        assert!(db.integrity_check(Some(1))?.is_empty());
+        assert!(db.quick_check()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn integrity_check_corrupted() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        {
+            let db = Connection::open(&path)?;
+            db.execute_batch(
+                "CREATE TABLE foo(x);
+                 CREATE INDEX foo_x ON foo(x);
+                 INSERT INTO foo VALUES (1), (2), (3);
+                 PRAGMA writable_schema = ON;
+                 DELETE FROM sqlite_master WHERE name = 'foo_x';",
+            )?;
+        }
+        // the index pages are now orphaned
+        let db = Connection::open(&path)?;
+        let problems = db.integrity_check(None)?;
+        assert!(!problems.is_empty());
+        assert!(problems[0].contains("never used"), "{problems:?}");
+        assert_eq!(db.integrity_check(Some(1))?, problems[..1]);
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_key_check() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "PRAGMA foreign_keys = OFF;
+             CREATE TABLE artist(id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE track(
+                 id INTEGER PRIMARY KEY,
+                 artist_id INTEGER REFERENCES artist(id)
+             );
+             CREATE TABLE \"my album\"(
+                 artist_id INTEGER PRIMARY KEY REFERENCES artist(id)
+             ) WITHOUT ROWID;
+             INSERT INTO artist VALUES (1, 'Dean Martin');
+             INSERT INTO track VALUES (10, 1), (11, 2), (12, NULL);
+             INSERT INTO \"my album\" VALUES (3);",
+        )?;
+        assert_eq!(
+            db.foreign_key_check(Some("track"))?,
+            [pragma::FkViolation {
+                table: "track".to_owned(),
+                rowid: Some(11),
+                referenced_table: "artist".to_owned(),
+                fk_index: 0,
+            }]
+        );
+        let violations = db.foreign_key_check(Some("my album"))?;
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rowid, None);
+        assert_eq!(db.foreign_key_check(None)?.len(), 2);
+        assert!(db.foreign_key_check(Some("artist"))?.is_empty());
+        db.foreign_key_check(Some("no_such_table")).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn table_info() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE track(
+                 album INTEGER NOT NULL,
+                 number INTEGER,
+                 title TEXT DEFAULT 'untitled',
+                 length,
+                 PRIMARY KEY (album, number)
+             );
+             CREATE INDEX track_title ON track(title);
+             CREATE UNIQUE INDEX track_length ON track(length, lower(title)) WHERE length > 0;",
+        )?;
+
+        let columns = db.table_info("track")?;
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["album", "number", "title", "length"]);
+        assert_eq!(
+            columns[0],
+            pragma::ColumnInfo {
+                cid: 0,
+                name: "album".to_owned(),
+                decl_type: "INTEGER".to_owned(),
+                not_null: true,
+                default: None,
+                pk: 1,
+                hidden: 0,
+            }
+        );
+        assert_eq!(columns[1].pk, 2);
+        assert_eq!(columns[2].default.as_deref(), Some("'untitled'"));
+        assert_eq!(columns[2].pk, 0);
+        assert_eq!(columns[3].decl_type, "");
+        assert!(db.table_info("no_such_table")?.is_empty());
+
+        let mut indexes = db.index_list("track")?;
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        let origins: Vec<_> = indexes
+            .iter()
+            .map(|i| (i.origin.as_str(), i.unique, i.partial))
+            .collect();
+        assert_eq!(
+            origins,
+            [("pk", true, false), ("c", true, true), ("c", false, false)]
+        );
+        assert_eq!(indexes[1].name, "track_length");
+        assert_eq!(indexes[2].name, "track_title");
+
+        let pk = db.index_info(&indexes[0].name)?;
+        let pk: Vec<_> = pk.iter().map(|c| c.name.as_deref()).collect();
+        assert_eq!(pk, [Some("album"), Some("number")]);
+        let length = db.index_info("track_length")?;
+        assert_eq!(length.len(), 2);
+        assert_eq!(length[0].cid, Some(3));
+        assert_eq!((length[1].cid, length[1].name.as_deref()), (None, None));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "modern_sqlite")]
+    fn table_xinfo() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE rect(
+                 w REAL,
+                 h REAL,
+                 area REAL GENERATED ALWAYS AS (w * h) VIRTUAL,
+                 perimeter REAL GENERATED ALWAYS AS (2 * (w + h)) STORED
+             );",
+        )?;
+        let columns = db.table_xinfo("rect")?;
+        let hidden: Vec<_> = columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.hidden))
+            .collect();
+        assert_eq!(hidden, [("w", 0), ("h", 0), ("area", 2), ("perimeter", 3)]);
+        // table_info omits generated columns
+        assert_eq!(db.table_info("rect")?.len(), 2);
+        Ok(())
+    }
+
     #[test]
     fn pragma_query_value() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -447,6 +1703,126 @@ mod test {
         assert_eq!("'value''; --'", sql.as_str());
     }
 
+    #[test]
+    fn quote_identifier() -> Result<()> {
+        assert_eq!(r#""foo""#, pragma::quote_identifier("foo"));
+        assert_eq!(r#""a""b""#, pragma::quote_identifier(r#"a"b"#));
+        assert_eq!(r#""""""#, pragma::quote_identifier(r#"""#));
+
+        let db = Connection::open_in_memory()?;
+        let table = r#"we"ird; DROP TABLE x; --"#;
+        let column = "select";
+        db.execute_batch(&format!(
+            "CREATE TABLE {}({} TEXT)",
+            pragma::quote_identifier(table),
+            pragma::quote_identifier(column)
+        ))?;
+        let name: String = db.one_column("SELECT name FROM sqlite_master")?;
+        assert_eq!(table, name);
+        assert_eq!(column, db.table_info(table)?[0].name);
+        Ok(())
+    }
+
+    #[test]
+    fn quote_string_literal() -> Result<()> {
+        assert_eq!("''", pragma::quote_string_literal(""));
+        assert_eq!("'it''s'", pragma::quote_string_literal("it's"));
+
+        let db = Connection::open_in_memory()?;
+        let s = "'; DROP TABLE x; -- ''";
+        let v: String = db.one_column(&format!("SELECT {}", pragma::quote_string_literal(s)))?;
+        assert_eq!(s, v);
+        Ok(())
+    }
+
+    #[test]
+    fn blob_literal() -> Result<()> {
+        assert_eq!("x''", pragma::blob_literal(&[]));
+        assert_eq!("x'0a'", pragma::blob_literal(&[10]));
+        assert_eq!(
+            "x'00017f80ff'",
+            pragma::blob_literal(&[0, 1, 127, 128, 255])
+        );
+
+        let db = Connection::open_in_memory()?;
+        for bytes in [&[][..], &[10], &[0, 1, 127, 128, 255]] {
+            let v: Vec<u8> = db.one_column(&format!("SELECT {}", pragma::blob_literal(bytes)))?;
+            assert_eq!(bytes, v);
+        }
+        let all: Vec<u8> = (0..=255).collect();
+        db.execute_batch(&format!(
+            "CREATE TABLE foo(x INTEGER, b BLOB DEFAULT ({}));
+             INSERT INTO foo(x) VALUES (1);",
+            pragma::blob_literal(&all)
+        ))?;
+        let v: Vec<u8> = db.one_column("SELECT b FROM foo")?;
+        assert_eq!(all, v);
+        Ok(())
+    }
+
+    #[test]
+    fn list_functions() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let functions = db.list_functions()?;
+        let lower = functions
+            .iter()
+            .find(|f| f.name == "lower" && f.n_arg == 1)
+            .unwrap();
+        assert!(lower.builtin);
+        assert_eq!("s", lower.kind);
+        // built-in aggregates can also be used as window functions
+        assert!(functions.iter().any(|f| f.name == "count" && f.kind == "w"));
+        assert!(functions.iter().all(|f| f.name != "my_func"));
+
+        #[cfg(feature = "functions")]
+        {
+            use crate::functions::FunctionFlags;
+            db.create_scalar_function(
+                "my_func",
+                2,
+                FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+                |_| Ok(0),
+            )?;
+            let functions = db.list_functions()?;
+            let f = functions.iter().find(|f| f.name == "my_func").unwrap();
+            assert!(!f.builtin);
+            assert_eq!(
+                ("s", "utf8", 2),
+                (f.kind.as_str(), f.encoding.as_str(), f.n_arg)
+            );
+            assert_ne!(0, f.flags & FunctionFlags::SQLITE_DETERMINISTIC.bits());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn list_collations() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let collations = db.list_collations()?;
+        assert!(collations.iter().any(|c| c == "NOCASE"));
+        assert!(collations.iter().all(|c| c != "reverse"));
+
+        #[cfg(feature = "collation")]
+        {
+            db.create_collation("reverse", |a, b| b.cmp(a))?;
+            assert!(db.list_collations()?.iter().any(|c| c == "reverse"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn list_modules() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let modules = db.list_modules()?;
+        assert!(modules.iter().all(|m| m != "rarray"));
+        #[cfg(feature = "array")]
+        {
+            crate::vtab::array::load_module(&db)?;
+            assert!(db.list_modules()?.iter().any(|m| m == "rarray"));
+        }
+        Ok(())
+    }
+
     #[test]
     fn locking_mode() -> Result<()> {
         let db = Connection::open_in_memory()?;
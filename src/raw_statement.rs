@@ -1,7 +1,7 @@
 use super::ffi;
 use super::StatementStatus;
-use crate::util::ParamIndexCache;
 use crate::util::SqliteMallocString;
+use crate::util::{ColumnIndexCache, ParamIndexCache};
 use std::ffi::CStr;
 use std::os::raw::c_int;
 use std::ptr;
@@ -14,6 +14,8 @@ pub struct RawStatement {
     tail: usize,
     // Cached indices of named parameters, computed on the fly.
     cache: ParamIndexCache,
+    // Cached indices of columns looked up by name, computed on the fly.
+    column_cache: ColumnIndexCache,
     // Cached SQL (trimmed) that we use as the key when we're in the statement
     // cache. This is None for statements which didn't come from the statement
     // cache.
@@ -34,6 +36,7 @@ impl RawStatement {
             ptr: stmt,
             tail,
             cache: ParamIndexCache::default(),
+            column_cache: ColumnIndexCache::default(),
             statement_cache_key: None,
         }
     }
@@ -100,6 +103,26 @@ impl RawStatement {
         }
     }
 
+    /// Whether column `idx` is named `name` (ASCII case-insensitively).
+    pub fn column_has_name(&self, idx: usize, name: &str) -> bool {
+        self.column_name(idx)
+            .is_some_and(|n| name.as_bytes().eq_ignore_ascii_case(n.to_bytes()))
+    }
+
+    /// Index of the first column named `name`, see
+    /// [`RawStatement::column_has_name`].
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        let matches = |i: usize| self.column_has_name(i, name);
+        self.column_cache.get_or_insert_with(name, matches, || {
+            (0..self.column_count()).find(|&i| matches(i))
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn column_index_scans(&self) -> usize {
+        self.column_cache.scans()
+    }
+
     #[inline]
     #[cfg(not(feature = "unlock_notify"))]
     pub fn step(&self) -> c_int {
@@ -135,6 +158,12 @@ impl RawStatement {
         }
     }
 
+    #[cfg(feature = "hooks")]
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        unsafe { ffi::sqlite3_stmt_busy(self.ptr) != 0 }
+    }
+
     #[inline]
     pub fn reset(&self) -> c_int {
         unsafe { ffi::sqlite3_reset(self.ptr) }
@@ -230,7 +259,17 @@ impl RawStatement {
         unsafe { ffi::sqlite3_stmt_isexplain(self.ptr) }
     }
 
-    // TODO sqlite3_normalized_sql (https://sqlite.org/c3ref/expanded_sql.html) // 3.27.0 + SQLITE_ENABLE_NORMALIZE
+    #[cfg(feature = "normalize")] // 3.27.0
+    pub fn normalized_sql(&self) -> Option<&CStr> {
+        unsafe {
+            let ptr = ffi::sqlite3_normalized_sql(self.ptr);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(ptr))
+            }
+        }
+    }
 }
 
 impl Drop for RawStatement {
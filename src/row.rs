@@ -1,20 +1,31 @@
 use fallible_iterator::FallibleIterator;
 use fallible_streaming_iterator::FallibleStreamingIterator;
+use std::collections::HashMap;
 use std::convert;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::{Error, Result, Statement};
-use crate::types::{FromSql, FromSqlError, ValueRef};
+use crate::types::{FromSql, FromSqlError, Value, ValueRef};
 
 /// An handle for the resulting rows of a query.
 #[must_use = "Rows is lazy and will do nothing unless consumed"]
 pub struct Rows<'stmt> {
     pub(crate) stmt: Option<&'stmt Statement<'stmt>>,
     row: Option<Row<'stmt>>,
+    // `Connection::lenient_bool` when the query started
+    lenient_bool: bool,
+    #[cfg(feature = "tracing")]
+    span: Option<crate::instrument::Timed>,
 }
 
 impl<'stmt> Rows<'stmt> {
     #[inline]
     fn reset(&mut self) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        if let Some(span) = self.span.take() {
+            span.finish();
+        }
         if let Some(stmt) = self.stmt.take() {
             stmt.reset()
         } else {
@@ -41,6 +52,26 @@ impl<'stmt> Rows<'stmt> {
         Ok((*self).get())
     }
 
+    /// Like [`Rows::next`], but copy the row into an [`OwnedRow`] which does
+    /// not borrow the statement.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, OwnedRow, Result};
+    /// fn load(conn: &Connection) -> Result<Vec<OwnedRow>> {
+    ///     let mut stmt = conn.prepare("SELECT * FROM people")?;
+    ///     let mut rows = stmt.query([])?;
+    ///     let mut buffer = Vec::new();
+    ///     while let Some(row) = rows.next_owned()? {
+    ///         buffer.push(row);
+    ///     }
+    ///     Ok(buffer)
+    /// }
+    /// ```
+    #[inline]
+    pub fn next_owned(&mut self) -> Result<Option<OwnedRow>> {
+        self.next()?.map(OwnedRow::from_row).transpose()
+    }
+
     /// Map over this `Rows`, converting it to a [`Map`], which
     /// implements `FallibleIterator`.
     /// ```rust,no_run
@@ -81,6 +112,31 @@ impl<'stmt> Rows<'stmt> {
         AndThenRows { rows: self, map: f }
     }
 
+    /// Check `flag` before each row, converting this `Rows` to an
+    /// [`InterruptibleRows`].
+    ///
+    /// Once `flag` is set (e.g. from another thread), the next call to `next`
+    /// resets the statement and returns `Err(Error::QueryInterrupted)`.
+    /// Unlike [`InterruptHandle::interrupt`](crate::InterruptHandle::interrupt),
+    /// this only takes effect between rows, but leaves other statements of the
+    /// connection alone.
+    /// ```rust,no_run
+    /// # use rusqlite::{Result, Statement};
+    /// # use std::sync::atomic::AtomicBool;
+    /// fn sum(stmt: &mut Statement, cancel: &AtomicBool) -> Result<i64> {
+    ///     let mut rows = stmt.query([])?.interruptible(cancel);
+    ///     let mut total = 0;
+    ///     while let Some(row) = rows.next()? {
+    ///         total += row.get::<_, i64>(0)?;
+    ///     }
+    ///     Ok(total)
+    /// }
+    /// ```
+    #[inline]
+    pub fn interruptible(self, flag: &AtomicBool) -> InterruptibleRows<'stmt, '_> {
+        InterruptibleRows { rows: self, flag }
+    }
+
     /// Give access to the underlying statement
     #[must_use]
     pub fn as_ref(&self) -> Option<&Statement<'stmt>> {
@@ -91,9 +147,14 @@ impl<'stmt> Rows<'stmt> {
 impl<'stmt> Rows<'stmt> {
     #[inline]
     pub(crate) fn new(stmt: &'stmt Statement<'stmt>) -> Rows<'stmt> {
+        #[cfg(feature = "metrics")]
+        stmt.record_query();
         Rows {
             stmt: Some(stmt),
             row: None,
+            lenient_bool: stmt.lenient_bool(),
+            #[cfg(feature = "tracing")]
+            span: Some(stmt.query_span()),
         }
     }
 
@@ -132,7 +193,13 @@ where
     #[inline]
     fn next(&mut self) -> Result<Option<B>> {
         match self.rows.next()? {
-            Some(v) => Ok(Some((self.f)(v)?)),
+            Some(v) => match (self.f)(v) {
+                Ok(b) => Ok(Some(b)),
+                Err(e) => {
+                    let _ = self.rows.reset(); // stop on first error
+                    Err(e)
+                }
+            },
             None => Ok(None),
         }
     }
@@ -142,6 +209,9 @@ where
 ///
 /// `F` is used to transform the _streaming_ iterator into a _standard_
 /// iterator.
+///
+/// Iteration stops at the first error (whether returned by SQLite or by `F`):
+/// the statement is reset and subsequent calls to `next` return `None`.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct MappedRows<'stmt, F> {
     rows: Rows<'stmt>,
@@ -157,15 +227,82 @@ where
     #[inline]
     fn next(&mut self) -> Option<Result<T>> {
         let map = &mut self.map;
-        self.rows
+        let next = self
+            .rows
             .next()
             .transpose()
-            .map(|row_result| row_result.and_then(map))
+            .map(|row_result| row_result.and_then(map));
+        if let Some(Err(_)) = next {
+            let _ = self.rows.reset(); // stop on first error
+        }
+        next
+    }
+}
+
+impl<'stmt, F> MappedRows<'stmt, F> {
+    /// Group the mapped rows into vectors of `size` rows, the last one
+    /// holding the remaining rows (possibly fewer than `size`).
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn send_names(conn: &Connection, send: impl Fn(Vec<String>)) -> Result<()> {
+    ///     let mut stmt = conn.prepare("SELECT name FROM people")?;
+    ///     for chunk in stmt.query_map([], |row| row.get(0))?.chunks(500) {
+    ///         send(chunk?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// Iteration stops at the first error, and the rows of the chunk being
+    /// filled are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[inline]
+    pub fn chunks(self, size: usize) -> Chunks<'stmt, F> {
+        assert!(size != 0, "chunk size must be non-zero");
+        Chunks { rows: self, size }
+    }
+}
+
+/// An iterator over the mapped resulting rows of a query, grouped into
+/// vectors.
+///
+/// See [`MappedRows::chunks`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Chunks<'stmt, F> {
+    rows: MappedRows<'stmt, F>,
+    size: usize,
+}
+
+impl<T, F> Iterator for Chunks<'_, F>
+where
+    F: FnMut(&Row<'_>) -> Result<T>,
+{
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Result<Vec<T>>> {
+        let mut chunk = Vec::new();
+        for row in self.rows.by_ref().take(self.size) {
+            match row {
+                Ok(v) => chunk.push(v),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(Ok(chunk))
+        }
     }
 }
 
 /// An iterator over the mapped resulting rows of a query, with an Error type
 /// unifying with Error.
+///
+/// Like [`MappedRows`], iteration stops at the first error.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct AndThenRows<'stmt, F> {
     rows: Rows<'stmt>,
@@ -182,10 +319,56 @@ where
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         let map = &mut self.map;
-        self.rows
+        let next = self
+            .rows
             .next()
             .transpose()
-            .map(|row_result| row_result.map_err(E::from).and_then(map))
+            .map(|row_result| row_result.map_err(E::from).and_then(map));
+        if let Some(Err(_)) = next {
+            let _ = self.rows.reset(); // stop on first error
+        }
+        next
+    }
+}
+
+/// The resulting rows of a query, checking a cancellation flag before each
+/// row.
+///
+/// See [`Rows::interruptible`].
+#[must_use = "Rows is lazy and will do nothing unless consumed"]
+pub struct InterruptibleRows<'stmt, 'flag> {
+    rows: Rows<'stmt>,
+    flag: &'flag AtomicBool,
+}
+
+impl<'stmt> InterruptibleRows<'stmt, '_> {
+    /// Like [`Rows::next`], but return `Err(Error::QueryInterrupted)` if the
+    /// flag has been set.
+    #[allow(clippy::should_implement_trait)] // cannot implement Iterator
+    #[inline]
+    pub fn next(&mut self) -> Result<Option<&Row<'stmt>>> {
+        self.advance()?;
+        Ok((*self).get())
+    }
+}
+
+impl<'stmt> FallibleStreamingIterator for InterruptibleRows<'stmt, '_> {
+    type Error = Error;
+    type Item = Row<'stmt>;
+
+    #[inline]
+    fn advance(&mut self) -> Result<()> {
+        if self.rows.stmt.is_some() && self.flag.load(Ordering::Relaxed) {
+            let _ = self.rows.reset();
+            self.rows.row = None;
+            return Err(Error::QueryInterrupted);
+        }
+        self.rows.advance()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&Row<'stmt>> {
+        self.rows.get()
     }
 }
 
@@ -216,7 +399,10 @@ impl<'stmt> FallibleStreamingIterator for Rows<'stmt> {
         if let Some(stmt) = self.stmt {
             match stmt.step() {
                 Ok(true) => {
-                    self.row = Some(Row { stmt });
+                    self.row = Some(Row {
+                        stmt,
+                        lenient_bool: self.lenient_bool,
+                    });
                     Ok(())
                 }
                 Ok(false) => {
@@ -245,6 +431,7 @@ impl<'stmt> FallibleStreamingIterator for Rows<'stmt> {
 /// A single result row of a query.
 pub struct Row<'stmt> {
     pub(crate) stmt: &'stmt Statement<'stmt>,
+    lenient_bool: bool,
 }
 
 impl<'stmt> Row<'stmt> {
@@ -285,19 +472,8 @@ impl<'stmt> Row<'stmt> {
     pub fn get<I: RowIndex, T: FromSql>(&self, idx: I) -> Result<T> {
         let idx = idx.idx(self.stmt)?;
         let value = self.stmt.value_ref(idx);
-        FromSql::column_result(value).map_err(|err| match err {
-            FromSqlError::InvalidType => Error::InvalidColumnType(
-                idx,
-                self.stmt.column_name_unwrap(idx).into(),
-                value.data_type(),
-            ),
-            FromSqlError::OutOfRange(i) => Error::IntegralValueOutOfRange(idx, i),
-            FromSqlError::Other(err) => {
-                Error::FromSqlConversionFailure(idx, value.data_type(), err)
-            }
-            FromSqlError::InvalidBlobSize { .. } => {
-                Error::FromSqlConversionFailure(idx, value.data_type(), Box::new(err))
-            }
+        column_result(idx, value, self.lenient_bool, || {
+            self.stmt.column_name_unwrap(idx).into()
         })
     }
 
@@ -344,6 +520,283 @@ impl<'stmt> Row<'stmt> {
     pub fn get_ref_unwrap<I: RowIndex>(&self, idx: I) -> ValueRef<'_> {
         self.get_ref(idx).unwrap()
     }
+
+    /// Returns a value whose `Debug` output shows the row as its column names
+    /// and values, e.g. `{ id: Integer(5), name: Text("hi") }` (for BLOBs,
+    /// only the size is shown).
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn dump(conn: &Connection) -> Result<()> {
+    ///     let mut stmt = conn.prepare("SELECT * FROM people")?;
+    ///     let mut rows = stmt.query([])?;
+    ///     while let Some(row) = rows.next()? {
+    ///         println!("{:?}", row.debug());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn debug(&self) -> impl std::fmt::Debug + '_ {
+        struct Name<'a>(&'a str);
+        impl std::fmt::Debug for Name<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+        struct ColumnValue<'a>(ValueRef<'a>);
+        impl std::fmt::Debug for ColumnValue<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self.0 {
+                    ValueRef::Null => f.write_str("Null"),
+                    ValueRef::Integer(i) => f.debug_tuple("Integer").field(&i).finish(),
+                    ValueRef::Real(r) => f.debug_tuple("Real").field(&r).finish(),
+                    ValueRef::Text(s) => f
+                        .debug_tuple("Text")
+                        .field(&String::from_utf8_lossy(s))
+                        .finish(),
+                    ValueRef::Blob(b) => write!(f, "Blob({} bytes)", b.len()),
+                }
+            }
+        }
+        struct RowDebug<'a, 'stmt>(&'a Row<'stmt>);
+        impl std::fmt::Debug for RowDebug<'_, '_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let stmt = self.0.stmt;
+                let mut dm = f.debug_map();
+                for c in 0..stmt.column_count() {
+                    dm.entry(
+                        &Name(stmt.column_name(c).unwrap_or("?")),
+                        &ColumnValue(stmt.value_ref(c)),
+                    );
+                }
+                dm.finish()
+            }
+        }
+        RowDebug(self)
+    }
+
+    /// Iterate over the columns of the result row, as pairs of column name
+    /// and value, in order.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn print_row(conn: &Connection) -> Result<()> {
+    ///     conn.query_row("SELECT * FROM people", [], |row| {
+    ///         for (name, value) in row.iter() {
+    ///             println!("{name} = {:?}", value.data_type());
+    ///         }
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics when a column name is not valid UTF-8.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, ValueRef<'_>)> + '_ {
+        let stmt = self.stmt;
+        (0..stmt.column_count()).map(move |c| {
+            let name = stmt
+                .column_name(c)
+                .expect("column index is in the range of the columns");
+            (name, stmt.value_ref(c))
+        })
+    }
+
+    /// Get the first columns of the result row as a tuple (of up to 16
+    /// elements), e.g. `|row| row.try_into_tuple::<(i64, String)>()` in place
+    /// of `|row| Ok((row.get(0)?, row.get(1)?))`.
+    ///
+    /// ## Failure
+    ///
+    /// Returns the same errors as [`Row::get`] for any of the tuple elements,
+    /// in particular `Error::InvalidColumnIndex` if the tuple has more
+    /// elements than the row has columns.
+    #[inline]
+    pub fn try_into_tuple<'a, T>(&'a self) -> Result<T>
+    where
+        T: convert::TryFrom<&'a Row<'a>, Error = Error>,
+    {
+        T::try_from(self)
+    }
+}
+
+/// Convert the value of column `idx`, mapping conversion errors like
+/// [`Row::get`] does.
+fn column_result<T: FromSql>(
+    idx: usize,
+    value: ValueRef<'_>,
+    lenient_bool: bool,
+    column_name: impl FnOnce() -> String,
+) -> Result<T> {
+    let result = if lenient_bool {
+        T::column_result_lenient_bool(value)
+    } else {
+        T::column_result(value)
+    };
+    result.map_err(|err| match err {
+        FromSqlError::InvalidType => {
+            Error::InvalidColumnType(idx, column_name(), value.data_type())
+        }
+        FromSqlError::OutOfRange(i) => Error::IntegralValueOutOfRange(idx, i),
+        FromSqlError::Other(err) => Error::FromSqlConversionFailure(idx, value.data_type(), err),
+        FromSqlError::InvalidBlobSize { .. } => {
+            Error::FromSqlConversionFailure(idx, value.data_type(), Box::new(err))
+        }
+    })
+}
+
+/// A result row detached from its statement, as returned by
+/// [`Rows::next_owned`].
+///
+/// The values are copied out of SQLite, so an `OwnedRow` can be buffered,
+/// sent to another thread or returned from a function after the statement is
+/// gone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedRow {
+    names: Vec<String>,
+    values: Vec<Value>,
+    lenient_bool: bool,
+}
+
+impl OwnedRow {
+    /// Get the value of the column at `idx`.
+    ///
+    /// ## Failure
+    ///
+    /// Returns the same errors as [`Row::get`].
+    pub fn get<T: FromSql>(&self, idx: usize) -> Result<T> {
+        let value = self.values.get(idx).ok_or(Error::InvalidColumnIndex(idx))?;
+        column_result(idx, value.into(), self.lenient_bool, || {
+            self.names[idx].clone()
+        })
+    }
+
+    /// Get the value of the column named `name` (compared
+    /// case-insensitively, like [`Row::get`] does).
+    ///
+    /// ## Failure
+    ///
+    /// Returns an `Error::InvalidColumnName` if there is no such column, or
+    /// the same errors as [`Row::get`].
+    pub fn get_by_name<T: FromSql>(&self, name: &str) -> Result<T> {
+        self.get(self.column_index(name)?)
+    }
+
+    /// Returns the index of the column named `name`, see
+    /// [`Statement::column_index`].
+    pub fn column_index(&self, name: &str) -> Result<usize> {
+        self.names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::InvalidColumnName(name.to_owned()))
+    }
+
+    /// Returns the names of the columns.
+    #[inline]
+    #[must_use]
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the values of the columns.
+    #[inline]
+    #[must_use]
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Take the values of the columns.
+    #[inline]
+    #[must_use]
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+}
+
+impl FromRow for OwnedRow {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        let n = row.stmt.column_count();
+        let mut names = Vec::with_capacity(n);
+        let mut values = Vec::with_capacity(n);
+        for i in 0..n {
+            names.push(row.stmt.column_name(i)?.to_owned());
+            values.push(row.get_ref(i)?.into());
+        }
+        Ok(OwnedRow {
+            names,
+            values,
+            lenient_bool: row.lenient_bool,
+        })
+    }
+}
+
+/// The result of a query stored column by column, as returned by
+/// [`Statement::query_columns`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnarResult {
+    names: Vec<String>,
+    columns: Vec<Vec<Value>>,
+}
+
+impl ColumnarResult {
+    pub(crate) fn read(mut rows: Rows<'_>) -> Result<ColumnarResult> {
+        let (names, mut columns) = match rows.as_ref() {
+            Some(stmt) => {
+                let names: Vec<String> =
+                    stmt.column_names().into_iter().map(String::from).collect();
+                let columns = vec![Vec::new(); names.len()];
+                (names, columns)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+        while let Some(row) = rows.next()? {
+            for (i, column) in columns.iter_mut().enumerate() {
+                column.push(row.get_ref(i)?.into());
+            }
+        }
+        Ok(ColumnarResult { names, columns })
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    #[must_use]
+    pub fn row_count(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    /// Returns the names of the columns.
+    #[inline]
+    #[must_use]
+    pub fn column_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Returns the values of the column at `idx`, one per row.
+    #[inline]
+    #[must_use]
+    pub fn column(&self, idx: usize) -> Option<&[Value]> {
+        self.columns.get(idx).map(Vec::as_slice)
+    }
+
+    /// Returns the values of the column named `name` (compared
+    /// case-insensitively), one per row.
+    #[must_use]
+    pub fn column_by_name(&self, name: &str) -> Option<&[Value]> {
+        let idx = self
+            .names
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))?;
+        self.column(idx)
+    }
+
+    /// Take the columns, each one holding one value per row.
+    #[inline]
+    #[must_use]
+    pub fn into_columns(self) -> Vec<Vec<Value>> {
+        self.columns
+    }
 }
 
 impl<'stmt> AsRef<Statement<'stmt>> for Row<'stmt> {
@@ -402,7 +855,8 @@ mod sealed {
 
 /// A trait implemented by types that can index into columns of a row.
 ///
-/// It is only implemented for `usize` and `&str`.
+/// It is only implemented for `usize` and `&str`. A `&str` designates the
+/// first column with that name, see [`Statement::column_index`].
 pub trait RowIndex: sealed::Sealed {
     /// Returns the index of the appropriate column, or `None` if no such
     /// column exists.
@@ -427,6 +881,56 @@ impl RowIndex for &'_ str {
     }
 }
 
+/// A type that can be built from a whole result row, see
+/// [`Statement::query_as`].
+///
+/// Implemented for tuples of up to 16 [`FromSql`] elements (taken from the
+/// first columns), for `Vec<Value>` (all columns in order), for
+/// `HashMap<String, Value>` (all columns by name) and for [`OwnedRow`].
+/// Implement it for your own
+/// types to map rows to structs without a macro:
+///
+/// ```rust,no_run
+/// # use rusqlite::{Connection, FromRow, Result, Row};
+/// struct Person {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// impl FromRow for Person {
+///     fn from_row(row: &Row<'_>) -> Result<Person> {
+///         Ok(Person {
+///             id: row.get("id")?,
+///             name: row.get("name")?,
+///         })
+///     }
+/// }
+///
+/// fn get_people(conn: &Connection) -> Result<Vec<Person>> {
+///     let mut stmt = conn.prepare("SELECT id, name FROM people")?;
+///     let rows = stmt.query_as::<Person, _>([])?;
+///     rows.collect()
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Build `Self` from the current row.
+    fn from_row(row: &Row<'_>) -> Result<Self>;
+}
+
+impl FromRow for Vec<Value> {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        (0..row.stmt.column_count()).map(|i| row.get(i)).collect()
+    }
+}
+
+impl<S: BuildHasher + Default> FromRow for HashMap<String, Value, S> {
+    fn from_row(row: &Row<'_>) -> Result<Self> {
+        (0..row.stmt.column_count())
+            .map(|i| Ok((row.stmt.column_name(i)?.to_owned(), row.get(i)?)))
+            .collect()
+    }
+}
+
 macro_rules! tuple_try_from_row {
     ($($field:ident),*) => {
         impl<'a, $($field,)*> convert::TryFrom<&'a Row<'a>> for ($($field,)*) where $($field: FromSql,)* {
@@ -445,6 +949,13 @@ macro_rules! tuple_try_from_row {
                 Ok(($($field,)*))
             }
         }
+
+        impl<$($field,)*> FromRow for ($($field,)*) where $($field: FromSql,)* {
+            #[inline]
+            fn from_row(row: &Row<'_>) -> Result<Self> {
+                convert::TryFrom::try_from(row)
+            }
+        }
     }
 }
 
@@ -463,7 +974,130 @@ tuples_try_from_row!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
 
 #[cfg(test)]
 mod tests {
-    use crate::{Connection, Result};
+    use std::collections::HashMap;
+
+    use crate::types::ValueRef;
+    use crate::{Connection, Error, Result};
+
+    fn mixed_rows() -> Result<Connection> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TABLE test (a);
+             INSERT INTO test VALUES (1), ('two'), (3);",
+        )?;
+        Ok(conn)
+    }
+
+    #[test]
+    fn test_mapped_rows_stop_on_error() -> Result<()> {
+        let conn = mixed_rows()?;
+        let mut stmt = conn.prepare("SELECT a FROM test ORDER BY rowid")?;
+        {
+            let mut rows = stmt.query_map([], |r| r.get::<_, i64>(0))?;
+            assert_eq!(rows.next(), Some(Ok(1)));
+            assert!(matches!(
+                rows.next(),
+                Some(Err(Error::InvalidColumnType(..)))
+            ));
+            assert_eq!(rows.next(), None);
+            assert_eq!(rows.next(), None);
+        }
+        // the statement was reset and can be run again
+        let rows = stmt.query_map([], |r| r.get::<_, crate::types::Value>(0))?;
+        assert_eq!(rows.count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_and_then_rows_stop_on_error() -> Result<()> {
+        let conn = mixed_rows()?;
+        let mut stmt = conn.prepare("SELECT a FROM test ORDER BY rowid")?;
+        {
+            let mut rows = stmt.query_and_then([], |r| r.get::<_, i64>(0))?;
+            assert_eq!(rows.next(), Some(Ok(1)));
+            assert!(rows.next().unwrap().is_err());
+            assert_eq!(rows.next(), None);
+        }
+        assert_eq!(stmt.query_and_then([], |r| r.get::<_, i64>(0))?.count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunks() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 1001) \
+             SELECT x FROM n",
+        )?;
+        let chunks = stmt
+            .query_map([], |r| r.get::<_, i64>(0))?
+            .chunks(100)
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(11, chunks.len());
+        assert!(chunks[..10].iter().all(|c| c.len() == 100));
+        assert_eq!(vec![1001], chunks[10]);
+        assert_eq!((1..=1001).collect::<Vec<_>>(), chunks.concat());
+
+        let conn = mixed_rows()?;
+        let mut stmt = conn.prepare("SELECT a FROM test ORDER BY rowid")?;
+        let mut chunks = stmt.query_map([], |r| r.get::<_, i64>(0))?.chunks(2);
+        assert!(matches!(
+            chunks.next(),
+            Some(Err(Error::InvalidColumnType(..)))
+        ));
+        assert!(chunks.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_stop_on_error() -> Result<()> {
+        use fallible_iterator::FallibleIterator;
+        let conn = mixed_rows()?;
+        let mut stmt = conn.prepare("SELECT a FROM test ORDER BY rowid")?;
+        let mut rows = stmt.query([])?.map(|r| r.get::<_, i64>(0));
+        assert_eq!(rows.next()?, Some(1));
+        rows.next().unwrap_err();
+        assert_eq!(rows.next()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interruptible_rows() -> Result<()> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) \
+             SELECT x FROM c LIMIT 1000000",
+        )?;
+        let cancel = AtomicBool::new(false);
+        let mut rows = stmt.query([])?.interruptible(&cancel);
+        let mut count = 0;
+        let err = loop {
+            match rows.next() {
+                Ok(Some(row)) => {
+                    count += 1;
+                    assert_eq!(count, row.get::<_, i64>(0)?);
+                    if count == 100 {
+                        std::thread::scope(|s| {
+                            s.spawn(|| cancel.store(true, Ordering::Relaxed));
+                        });
+                    }
+                }
+                Ok(None) => panic!("not interrupted"),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(Error::QueryInterrupted, err);
+        assert_eq!(100, count);
+        assert!(rows.next()?.is_none());
+        drop(rows);
+
+        // the statement can be reused
+        cancel.store(false, Ordering::Relaxed);
+        let mut rows = stmt.query([])?.interruptible(&cancel);
+        assert_eq!(1, rows.next()?.unwrap().get::<_, i64>(0)?);
+        Ok(())
+    }
 
     #[test]
     fn test_try_from_row_for_tuple_1() -> Result<()> {
@@ -501,6 +1135,123 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_next_owned() -> Result<()> {
+        use super::OwnedRow;
+        use crate::types::Value;
+
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TABLE people (id INTEGER, name TEXT);
+             INSERT INTO people VALUES (1, 'Alice'), (2, NULL);",
+        )?;
+        let buffer = {
+            let mut stmt = conn.prepare("SELECT id, name AS Name FROM people ORDER BY id")?;
+            let mut rows = stmt.query([])?;
+            let mut buffer: Vec<OwnedRow> = Vec::new();
+            while let Some(row) = rows.next_owned()? {
+                buffer.push(row);
+            }
+            buffer
+        };
+        // the statement is gone, rows can be sent to another thread
+        let buffer = std::thread::spawn(move || buffer).join().unwrap();
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer[0].column_names(), ["id", "Name"]);
+        assert_eq!(buffer[0].get::<i64>(0)?, 1);
+        assert_eq!(buffer[0].get_by_name::<String>("name")?, "Alice");
+        assert_eq!(buffer[1].get_by_name::<Option<String>>("NAME")?, None);
+        assert_eq!(buffer[1].values(), [Value::Integer(2), Value::Null]);
+
+        assert_eq!(
+            buffer[1].get::<i64>(2).unwrap_err(),
+            Error::InvalidColumnIndex(2)
+        );
+        assert_eq!(
+            buffer[1].get_by_name::<i64>("age").unwrap_err(),
+            Error::InvalidColumnName("age".to_owned())
+        );
+        assert!(matches!(
+            buffer[0].get::<i64>(1).unwrap_err(),
+            Error::InvalidColumnType(1, ref name, _) if name == "Name"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_debug() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare(
+            "SELECT 5 AS id, 'hi' AS name, 1.5 AS score, x'010203' AS data, NULL AS other",
+        )?;
+        let mut rows = stmt.query([])?;
+        let row = rows.next()?.unwrap();
+        assert_eq!(
+            format!("{:?}", row.debug()),
+            r#"{id: Integer(5), name: Text("hi"), score: Real(1.5), data: Blob(3 bytes), other: Null}"#
+        );
+        // the row is still usable
+        assert_eq!(row.get::<_, i64>("id")?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_iter() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let mut stmt = conn.prepare("SELECT 5 AS id, 'hi' AS name, NULL AS other")?;
+        let mut rows = stmt.query([])?;
+        let row = rows.next()?.unwrap();
+        assert_eq!(3, row.iter().len());
+        let columns: HashMap<&str, ValueRef<'_>> = row.iter().collect();
+        assert_eq!(
+            HashMap::from([
+                ("id", ValueRef::Integer(5)),
+                ("name", ValueRef::Text(b"hi")),
+                ("other", ValueRef::Null),
+            ]),
+            columns
+        );
+        let names: Vec<&str> = row.iter().map(|(name, _)| name).collect();
+        assert_eq!(vec!["id", "name", "other"], names);
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_into_tuple() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TABLE test (a INTEGER, b TEXT, c REAL, d BLOB, e INTEGER);
+             INSERT INTO test VALUES (1, 'one', 1.5, x'01', NULL);",
+        )?;
+        let mut stmt = conn.prepare("SELECT a, b FROM test")?;
+        let val = stmt.query_row([], |r| r.try_into_tuple::<(i64, String)>())?;
+        assert_eq!(val, (1, "one".to_owned()));
+
+        let mut stmt = conn.prepare("SELECT a, b, c FROM test")?;
+        let val: Vec<(i64, String, f64)> = stmt
+            .query_map([], |r| r.try_into_tuple())?
+            .collect::<Result<_>>()?;
+        assert_eq!(val, [(1, "one".to_owned(), 1.5)]);
+
+        let mut stmt = conn.prepare("SELECT * FROM test")?;
+        let val = stmt.query_row([], |r| {
+            r.try_into_tuple::<(i64, String, f64, Vec<u8>, Option<i64>)>()
+        })?;
+        assert_eq!(val, (1, "one".to_owned(), 1.5, vec![1], None));
+
+        let err = stmt
+            .query_row([], |r| r.try_into_tuple::<(i64, i64)>())
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(1, ..)), "{err:?}");
+        let err = stmt
+            .query_row([], |r| {
+                r.try_into_tuple::<(i64, String, f64, Vec<u8>, Option<i64>, i64)>()
+            })
+            .unwrap_err();
+        assert_eq!(err, Error::InvalidColumnIndex(5));
+        Ok(())
+    }
+
     #[test]
     fn test_try_from_row_for_tuple_16() -> Result<()> {
         use std::convert::TryFrom;
@@ -0,0 +1,213 @@
+use crate::pragma::Sql;
+use crate::types::{FromSql, FromSqlError, FromSqlResult, ValueRef};
+use crate::{Connection, DatabaseName, Result};
+
+/// Kind of a [`SchemaObject`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SchemaObjectType {
+    /// A table, including virtual tables.
+    Table,
+    /// An index.
+    Index,
+    /// A view.
+    View,
+    /// A trigger.
+    Trigger,
+}
+
+impl FromSql for SchemaObjectType {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value.as_str()? {
+            "table" => Ok(SchemaObjectType::Table),
+            "index" => Ok(SchemaObjectType::Index),
+            "view" => Ok(SchemaObjectType::View),
+            "trigger" => Ok(SchemaObjectType::Trigger),
+            other => Err(FromSqlError::Other(
+                format!("unknown schema object type {other}").into(),
+            )),
+        }
+    }
+}
+
+/// An entry of the schema table (`sqlite_schema`, formerly `sqlite_master`),
+/// as returned by [`Connection::schema_objects`].
+///
+/// See [The Schema Table](https://sqlite.org/schematab.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SchemaObject {
+    /// Kind of object.
+    pub type_: SchemaObjectType,
+    /// Name of the object.
+    pub name: String,
+    /// Name of the table (or view) the object is associated with; the name
+    /// of the object itself for tables and views.
+    pub tbl_name: String,
+    /// SQL text creating the object, `None` for the indexes created
+    /// automatically for `UNIQUE` and `PRIMARY KEY` constraints.
+    pub sql: Option<String>,
+}
+
+impl SchemaObject {
+    /// Whether this is an object created and used by SQLite itself, whose
+    /// name starts with `sqlite_` (e.g. `sqlite_sequence`, `sqlite_stat1` or
+    /// `sqlite_autoindex_*`).
+    #[inline]
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        self.name.starts_with("sqlite_")
+    }
+}
+
+impl Connection {
+    /// List the objects (tables, indexes, views and triggers) of database
+    /// `schema`, in their order of creation.
+    ///
+    /// The internal objects, whose name starts with `sqlite_`, are only
+    /// listed if `include_internal` is `true`.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `schema` does not exist or if the underlying
+    /// SQLite call fails.
+    pub fn schema_objects(
+        &self,
+        schema: DatabaseName<'_>,
+        include_internal: bool,
+    ) -> Result<Vec<SchemaObject>> {
+        let mut master = Sql::new();
+        master.push_schema_name(schema);
+        master.push_dot();
+        master.push_keyword("sqlite_master")?;
+        let filter = if include_internal {
+            ""
+        } else {
+            " WHERE name NOT LIKE 'sqlite\\_%' ESCAPE '\\'"
+        };
+        let sql = format!(
+            "SELECT type, name, tbl_name, sql FROM {}{filter} ORDER BY rowid",
+            master.as_str()
+        );
+        let mut stmt = self.prepare(&sql)?;
+        let objects = stmt.query_map([], |row| {
+            Ok(SchemaObject {
+                type_: row.get(0)?,
+                name: row.get(1)?,
+                tbl_name: row.get(2)?,
+                sql: row.get(3)?,
+            })
+        })?;
+        objects.collect()
+    }
+
+    /// List the tables (including virtual tables) of database `schema`,
+    /// except the internal ones. See [`Connection::schema_objects`].
+    #[inline]
+    pub fn tables(&self, schema: DatabaseName<'_>) -> Result<Vec<SchemaObject>> {
+        self.schema_objects_of_type(schema, SchemaObjectType::Table)
+    }
+
+    /// List the views of database `schema`. See
+    /// [`Connection::schema_objects`].
+    #[inline]
+    pub fn views(&self, schema: DatabaseName<'_>) -> Result<Vec<SchemaObject>> {
+        self.schema_objects_of_type(schema, SchemaObjectType::View)
+    }
+
+    fn schema_objects_of_type(
+        &self,
+        schema: DatabaseName<'_>,
+        type_: SchemaObjectType,
+    ) -> Result<Vec<SchemaObject>> {
+        let mut objects = self.schema_objects(schema, false)?;
+        objects.retain(|o| o.type_ == type_);
+        Ok(objects)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SchemaObject, SchemaObjectType};
+    use crate::{Connection, DatabaseName, Result};
+
+    fn object(
+        type_: SchemaObjectType,
+        name: &str,
+        tbl_name: &str,
+        sql: Option<&str>,
+    ) -> SchemaObject {
+        SchemaObject {
+            type_,
+            name: name.to_owned(),
+            tbl_name: tbl_name.to_owned(),
+            sql: sql.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn test_schema_objects() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(id INTEGER PRIMARY KEY AUTOINCREMENT, x TEXT UNIQUE);
+             CREATE INDEX foo_x ON foo(x);
+             CREATE VIEW bar AS SELECT x FROM foo;
+             CREATE TRIGGER foo_insert AFTER INSERT ON foo BEGIN SELECT 1; END;",
+        )?;
+        let objects = db.schema_objects(DatabaseName::Main, false)?;
+        assert_eq!(
+            vec![
+                object(
+                    SchemaObjectType::Table,
+                    "foo",
+                    "foo",
+                    Some("CREATE TABLE foo(id INTEGER PRIMARY KEY AUTOINCREMENT, x TEXT UNIQUE)")
+                ),
+                object(
+                    SchemaObjectType::Index,
+                    "foo_x",
+                    "foo",
+                    Some("CREATE INDEX foo_x ON foo(x)")
+                ),
+                object(
+                    SchemaObjectType::View,
+                    "bar",
+                    "bar",
+                    Some("CREATE VIEW bar AS SELECT x FROM foo")
+                ),
+                object(
+                    SchemaObjectType::Trigger,
+                    "foo_insert",
+                    "foo",
+                    Some("CREATE TRIGGER foo_insert AFTER INSERT ON foo BEGIN SELECT 1; END")
+                ),
+            ],
+            objects
+        );
+
+        let all = db.schema_objects(DatabaseName::Main, true)?;
+        let internal: Vec<_> = all.iter().filter(|o| o.is_internal()).collect();
+        assert_eq!(2, internal.len());
+        assert!(internal
+            .iter()
+            .any(|o| o.name == "sqlite_sequence" && o.type_ == SchemaObjectType::Table));
+        assert!(internal
+            .iter()
+            .any(|o| o.type_ == SchemaObjectType::Index && o.sql.is_none()));
+
+        let tables = db.tables(DatabaseName::Main)?;
+        assert_eq!(
+            vec!["foo"],
+            tables.iter().map(|o| &o.name).collect::<Vec<_>>()
+        );
+        let views = db.views(DatabaseName::Main)?;
+        assert_eq!(
+            vec!["bar"],
+            views.iter().map(|o| &o.name).collect::<Vec<_>>()
+        );
+        assert!(db.tables(DatabaseName::Temp)?.is_empty());
+        db.schema_objects(DatabaseName::Attached("missing"), false)
+            .unwrap_err();
+        Ok(())
+    }
+}
@@ -15,7 +15,7 @@ use crate::error::{check, error_from_sqlite_code, Error};
 use crate::ffi;
 use crate::hooks::Action;
 use crate::types::ValueRef;
-use crate::{errmsg_to_string, str_to_cstring, Connection, DatabaseName, Result};
+use crate::{errmsg_to_string, len_as_c_int, str_to_cstring, Connection, DatabaseName, Result};
 
 // https://sqlite.org/session.html
 
@@ -117,6 +117,10 @@ impl Session<'_> {
     }
 
     /// Write the set of changes represented by this session to `output`.
+    ///
+    /// Unlike [`Session::changeset`], the changeset is never materialized in
+    /// memory as a whole: it is passed to `output` in chunks as it is
+    /// generated. Use [`Connection::apply_strm`] to apply it from a reader.
     #[inline]
     pub fn changeset_strm(&mut self, output: &mut dyn Write) -> Result<()> {
         let output_ref = &output;
@@ -586,6 +590,60 @@ impl Drop for Changegroup {
     }
 }
 
+/// Rebase changesets according to the conflicts resolved while applying
+/// other changesets.
+///
+/// When a node applies a remote changeset with
+/// [`Connection::apply_with_rebase`], its conflict resolutions are returned
+/// as a rebase buffer. A local changeset (made before the remote one was
+/// applied) can then be rebased with those buffers before being sent to the
+/// other nodes, so that it takes the resolutions into account and applies
+/// cleanly where the remote changeset was already applied.
+///
+/// See [Rebasing changesets](https://sqlite.org/session/rebaser.html).
+pub struct Rebaser {
+    r: *mut ffi::sqlite3_rebaser,
+}
+
+impl Rebaser {
+    /// Create a new rebaser, with no conflict resolution.
+    #[inline]
+    pub fn new() -> Result<Self> {
+        let mut r = ptr::null_mut();
+        check(unsafe { ffi::sqlite3rebaser_create(&mut r) })?;
+        Ok(Rebaser { r })
+    }
+
+    /// Add the conflict resolutions of `rebase`, a buffer returned by
+    /// [`Connection::apply_with_rebase`].
+    ///
+    /// To rebase against several changesets, configure their buffers in the
+    /// order in which the changesets were applied.
+    #[inline]
+    pub fn configure(&mut self, rebase: &[u8]) -> Result<()> {
+        let n = len_as_c_int(rebase.len())?;
+        check(unsafe { ffi::sqlite3rebaser_configure(self.r, n, rebase.as_ptr().cast()) })
+    }
+
+    /// Rebase changeset `cs` (not a patchset).
+    #[inline]
+    pub fn rebase(&self, cs: &Changeset) -> Result<Changeset> {
+        let mut n = 0;
+        let mut output: *mut c_void = ptr::null_mut();
+        check(unsafe { ffi::sqlite3rebaser_rebase(self.r, cs.n, cs.cs, &mut n, &mut output) })?;
+        Ok(Changeset { cs: output, n })
+    }
+}
+
+impl Drop for Rebaser {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            ffi::sqlite3rebaser_delete(self.r);
+        }
+    }
+}
+
 impl Connection {
     /// Apply a changeset to a database
     pub fn apply<F, C>(&self, cs: &Changeset, filter: Option<F>, conflict: C) -> Result<()>
@@ -620,7 +678,12 @@ impl Connection {
         })
     }
 
-    /// Apply a changeset to a database
+    /// Apply a changeset read from `input` to a database, see
+    /// [`Connection::apply`].
+    ///
+    /// The changeset is read in chunks, as it is applied, so it does not have
+    /// to fit in memory (e.g. when written by [`Session::changeset_strm`] to a
+    /// file).
     pub fn apply_strm<F, C>(
         &self,
         input: &mut dyn Read,
@@ -660,6 +723,57 @@ impl Connection {
     }
 }
 
+impl Connection {
+    /// Apply a changeset to a database, like [`Connection::apply`], and
+    /// return the conflict resolutions to give to
+    /// [`Rebaser::configure`].
+    ///
+    /// The returned buffer is empty if there was no conflict.
+    pub fn apply_with_rebase<F, C>(
+        &self,
+        cs: &Changeset,
+        filter: Option<F>,
+        conflict: C,
+    ) -> Result<Vec<u8>>
+    where
+        F: Fn(&str) -> bool + Send + 'static,
+        C: Fn(ConflictType, ChangesetItem) -> ConflictAction + Send + 'static,
+    {
+        let db = self.db.borrow_mut().db;
+
+        let filtered = filter.is_some();
+        let tuple = &mut (filter, conflict);
+        let mut rebase: *mut c_void = ptr::null_mut();
+        let mut n_rebase: c_int = 0;
+        let rc = unsafe {
+            ffi::sqlite3changeset_apply_v2(
+                db,
+                cs.n,
+                cs.cs,
+                if filtered {
+                    Some(call_filter::<F, C>)
+                } else {
+                    None
+                },
+                Some(call_conflict::<F, C>),
+                tuple as *mut (Option<F>, C) as *mut c_void,
+                &mut rebase,
+                &mut n_rebase,
+                0,
+            )
+        };
+        let buffer = if rebase.is_null() {
+            Vec::new()
+        } else {
+            let buffer = unsafe { from_raw_parts(rebase as *const u8, n_rebase as usize) }.to_vec();
+            unsafe { ffi::sqlite3_free(rebase) };
+            buffer
+        };
+        check(rc)?;
+        Ok(buffer)
+    }
+}
+
 /// Constants passed to the conflict handler
 /// See [here](https://sqlite.org/session.html#SQLITE_CHANGESET_CONFLICT) for details.
 #[allow(missing_docs)]
@@ -747,13 +861,16 @@ unsafe extern "C" fn x_input(p_in: *mut c_void, data: *mut c_void, len: *mut c_i
     }
     let bytes: &mut [u8] = from_raw_parts_mut(data as *mut u8, *len as usize);
     let input = p_in as *mut &mut dyn Read;
-    match (*input).read(bytes) {
-        Ok(n) => {
-            *len = n as i32; // TODO Validate: n = 0 may not mean the reader will always no longer be able to
-                             // produce bytes.
-            ffi::SQLITE_OK
+    loop {
+        match (*input).read(bytes) {
+            Ok(n) => {
+                *len = n as i32; // TODO Validate: n = 0 may not mean the reader will always no longer be able to
+                                 // produce bytes.
+                return ffi::SQLITE_OK;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return ffi::SQLITE_IOERR_READ, // TODO check if err is a (ru)sqlite Error => propagate
         }
-        Err(_) => ffi::SQLITE_IOERR_READ, // TODO check if err is a (ru)sqlite Error => propagate
     }
 }
 
@@ -777,7 +894,7 @@ mod test {
     use std::io::Read;
     use std::sync::atomic::{AtomicBool, Ordering};
 
-    use super::{Changeset, ChangesetIter, ConflictAction, ConflictType, Session};
+    use super::{Changeset, ChangesetIter, ConflictAction, ConflictType, Rebaser, Session};
     use crate::hooks::Action;
     use crate::{Connection, Result};
 
@@ -931,6 +1048,106 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_changeset_strm_convergence() -> Result<()> {
+        let schema = "CREATE TABLE foo(id INTEGER PRIMARY KEY, t TEXT, b BLOB);
+                      INSERT INTO foo VALUES (1, 'a', NULL), (2, 'b', x'00'), (3, 'c', NULL);";
+        let source = Connection::open_in_memory()?;
+        source.execute_batch(schema)?;
+        let target = Connection::open_in_memory()?;
+        target.execute_batch(schema)?;
+
+        let mut session = Session::new(&source)?;
+        session.attach(None)?;
+        source.execute_batch(
+            "INSERT INTO foo VALUES (4, 'd', zeroblob(100000));
+             UPDATE foo SET t = 'B' WHERE id = 2;
+             DELETE FROM foo WHERE id = 3;",
+        )?;
+        let mut changeset = Vec::new();
+        session.changeset_strm(&mut changeset)?;
+        assert!(changeset.len() > 100_000);
+
+        let mut input = changeset.as_slice();
+        target.apply_strm(
+            &mut input,
+            None::<fn(&str) -> bool>,
+            |_conflict_type, _item| ConflictAction::SQLITE_CHANGESET_ABORT,
+        )?;
+
+        let rows = |db: &Connection| -> Result<Vec<(i64, String, Option<Vec<u8>>)>> {
+            let mut stmt = db.prepare("SELECT id, t, b FROM foo ORDER BY id")?;
+            let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?;
+            rows.collect()
+        };
+        let expected = rows(&source)?;
+        assert_eq!(3, expected.len());
+        assert_eq!(expected, rows(&target)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rebaser() -> Result<()> {
+        let schema = "CREATE TABLE foo(id INTEGER PRIMARY KEY, x TEXT);
+                      INSERT INTO foo VALUES (1, 'orig');";
+        let nodes = [
+            Connection::open_in_memory()?,
+            Connection::open_in_memory()?,
+            Connection::open_in_memory()?,
+        ];
+        for node in &nodes {
+            node.execute_batch(schema)?;
+        }
+        let [a, b, c] = &nodes;
+        let changeset = |db: &Connection, sql: &str| -> Result<Changeset> {
+            let mut session = Session::new(db)?;
+            session.attach(None)?;
+            db.execute_batch(sql)?;
+            session.changeset()
+        };
+        let x = |db: &Connection| -> Result<String> { db.one_column("SELECT x FROM foo") };
+
+        // concurrent changes of the same row on a and b
+        let local = changeset(a, "UPDATE foo SET x = 'a' WHERE id = 1")?;
+        let remote = changeset(b, "UPDATE foo SET x = 'b' WHERE id = 1")?;
+        // c gets b's change first
+        c.apply(
+            &remote,
+            None::<fn(&str) -> bool>,
+            |_conflict_type, _item| ConflictAction::SQLITE_CHANGESET_ABORT,
+        )?;
+        // a gets b's change, and keeps its own value
+        let rebase =
+            a.apply_with_rebase(&remote, None::<fn(&str) -> bool>, |conflict_type, _item| {
+                assert_eq!(ConflictType::SQLITE_CHANGESET_DATA, conflict_type);
+                ConflictAction::SQLITE_CHANGESET_OMIT
+            })?;
+        assert!(!rebase.is_empty());
+        assert_eq!("a", x(a)?);
+
+        // a's change, as is, conflicts on c
+        static CONFLICTS: AtomicBool = AtomicBool::new(false);
+        c.apply(&local, None::<fn(&str) -> bool>, |_conflict_type, _item| {
+            CONFLICTS.store(true, Ordering::Relaxed);
+            ConflictAction::SQLITE_CHANGESET_ABORT
+        })
+        .unwrap_err();
+        assert!(CONFLICTS.load(Ordering::Relaxed));
+        assert_eq!("b", x(c)?);
+
+        // rebased, it applies cleanly and the nodes converge
+        let mut rebaser = Rebaser::new()?;
+        rebaser.configure(&rebase)?;
+        let rebased = rebaser.rebase(&local)?;
+        c.apply(
+            &rebased,
+            None::<fn(&str) -> bool>,
+            |_conflict_type, _item| ConflictAction::SQLITE_CHANGESET_ABORT,
+        )?;
+        assert_eq!("a", x(c)?);
+        Ok(())
+    }
+
     #[test]
     fn test_session_empty() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -0,0 +1,107 @@
+//! Database snapshot, for repeatable reads on WAL databases.
+//!
+//! Requires SQLite to be compiled with `SQLITE_ENABLE_SNAPSHOT` (which the
+//! `snapshot` feature does for the bundled SQLite).
+use std::fmt;
+use std::ptr::{self, NonNull};
+
+use crate::{ffi, Connection, DatabaseName, Result};
+
+/// A point-in-time view of a WAL database, as returned by
+/// [`Connection::get_snapshot`].
+///
+/// A snapshot is not tied to the connection it was taken from: it can be
+/// opened by any connection on the same database file, as long as the
+/// corresponding WAL content has not been checkpointed away.
+pub struct Snapshot(NonNull<ffi::sqlite3_snapshot>);
+
+impl Drop for Snapshot {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_snapshot_free(self.0.as_ptr()) };
+    }
+}
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Snapshot").field(&self.0).finish()
+    }
+}
+
+impl Connection {
+    /// Record a snapshot of the current state of `schema`.
+    ///
+    /// The connection must not be in autocommit mode and must not have a write
+    /// transaction open on `schema`. If no read transaction is open yet, one is
+    /// started. `schema` must be a WAL mode database.
+    ///
+    /// See [`sqlite3_snapshot_get`](https://www.sqlite.org/c3ref/snapshot_get.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if any of the above requirements is not met.
+    pub fn get_snapshot(&self, schema: DatabaseName<'_>) -> Result<Snapshot> {
+        let schema = schema.as_cstring()?;
+        let mut snapshot = ptr::null_mut();
+        let r = unsafe { ffi::sqlite3_snapshot_get(self.handle(), schema.as_ptr(), &mut snapshot) };
+        self.decode_result(r)?;
+        Ok(Snapshot(NonNull::new(snapshot).unwrap()))
+    }
+
+    /// Start a read transaction on `schema` that sees the database as it was
+    /// when `snapshot` was taken.
+    ///
+    /// The connection must have an open transaction (e.g. after `BEGIN`) in
+    /// which `schema` has not been read yet.
+    ///
+    /// See [`sqlite3_snapshot_open`](https://www.sqlite.org/c3ref/snapshot_open.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if a read transaction is already open on `schema`, or
+    /// with `SQLITE_ERROR_SNAPSHOT` if `snapshot` is no longer available (e.g.
+    /// the WAL has been reset by a checkpoint).
+    pub fn open_snapshot(&self, schema: DatabaseName<'_>, snapshot: &Snapshot) -> Result<()> {
+        let schema = schema.as_cstring()?;
+        let r = unsafe {
+            ffi::sqlite3_snapshot_open(self.handle(), schema.as_ptr(), snapshot.0.as_ptr())
+        };
+        self.decode_result(r)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Connection, DatabaseName, Result};
+
+    #[test]
+    fn test_snapshot() -> Result<()> {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("test.db3");
+        let db1 = Connection::open(&path)?;
+        db1.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(()))?;
+        db1.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES(1);")?;
+
+        db1.execute_batch("BEGIN")?;
+        let snapshot = db1.get_snapshot(DatabaseName::Main)?;
+        db1.execute_batch("COMMIT")?;
+
+        let db2 = Connection::open(&path)?;
+        db2.execute("INSERT INTO foo VALUES(2)", [])?;
+        assert_eq!(2, db1.one_column::<i64>("SELECT COUNT(*) FROM foo")?);
+
+        db1.execute_batch("BEGIN")?;
+        db1.open_snapshot(DatabaseName::Main, &snapshot)?;
+        assert_eq!(1, db1.one_column::<i64>("SELECT COUNT(*) FROM foo")?);
+        db1.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_requires_wal() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER); BEGIN;")?;
+        db.get_snapshot(DatabaseName::Main).unwrap_err();
+        Ok(())
+    }
+}
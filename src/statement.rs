@@ -7,8 +7,10 @@ use std::{fmt, mem, ptr, str};
 use super::ffi;
 use super::{len_as_c_int, str_for_sqlite};
 use super::{
-    AndThenRows, Connection, Error, MappedRows, Params, RawStatement, Result, Row, Rows, ValueRef,
+    AndThenRows, ColumnarResult, Connection, Error, FromRow, MappedRows, Params, RawStatement,
+    Result, Row, Rows, ValueRef,
 };
+use crate::config::NanPolicy;
 use crate::types::{ToSql, ToSqlOutput};
 #[cfg(feature = "array")]
 use crate::vtab::array::{free_array, ARRAY_TYPE};
@@ -346,6 +348,99 @@ impl Statement<'_> {
         self.query(params).map(|rows| rows.and_then(f))
     }
 
+    /// Executes the prepared statement and converts each resulting row with
+    /// [`FromRow`].
+    ///
+    /// This is equivalent to `stmt.query_map(params, T::from_row)`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn get_names(conn: &Connection) -> Result<Vec<(i64, String)>> {
+    ///     let mut stmt = conn.prepare("SELECT id, name FROM people")?;
+    ///     let rows = stmt.query_as([])?;
+    ///     rows.collect()
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if binding parameters fails.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    pub fn query_as<T, P>(&mut self, params: P) -> Result<MappedRows<'_, fn(&Row<'_>) -> Result<T>>>
+    where
+        T: FromRow,
+        P: Params,
+    {
+        self.query_map(params, T::from_row)
+    }
+
+    /// Execute the query and collect all the result values column by column
+    /// instead of row by row.
+    ///
+    /// This layout suits processing a column at a time (e.g. aggregations).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// # use rusqlite::types::Value;
+    /// fn total(conn: &Connection) -> Result<f64> {
+    ///     let mut stmt = conn.prepare("SELECT name, amount FROM payment")?;
+    ///     let result = stmt.query_columns([])?;
+    ///     let amounts = result.column_by_name("amount").unwrap();
+    ///     Ok(amounts
+    ///         .iter()
+    ///         .filter_map(|v| match v {
+    ///             Value::Real(f) => Some(*f),
+    ///             _ => None,
+    ///         })
+    ///         .sum())
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if binding parameters fails or if stepping the
+    /// query fails.
+    #[inline]
+    pub fn query_columns<P: Params>(&mut self, params: P) -> Result<ColumnarResult> {
+        ColumnarResult::read(self.query(params)?)
+    }
+
+    /// Execute the prepared statement and combine its rows into a single
+    /// value, without collecting them first.
+    ///
+    /// `f` is called with the accumulated value (starting with `init`) and
+    /// each row in turn, and returns the new accumulated value.
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn total_amount(conn: &Connection) -> Result<f64> {
+    ///     let mut stmt = conn.prepare("SELECT amount FROM payment")?;
+    ///     stmt.fold([], 0.0, |total, row| Ok(total + row.get::<_, f64>(0)?))
+    /// }
+    /// ```
+    ///
+    /// ## Failure
+    ///
+    /// Will return `Err` if binding parameters fails, if stepping the query
+    /// fails, or as soon as `f` returns an error.
+    pub fn fold<B, P, F>(&mut self, params: P, init: B, mut f: F) -> Result<B>
+    where
+        P: Params,
+        F: FnMut(B, &Row<'_>) -> Result<B>,
+    {
+        let mut rows = self.query(params)?;
+        let mut acc = init;
+        while let Some(row) = rows.next()? {
+            acc = f(acc, row)?;
+        }
+        Ok(acc)
+    }
+
     /// Return `true` if a query in the SQL statement it executes returns one
     /// or more rows and `false` if the SQL returns an empty set.
     #[inline]
@@ -453,10 +548,13 @@ impl Statement<'_> {
         P::Item: ToSql,
     {
         let expected = self.stmt.bind_parameter_count();
+        let mut params = params.into_iter();
         let mut index = 0;
-        for p in params {
+        for p in params.by_ref() {
             index += 1; // The leftmost SQL parameter has an index of 1.
             if index > expected {
+                // count the extra parameters, to report how many were given
+                index += params.count();
                 break;
             }
             self.bind_parameter(&p, index)?;
@@ -554,6 +652,57 @@ impl Statement<'_> {
         self.bind_parameter(&param, one_based_col_index)
     }
 
+    /// Low level API to bind one group of parameters of a multi-row statement.
+    ///
+    /// For a statement like `INSERT INTO t VALUES (?,?,?),(?,?,?)`, each row
+    /// is a contiguous group of `params.len()` parameters: the group for
+    /// `row_index` (zero-based) starts at parameter
+    /// `row_index * params.len() + 1`.
+    ///
+    /// The same caveats as [`Statement::raw_bind_parameter`] apply: execute
+    /// the statement with [`Statement::raw_execute`] or
+    /// [`Statement::raw_query`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result, ToSql};
+    /// fn insert_pairs(conn: &Connection, pairs: &[(i64, String)]) -> Result<usize> {
+    ///     let values = vec!["(?, ?)"; pairs.len()].join(", ");
+    ///     let mut stmt = conn.prepare(&format!("INSERT INTO pairs (a, b) VALUES {values}"))?;
+    ///     for (i, (a, b)) in pairs.iter().enumerate() {
+    ///         stmt.raw_bind_row(i, &[a as &dyn ToSql, b])?;
+    ///     }
+    ///     stmt.raw_execute()
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the group for `row_index` extends past
+    /// [`Statement::parameter_count`], or if binding a parameter fails.
+    pub fn raw_bind_row(&mut self, row_index: usize, params: &[&dyn ToSql]) -> Result<()> {
+        let width = params.len();
+        let expected = self.parameter_count();
+        let end = row_index
+            .checked_mul(width)
+            .and_then(|f| f.checked_add(width));
+        let first = match end {
+            Some(end) if end <= expected => end - width,
+            // `usize::MAX` stands for a group too far to be computed
+            _ => {
+                return Err(Error::InvalidParameterCount(
+                    end.unwrap_or(usize::MAX),
+                    expected,
+                ))
+            }
+        };
+        for (i, p) in params.iter().enumerate() {
+            self.bind_parameter(*p, first + i + 1)?;
+        }
+        Ok(())
+    }
+
     /// Low level API to execute a statement given that all parameters were
     /// bound explicitly with the [`Statement::raw_bind_parameter`] API.
     ///
@@ -628,7 +777,18 @@ impl Statement<'_> {
         };
         self.conn.decode_result(match value {
             ValueRef::Null => unsafe { ffi::sqlite3_bind_null(ptr, col as c_int) },
+            ValueRef::Text(s) if s.is_empty() && self.conn.db.borrow().empty_string_as_null => unsafe {
+                ffi::sqlite3_bind_null(ptr, col as c_int)
+            },
             ValueRef::Integer(i) => unsafe { ffi::sqlite3_bind_int64(ptr, col as c_int, i) },
+            ValueRef::Real(r) if r.is_nan() => match self.conn.db.borrow().nan_policy {
+                NanPolicy::Null => unsafe { ffi::sqlite3_bind_null(ptr, col as c_int) },
+                NanPolicy::Error => {
+                    return Err(Error::ToSqlConversionFailure(
+                        format!("NaN cannot be bound to parameter {col}").into(),
+                    ))
+                }
+            },
             ValueRef::Real(r) => unsafe { ffi::sqlite3_bind_double(ptr, col as c_int, r) },
             ValueRef::Text(s) => unsafe {
                 let (c_str, len, destructor) = str_for_sqlite(s)?;
@@ -655,8 +815,23 @@ impl Statement<'_> {
 
     #[inline]
     fn execute_with_bound_parameters(&mut self) -> Result<usize> {
+        #[cfg(feature = "metrics")]
+        self.conn.record_metrics(|m| m.executes += 1);
+        #[cfg(feature = "tracing")]
+        {
+            let span = crate::instrument::Timed::execute(self.conn, self.sql_str());
+            let r = span.in_scope(|| self.execute_with_bound_parameters_());
+            span.finish();
+            r
+        }
+        #[cfg(not(feature = "tracing"))]
+        self.execute_with_bound_parameters_()
+    }
+
+    #[inline]
+    fn execute_with_bound_parameters_(&mut self) -> Result<usize> {
         self.check_update()?;
-        let r = self.stmt.step();
+        let r = self.raw_step();
         let rr = self.stmt.reset();
         match r {
             ffi::SQLITE_DONE => match rr {
@@ -694,12 +869,43 @@ impl Statement<'_> {
 
     /// Returns a string containing the SQL text of prepared statement with
     /// bound parameters expanded.
+    ///
+    /// Parameters keep their value after the statement is run, so this shows
+    /// the values used by the last execution, which helps logging or
+    /// debugging a query. Beware that the result contains these values
+    /// verbatim, which may be sensitive data (passwords, personal data...).
+    ///
+    /// Returns `None` if SQLite could not allocate the string, or if the
+    /// expanded text would exceed `SQLITE_LIMIT_LENGTH`. Strings and blobs
+    /// are expanded as SQL literals, unbound parameters as `NULL`.
+    ///
+    /// See [`sqlite3_expanded_sql`](https://sqlite.org/c3ref/expanded_sql.html).
     pub fn expanded_sql(&self) -> Option<String> {
         self.stmt
             .expanded_sql()
             .map(|s| s.to_string_lossy().to_string())
     }
 
+    /// Returns the SQL text of the statement in normalized form: literals
+    /// are replaced by `?`, keywords are upper-cased and whitespace and
+    /// comments are removed, so that queries differing only by their
+    /// literals (or formatting) have the same normalized text, e.g. to
+    /// group them in metrics.
+    ///
+    /// Requires SQLite to be compiled with `SQLITE_ENABLE_NORMALIZE` (which
+    /// the `normalize` feature does for the bundled SQLite). Returns `None`
+    /// if SQLite could not compute the normalized text.
+    ///
+    /// See [`sqlite3_normalized_sql`](https://sqlite.org/c3ref/expanded_sql.html).
+    #[cfg(feature = "normalize")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "normalize")))]
+    #[must_use]
+    pub fn normalized_sql(&self) -> Option<String> {
+        self.stmt
+            .normalized_sql()
+            .map(|s| s.to_string_lossy().into_owned())
+    }
+
     /// Get the value for one of the status counters for this statement.
     #[inline]
     pub fn get_status(&self, status: StatementStatus) -> i32 {
@@ -729,6 +935,38 @@ impl Statement<'_> {
         self.stmt.readonly()
     }
 
+    /// List the virtual machine program this statement runs, as shown by
+    /// [`EXPLAIN`](https://www.sqlite.org/lang_explain.html).
+    ///
+    /// The listing is meant for debugging: opcodes and their operands change
+    /// between SQLite versions.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the statement text cannot be prepared again with
+    /// an `EXPLAIN` prefix (e.g. because it already is an `EXPLAIN`).
+    pub fn bytecode(&self) -> Result<Vec<BytecodeOp>> {
+        let sql = match self.stmt.sql() {
+            Some(sql) => sql.to_str()?,
+            None => return Ok(Vec::new()),
+        };
+        let mut explain = self.conn.prepare(&format!("EXPLAIN {sql}"))?;
+        // parameters don't need to be bound to list the program
+        let rows = explain.raw_query().mapped(|row| {
+            Ok(BytecodeOp {
+                addr: row.get(0)?,
+                opcode: row.get(1)?,
+                p1: row.get(2)?,
+                p2: row.get(3)?,
+                p3: row.get(4)?,
+                p4: row.get(5)?,
+                p5: row.get(6)?,
+                comment: row.get(7)?,
+            })
+        });
+        rows.collect()
+    }
+
     #[cfg(feature = "extra_check")]
     #[inline]
     pub(crate) fn check_no_tail(&self) -> Result<()> {
@@ -756,7 +994,7 @@ impl Statement<'_> {
         stmt
     }
 
-    /// Reset all bindings
+    /// Reset all bindings to `NULL`. See also [`Statement::reset`].
     pub fn clear_bindings(&mut self) {
         self.stmt.clear_bindings();
     }
@@ -849,15 +1087,78 @@ impl Statement<'_> {
 
     #[inline]
     pub(super) fn step(&self) -> Result<bool> {
-        match self.stmt.step() {
+        match self.raw_step() {
             ffi::SQLITE_ROW => Ok(true),
             ffi::SQLITE_DONE => Ok(false),
             code => Err(self.conn.decode_result(code).unwrap_err()),
         }
     }
 
+    // `sqlite3_step`, accounting for the step budget (see
+    // `Connection::set_step_budget`) and the step time (see
+    // `Connection::metrics`)
+    #[inline]
+    fn raw_step(&self) -> c_int {
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+        let rc = self.budgeted_step();
+        #[cfg(feature = "metrics")]
+        self.conn.record_metrics(|m| m.step_time += start.elapsed());
+        rc
+    }
+
+    #[inline]
+    fn budgeted_step(&self) -> c_int {
+        #[cfg(feature = "hooks")]
+        let budget = self.conn.db.borrow().step_budget.clone();
+        #[cfg(feature = "hooks")]
+        if let Some(budget) = budget {
+            budget.enter(!self.stmt.is_busy());
+            let rc = self.stmt.step();
+            budget.leave();
+            return rc;
+        }
+        self.stmt.step()
+    }
+
+    #[cfg(feature = "metrics")]
+    #[inline]
+    pub(crate) fn record_query(&self) {
+        self.conn.record_metrics(|m| m.queries += 1);
+    }
+
+    // SQL text of the statement, for the tracing spans
+    #[cfg(feature = "tracing")]
+    fn sql_str(&self) -> &str {
+        self.stmt
+            .sql()
+            .and_then(|sql| sql.to_str().ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn lenient_bool(&self) -> bool {
+        self.conn.db.borrow().lenient_bool
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn query_span(&self) -> crate::instrument::Timed {
+        crate::instrument::Timed::query(self.conn, self.sql_str())
+    }
+
+    /// Reset the statement, so that it can be run again from the start with
+    /// the same bindings, see [`sqlite3_reset`](https://sqlite.org/c3ref/reset.html).
+    ///
+    /// Unlike [`Statement::clear_bindings`], the parameters keep their
+    /// values: this is useful to re-run the same query (e.g. when polling)
+    /// with [`Statement::raw_query`] or [`Statement::raw_execute`] without
+    /// binding the parameters again. The statement is also reset when the
+    /// [`Rows`] of a query are dropped, and after an execution.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the last step of the statement failed.
     #[inline]
-    pub(super) fn reset(&self) -> Result<()> {
+    pub fn reset(&self) -> Result<()> {
         match self.stmt.reset() {
             ffi::SQLITE_OK => Ok(()),
             code => Err(self.conn.decode_result(code).unwrap_err()),
@@ -865,6 +1166,32 @@ impl Statement<'_> {
     }
 }
 
+/// One instruction of a statement program, as returned by
+/// [`Statement::bytecode`].
+///
+/// See [the opcode documentation](https://www.sqlite.org/opcode.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BytecodeOp {
+    /// Address of the instruction.
+    pub addr: i64,
+    /// Name of the opcode (e.g. `OpenRead`).
+    pub opcode: String,
+    /// First operand.
+    pub p1: i64,
+    /// Second operand.
+    pub p2: i64,
+    /// Third operand.
+    pub p3: i64,
+    /// Fourth operand, rendered as text.
+    pub p4: Option<String>,
+    /// Fifth operand (flags).
+    pub p5: i64,
+    /// Description of the instruction, only available if SQLite was compiled
+    /// with `SQLITE_ENABLE_EXPLAIN_COMMENTS`.
+    pub comment: Option<String>,
+}
+
 /// Prepared statement status counters.
 ///
 /// See `https://www.sqlite.org/c3ref/c_stmtstatus_counter.html`
@@ -898,6 +1225,9 @@ pub enum StatementStatus {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
+
+    use super::{BytecodeOp, Statement};
     use crate::types::ToSql;
     use crate::{params_from_iter, Connection, Error, Result};
 
@@ -1074,6 +1404,97 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_query_columns() -> Result<()> {
+        use crate::types::Value;
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER, y TEXT, z);
+             INSERT INTO foo VALUES (1, 'a', 1.5), (2, 'b', NULL), (3, 'c', x'00');",
+        )?;
+        let sql = "SELECT x, y AS name, z FROM foo ORDER BY x";
+        let mut stmt = db.prepare(sql)?;
+        let result = stmt.query_columns([])?;
+        assert_eq!(3, result.row_count());
+        assert_eq!(&["x", "name", "z"], result.column_names());
+
+        let rows = stmt
+            .query_map([], |r| {
+                Ok(vec![r.get::<_, Value>(0)?, r.get(1)?, r.get(2)?])
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        for (i, column) in result.clone().into_columns().iter().enumerate() {
+            let from_rows: Vec<Value> = rows.iter().map(|row| row[i].clone()).collect();
+            assert_eq!(&from_rows, column);
+        }
+        assert_eq!(result.column(1), result.column_by_name("NAME"));
+        assert!(result.column(3).is_none());
+
+        let mut stmt = db.prepare("SELECT x FROM foo WHERE x > 10")?;
+        let result = stmt.query_columns([])?;
+        assert_eq!(0, result.row_count());
+        assert_eq!(Some(&[][..]), result.column(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_bind_one_at_a_time() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE test (a INTEGER, b TEXT, c REAL)")?;
+        let mut stmt = db.prepare("INSERT INTO test (a, b, c) VALUES (?, ?, ?)")?;
+        let values: Vec<Box<dyn ToSql>> = vec![Box::new(1), Box::new("one"), Box::new(1.5)];
+        for (i, value) in values.iter().enumerate() {
+            stmt.raw_bind_parameter(i + 1, value)?;
+        }
+        assert_eq!(1, stmt.raw_execute()?);
+        // bindings are kept until replaced
+        stmt.raw_bind_parameter(1, 2)?;
+        assert_eq!(1, stmt.raw_execute()?);
+
+        let rows: Vec<(i64, String, f64)> = db
+            .prepare("SELECT a, b, c FROM test ORDER BY a")?
+            .query_map([], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))?
+            .collect::<Result<_>>()?;
+        assert_eq!(
+            vec![(1, "one".to_owned(), 1.5), (2, "one".to_owned(), 1.5)],
+            rows
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_raw_bind_row() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE test (a INTEGER, b TEXT, c REAL)")?;
+        let values = vec!["(?, ?, ?)"; 100].join(", ");
+        let mut stmt = db.prepare(&format!("INSERT INTO test (a, b, c) VALUES {values}"))?;
+        for i in 0..100 {
+            let b = format!("row{i}");
+            stmt.raw_bind_row(i, &[&(i as i64), &b, &(i as f64 / 2.0)])?;
+        }
+        assert_eq!(
+            stmt.raw_bind_row(100, &[&1, &2, &3]),
+            Err(Error::InvalidParameterCount(303, 300))
+        );
+        // must not wrap around to an earlier group
+        for row in [usize::MAX, usize::MAX / 3 + 1] {
+            assert_eq!(
+                stmt.raw_bind_row(row, &[&1, &2, &3]),
+                Err(Error::InvalidParameterCount(usize::MAX, 300))
+            );
+        }
+        assert_eq!(stmt.raw_execute()?, 100);
+
+        assert_eq!(100, db.one_column::<i64>("SELECT COUNT(*) FROM test")?);
+        let (b, c): (String, f64) =
+            db.query_row("SELECT b, c FROM test WHERE a = 42", [], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })?;
+        assert_eq!(b, "row42");
+        assert_eq!(c, 21.0);
+        Ok(())
+    }
+
     #[test]
     fn test_unbound_parameters_are_reused() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1124,6 +1545,134 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_query_as() -> Result<()> {
+        use crate::types::Value;
+        use crate::{FromRow, Row};
+        use std::collections::HashMap;
+
+        #[derive(Debug, PartialEq)]
+        struct Person {
+            id: i64,
+            name: String,
+        }
+
+        impl FromRow for Person {
+            fn from_row(row: &Row<'_>) -> Result<Person> {
+                Ok(Person {
+                    id: row.get("id")?,
+                    name: row.get("name")?,
+                })
+            }
+        }
+
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE people(id INTEGER, name TEXT);
+             INSERT INTO people VALUES (1, 'Alice'), (2, 'Bob');",
+        )?;
+        let mut stmt = db.prepare("SELECT name, id FROM people ORDER BY id")?;
+
+        let people: Vec<Person> = stmt.query_as([])?.collect::<Result<_>>()?;
+        assert_eq!(
+            people,
+            [
+                Person {
+                    id: 1,
+                    name: "Alice".to_owned()
+                },
+                Person {
+                    id: 2,
+                    name: "Bob".to_owned()
+                }
+            ]
+        );
+
+        let tuples: Vec<(String, i64)> = stmt.query_as([])?.collect::<Result<_>>()?;
+        assert_eq!(tuples, [("Alice".to_owned(), 1), ("Bob".to_owned(), 2)]);
+
+        let values = stmt.query_as::<Vec<Value>, _>([])?.next().unwrap()?;
+        assert_eq!(values, [Value::Text("Alice".to_owned()), Value::Integer(1)]);
+
+        let map = stmt
+            .query_as::<HashMap<String, Value>, _>([])?
+            .nth(1)
+            .unwrap()?;
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["id"], Value::Integer(2));
+        assert_eq!(map["name"], Value::Text("Bob".to_owned()));
+
+        let err = stmt.query_as::<(i64, i64), _>([])?.next().unwrap();
+        assert!(matches!(err, Err(Error::InvalidColumnType(0, ..))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reset_keeps_bindings() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER); INSERT INTO foo VALUES (1), (2), (3);")?;
+        let mut stmt = db.prepare("SELECT x FROM foo WHERE x >= ?1 ORDER BY x")?;
+        stmt.raw_bind_parameter(1, 2)?;
+        let collect = |stmt: &mut Statement<'_>| -> Result<Vec<i64>> {
+            let mut rows = stmt.raw_query();
+            let mut xs = Vec::new();
+            while let Some(row) = rows.next()? {
+                xs.push(row.get(0)?);
+            }
+            Ok(xs)
+        };
+        assert_eq!(vec![2, 3], collect(&mut stmt)?);
+        stmt.reset()?;
+        assert_eq!(vec![2, 3], collect(&mut stmt)?);
+
+        // reset in the middle of the result set
+        {
+            let mut rows = stmt.raw_query();
+            assert_eq!(2, rows.next()?.unwrap().get::<_, i64>(0)?);
+        }
+        stmt.reset()?;
+        assert_eq!(vec![2, 3], collect(&mut stmt)?);
+
+        stmt.clear_bindings();
+        assert!(collect(&mut stmt)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fold() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(k TEXT, x INTEGER);
+             INSERT INTO foo VALUES ('a', 1), ('b', 2), ('a', 3), (NULL, 4);",
+        )?;
+        let mut stmt = db.prepare("SELECT k, x FROM foo ORDER BY rowid")?;
+        let sum = stmt.fold([], 0, |sum, row| Ok(sum + row.get::<_, i64>(1)?))?;
+        assert_eq!(10, sum);
+
+        let mut stmt = db.prepare("SELECT k, x FROM foo WHERE k IS NOT NULL")?;
+        let by_key = stmt.fold([], HashMap::new(), |mut map, row| {
+            *map.entry(row.get::<_, String>(0)?).or_insert(0) += row.get::<_, i64>(1)?;
+            Ok(map)
+        })?;
+        assert_eq!(
+            HashMap::from([("a".to_owned(), 4), ("b".to_owned(), 2)]),
+            by_key
+        );
+
+        // the NULL key stops the fold
+        let mut stmt = db.prepare("SELECT k FROM foo ORDER BY rowid")?;
+        let mut seen = 0;
+        let err = stmt
+            .fold([], (), |(), row| {
+                seen += 1;
+                row.get::<_, String>(0).map(drop)
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(0, ..)), "{err:?}");
+        assert_eq!(4, seen);
+        Ok(())
+    }
+
     #[test]
     fn test_exists() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -1224,6 +1773,72 @@ mod test {
         let stmt = db.prepare("SELECT ?1")?;
         stmt.bind_parameter(&1, 1)?;
         assert_eq!(Some("SELECT 1".to_owned()), stmt.expanded_sql());
+
+        let mut stmt = db.prepare("SELECT :name, ?2, ?3, ?4")?;
+        assert_eq!(
+            Some("SELECT NULL, NULL, NULL, NULL".to_owned()),
+            stmt.expanded_sql()
+        );
+        stmt.query_row((&"it's", 2.5, &[1u8, 255][..], None::<i64>), |_| Ok(()))?;
+        // the values of the last execution
+        assert_eq!(
+            Some("SELECT 'it''s', 2.5, x'01ff', NULL".to_owned()),
+            stmt.expanded_sql()
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "normalize")]
+    fn test_normalized_sql() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, y TEXT)")?;
+        let a = db
+            .prepare("SELECT x FROM foo WHERE x = 1 AND y = 'a'")?
+            .normalized_sql()
+            .unwrap();
+        let b = db
+            .prepare("select x  from foo -- comment\n where x = 42 and y = 'bc'")?
+            .normalized_sql()
+            .unwrap();
+        assert_eq!(a, b);
+        assert!(a.contains('?'), "{a}");
+        assert!(!a.contains('1') && !a.contains("'a'"), "{a}");
+        let c = db
+            .prepare("SELECT y FROM foo WHERE x = 1")?
+            .normalized_sql()
+            .unwrap();
+        assert_ne!(a, c);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_parameter_count() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db.prepare("SELECT ?1, ?2, ?3")?;
+        // checked before stepping
+        assert_eq!(
+            Some(Error::InvalidParameterCount(2, 3)),
+            stmt.query([1, 2]).err()
+        );
+        assert_eq!(
+            Some(Error::InvalidParameterCount(5, 3)),
+            stmt.query(params_from_iter([1, 2, 3, 4, 5])).err()
+        );
+        assert_eq!(
+            Some(Error::InvalidParameterCount(1, 3)),
+            stmt.query(params_from_iter(["a"])).err()
+        );
+        assert_eq!(
+            Some(Error::InvalidParameterCount(4, 3)),
+            stmt.query([&1 as &dyn ToSql, &2, &3, &4]).err()
+        );
+        let err = stmt.query((1, 2)).err().unwrap();
+        assert_eq!(
+            "Wrong number of parameters passed to query. Got 2, needed 3",
+            err.to_string()
+        );
+        assert!(stmt.query([1, 2, 3])?.next()?.is_some());
         Ok(())
     }
 
@@ -1370,4 +1985,25 @@ mod test {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_bytecode() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo(x INTEGER, y TEXT)")?;
+        let stmt = db.prepare("SELECT y FROM foo WHERE x > ?1")?;
+        let program = stmt.bytecode()?;
+        assert!(!program.is_empty());
+        for (i, op) in program.iter().enumerate() {
+            assert_eq!(i as i64, op.addr);
+        }
+        let has = |f: &dyn Fn(&BytecodeOp) -> bool| program.iter().any(f);
+        assert!(has(&|op| op.opcode == "OpenRead"));
+        // reads both `x` and `y`
+        assert!(has(&|op| op.opcode == "Column" && op.p2 == 0));
+        assert!(has(&|op| op.opcode == "Column" && op.p2 == 1));
+
+        let explain = db.prepare("EXPLAIN SELECT 1")?;
+        explain.bytecode().unwrap_err();
+        Ok(())
+    }
 }
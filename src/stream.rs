@@ -0,0 +1,179 @@
+//! Query results as an async [`Stream`], for use with tokio.
+//!
+//! A [`Statement`](crate::Statement) borrows its connection, so it cannot be
+//! moved to a blocking task. Instead, [`RowStream::new`] takes a shared
+//! connection, and prepares and steps the statement on tokio's blocking pool,
+//! sending the rows to the async side as [`OwnedRow`]s.
+//!
+//! ```rust,no_run
+//! use std::sync::{Arc, Mutex};
+//!
+//! use rusqlite::stream::RowStream;
+//! use rusqlite::{Connection, Result};
+//!
+//! async fn names(conn: Arc<Mutex<Connection>>) -> Result<Vec<String>> {
+//!     let mut rows = RowStream::new(conn, "SELECT name FROM people", [], 16);
+//!     let mut names = Vec::new();
+//!     while let Some(row) = rows.next().await {
+//!         names.push(row?.get(0)?);
+//!     }
+//!     Ok(names)
+//! }
+//! ```
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+pub use futures_core::Stream;
+use tokio::sync::mpsc;
+
+use crate::error::misuse;
+use crate::{Connection, OwnedRow, Params, Result};
+
+/// The rows of a query run on tokio's blocking pool.
+///
+/// At most `capacity` rows are buffered: the statement is not stepped further
+/// until the stream is polled. Dropping the stream stops the statement before
+/// its next row.
+#[derive(Debug)]
+pub struct RowStream {
+    rx: mpsc::Receiver<Result<OwnedRow>>,
+}
+
+impl RowStream {
+    /// Run `sql` with `params` on a blocking task, holding the lock on `conn`
+    /// until all the rows have been sent or the stream has been dropped.
+    ///
+    /// Errors (when preparing or stepping the statement) are yielded by the
+    /// stream, which then ends. A poisoned lock is reported as an error too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime, or if `capacity` is 0.
+    pub fn new<S, P>(conn: Arc<Mutex<Connection>>, sql: S, params: P, capacity: usize) -> RowStream
+    where
+        S: Into<String>,
+        P: Params + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(capacity);
+        let sql = sql.into();
+        tokio::task::spawn_blocking(move || {
+            let conn = match conn.lock() {
+                Ok(conn) => conn,
+                Err(_) => {
+                    let _ = tx.blocking_send(Err(misuse("connection mutex is poisoned")));
+                    return;
+                }
+            };
+            if let Err(e) = send_rows(&conn, &sql, params, &tx) {
+                let _ = tx.blocking_send(Err(e));
+            }
+        });
+        RowStream { rx }
+    }
+
+    /// Wait for the next row, or `None` once all the rows have been
+    /// received.
+    #[allow(clippy::should_implement_trait)] // async
+    pub async fn next(&mut self) -> Option<Result<OwnedRow>> {
+        self.rx.recv().await
+    }
+}
+
+impl Stream for RowStream {
+    type Item = Result<OwnedRow>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+// Send the rows of `sql` to `tx`, stopping early if the receiver is gone.
+fn send_rows<P: Params>(
+    conn: &Connection,
+    sql: &str,
+    params: P,
+    tx: &mpsc::Sender<Result<OwnedRow>>,
+) -> Result<()> {
+    let mut stmt = conn.prepare(sql)?;
+    let mut rows = stmt.query(params)?;
+    while let Some(row) = rows.next_owned()? {
+        if tx.blocking_send(Ok(row)).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::RowStream;
+    use crate::{Connection, Result};
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+    }
+
+    fn shared_db() -> Result<Arc<Mutex<Connection>>> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE foo(x INTEGER);
+             WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 100)
+             INSERT INTO foo SELECT x FROM n;",
+        )?;
+        Ok(Arc::new(Mutex::new(db)))
+    }
+
+    #[test]
+    fn test_row_stream() -> Result<()> {
+        let db = shared_db()?;
+        let xs = runtime().block_on(async {
+            let mut rows = RowStream::new(db.clone(), "SELECT x FROM foo WHERE x > ?1", [90], 4);
+            let mut xs = Vec::new();
+            while let Some(row) = rows.next().await {
+                xs.push(row?.get::<i64>(0)?);
+            }
+            Ok::<_, crate::Error>(xs)
+        })?;
+        assert_eq!((91..=100).collect::<Vec<i64>>(), xs);
+        // the lock was released
+        let n: i64 = db.lock().unwrap().one_column("SELECT count(*) FROM foo")?;
+        assert_eq!(100, n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_stream_error() {
+        let db = shared_db().unwrap();
+        runtime().block_on(async {
+            let mut rows = RowStream::new(db, "SELECT missing FROM foo", [], 1);
+            assert!(matches!(rows.next().await, Some(Err(_))));
+            assert!(rows.next().await.is_none());
+        });
+    }
+
+    #[test]
+    fn test_row_stream_drop() -> Result<()> {
+        let db = shared_db()?;
+        runtime().block_on(async {
+            let mut rows = RowStream::new(db.clone(), "SELECT x FROM foo", [], 1);
+            let row = rows.next().await.unwrap()?;
+            assert_eq!(1, row.get::<i64>(0)?);
+            drop(rows);
+            Ok::<_, crate::Error>(())
+        })?;
+        // the blocking task stops and releases the lock
+        loop {
+            if let Ok(db) = db.try_lock() {
+                assert_eq!(100, db.one_column::<i64>("SELECT count(*) FROM foo")?);
+                break;
+            }
+            std::thread::yield_now();
+        }
+        Ok(())
+    }
+}
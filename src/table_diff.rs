@@ -0,0 +1,212 @@
+use crate::error::misuse;
+use crate::pragma::Sql;
+use crate::types::Value;
+use crate::{Connection, DatabaseName, Result};
+
+/// Row-level differences between two tables, as returned by
+/// [`Connection::diff_tables`].
+///
+/// Rows are given as the values of all columns, in the order of
+/// [`TableDiff::columns`], and are sorted by key.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct TableDiff {
+    /// Names of the compared columns, those of the left table.
+    pub columns: Vec<String>,
+    /// Rows of the right table whose key is not in the left table.
+    pub inserted: Vec<Vec<Value>>,
+    /// Rows of the left table whose key is not in the right table.
+    pub deleted: Vec<Vec<Value>>,
+    /// Rows with the same key in both tables but different values, as
+    /// `(left, right)` pairs.
+    pub changed: Vec<(Vec<Value>, Vec<Value>)>,
+}
+
+impl TableDiff {
+    /// Whether the two tables have the same content.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.deleted.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Connection {
+    /// Compare table `left` of database `left_db` with table `right` of
+    /// database `right_db` (e.g. an attached copy), matching rows on
+    /// `key_columns`.
+    ///
+    /// All the columns of `left` are compared; `right` must have columns with
+    /// the same names (it may have others, which are ignored). Values are
+    /// compared with `IS`, so `NULL`s are equal to each other. The key should
+    /// be unique in both tables.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `key_columns` is empty or if a table or column
+    /// does not exist.
+    pub fn diff_tables(
+        &self,
+        left_db: DatabaseName<'_>,
+        left: &str,
+        right_db: DatabaseName<'_>,
+        right: &str,
+        key_columns: &[&str],
+    ) -> Result<TableDiff> {
+        if key_columns.is_empty() {
+            return Err(misuse("diff_tables requires at least one key column"));
+        }
+        let left = qualified_name(left_db, left);
+        let right = qualified_name(right_db, right);
+        let columns: Vec<String> = {
+            let stmt = self.prepare(&format!("SELECT * FROM {left}"))?;
+            stmt.column_names().into_iter().map(str::to_owned).collect()
+        };
+        let quoted = |names: &mut dyn Iterator<Item = &str>, alias: &str, sep: &str| {
+            names
+                .map(|name| {
+                    let mut sql = Sql::new();
+                    sql.push_identifier(name);
+                    format!("{alias}.{}", sql.as_str())
+                })
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        let all = |alias: &str| quoted(&mut columns.iter().map(String::as_str), alias, ", ");
+        let keys = |alias: &str| quoted(&mut key_columns.iter().copied(), alias, ", ");
+        let same_key = key_columns
+            .iter()
+            .map(|k| {
+                let mut sql = Sql::new();
+                sql.push_identifier(k);
+                format!("l.{0} IS r.{0}", sql.as_str())
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let different = columns
+            .iter()
+            .filter(|c| !key_columns.contains(&c.as_str()))
+            .map(|c| {
+                let mut sql = Sql::new();
+                sql.push_identifier(c);
+                format!("l.{0} IS NOT r.{0}", sql.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        let missing = |from: String, alias: &str, other: String| {
+            collect_rows(
+                self,
+                &format!(
+                    "SELECT {} FROM {from} WHERE NOT EXISTS (SELECT 1 FROM {other} WHERE \
+                     {same_key}) ORDER BY {}",
+                    all(alias),
+                    keys(alias),
+                ),
+            )
+        };
+        let deleted = missing(format!("{left} AS l"), "l", format!("{right} AS r"))?;
+        let inserted = missing(format!("{right} AS r"), "r", format!("{left} AS l"))?;
+        let changed = if different.is_empty() {
+            Vec::new()
+        } else {
+            collect_rows(
+                self,
+                &format!(
+                    "SELECT {}, {} FROM {left} AS l JOIN {right} AS r ON {same_key} \
+                     WHERE {} ORDER BY {}",
+                    all("l"),
+                    all("r"),
+                    different.join(" OR "),
+                    keys("l"),
+                ),
+            )?
+            .into_iter()
+            .map(|mut row| {
+                let r = row.split_off(columns.len());
+                (row, r)
+            })
+            .collect()
+        };
+        Ok(TableDiff {
+            columns,
+            inserted,
+            deleted,
+            changed,
+        })
+    }
+}
+
+fn collect_rows(conn: &Connection, sql: &str) -> Result<Vec<Vec<Value>>> {
+    let mut stmt = conn.prepare(sql)?;
+    let n = stmt.column_count();
+    let rows = stmt.query_map([], |row| (0..n).map(|i| row.get(i)).collect())?;
+    rows.collect()
+}
+
+fn qualified_name(db: DatabaseName<'_>, table: &str) -> String {
+    let mut sql = Sql::new();
+    sql.push_schema_name(db);
+    sql.push_dot();
+    sql.push_identifier(table);
+    sql.as_str().to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::Value;
+    use crate::{Connection, DatabaseName, Result};
+
+    #[test]
+    fn test_diff_tables() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "ATTACH DATABASE ':memory:' AS other;
+             CREATE TABLE main.item(id INTEGER PRIMARY KEY, name TEXT, qty INTEGER);
+             CREATE TABLE other.item(id INTEGER PRIMARY KEY, name TEXT, qty INTEGER);
+             INSERT INTO main.item VALUES (1, 'a', 1), (2, 'b', NULL), (3, 'c', 3);
+             INSERT INTO other.item VALUES (1, 'a', 1), (2, 'b', 2), (4, 'd', 4);",
+        )?;
+        let diff = db.diff_tables(
+            DatabaseName::Main,
+            "item",
+            DatabaseName::Attached("other"),
+            "item",
+            &["id"],
+        )?;
+        assert_eq!(vec!["id", "name", "qty"], diff.columns);
+        let row = |id: i64, name: &str, qty: Option<i64>| {
+            vec![
+                Value::Integer(id),
+                Value::Text(name.to_owned()),
+                qty.map_or(Value::Null, Value::Integer),
+            ]
+        };
+        assert_eq!(vec![row(4, "d", Some(4))], diff.inserted);
+        assert_eq!(vec![row(3, "c", Some(3))], diff.deleted);
+        assert_eq!(
+            vec![(row(2, "b", None), row(2, "b", Some(2)))],
+            diff.changed
+        );
+
+        let same = db.diff_tables(
+            DatabaseName::Main,
+            "item",
+            DatabaseName::Main,
+            "item",
+            &["id"],
+        )?;
+        assert!(same.is_empty());
+
+        db.diff_tables(DatabaseName::Main, "item", DatabaseName::Main, "item", &[])
+            .unwrap_err();
+        db.diff_tables(
+            DatabaseName::Main,
+            "item",
+            DatabaseName::Main,
+            "missing",
+            &["id"],
+        )
+        .unwrap_err();
+        Ok(())
+    }
+}
@@ -3,41 +3,95 @@
 use std::ffi::{CStr, CString};
 use std::mem;
 use std::os::raw::{c_char, c_int, c_void};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::ptr;
+#[cfg(not(feature = "loadable_extension"))]
+use std::sync::Mutex;
 use std::time::Duration;
 
 use super::ffi;
 use crate::Connection;
 
-/// Set up the process-wide SQLite error logging callback.
+#[cfg(not(feature = "loadable_extension"))]
+type BoxedLog = Box<dyn FnMut(c_int, &str) + Send + 'static>;
+
+// The closure registered by `config_log_with`. SQLite may log from any thread, so
+// calls are serialized through the mutex.
+#[cfg(not(feature = "loadable_extension"))]
+static LOG_CALLBACK: Mutex<Option<BoxedLog>> = Mutex::new(None);
+
+/// Set up the process-wide SQLite error logging callback, or clear it with
+/// `None`.
+///
+/// Shorthand for [`config_log_with`] and [`clear_log`], for a plain function.
+///
+/// # Safety
+///
+/// See [`config_log_with`].
+#[cfg(not(feature = "loadable_extension"))]
+pub unsafe fn config_log(callback: Option<fn(c_int, &str)>) -> crate::Result<()> {
+    match callback {
+        Some(f) => config_log_with(f),
+        None => clear_log(),
+    }
+}
+
+/// Set up the process-wide SQLite error logging callback, which can be a
+/// closure (e.g. capturing a logger handle).
+///
+/// The log receives messages that do not necessarily surface as an `Err`
+/// (e.g. database corruption, recovery of a hot journal or automatic index
+/// warnings), so it can be routed into the application's own logging.
+///
+/// This should be called before the first connection is opened: SQLite
+/// versions before 3.42.0 reject `SQLITE_CONFIG_LOG` with `SQLITE_MISUSE` once
+/// the library is initialized.
 ///
 /// # Safety
 ///
 /// This function is marked unsafe for two reasons:
 ///
 /// * The function is not threadsafe. No other SQLite calls may be made while
-///   `config_log` is running, and multiple threads may not call `config_log`
-///   simultaneously.
-/// * The provided `callback` itself function has two requirements:
-///     * It must not invoke any SQLite calls.
-///     * It must be threadsafe if SQLite is used in a multithreaded way.
+///   `config_log_with` is running, and multiple threads may not call it (or
+///   [`config_log`] or [`clear_log`]) simultaneously.
+/// * The provided `callback` must not invoke any SQLite calls.
 ///
 /// cf [The Error And Warning Log](http://sqlite.org/errlog.html).
 #[cfg(not(feature = "loadable_extension"))]
-pub unsafe fn config_log(callback: Option<fn(c_int, &str)>) -> crate::Result<()> {
-    extern "C" fn log_callback(p_arg: *mut c_void, err: c_int, msg: *const c_char) {
-        let s = unsafe { CStr::from_ptr(msg).to_string_lossy() };
-        let callback: fn(c_int, &str) = unsafe { mem::transmute(p_arg) };
+pub unsafe fn config_log_with<F>(callback: F) -> crate::Result<()>
+where
+    F: FnMut(c_int, &str) + Send + 'static,
+{
+    set_log(Some(Box::new(callback)))
+}
 
-        drop(catch_unwind(|| callback(err, &s)));
+/// Remove the process-wide SQLite error logging callback.
+///
+/// # Safety
+///
+/// See [`config_log_with`].
+#[cfg(not(feature = "loadable_extension"))]
+pub unsafe fn clear_log() -> crate::Result<()> {
+    set_log(None)
+}
+
+#[cfg(not(feature = "loadable_extension"))]
+unsafe fn set_log(callback: Option<BoxedLog>) -> crate::Result<()> {
+    extern "C" fn log_callback(_: *mut c_void, err: c_int, msg: *const c_char) {
+        let s = unsafe { CStr::from_ptr(msg).to_string_lossy() };
+        drop(catch_unwind(AssertUnwindSafe(|| {
+            let mut callback = LOG_CALLBACK.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(ref mut callback) = *callback {
+                callback(err, &s);
+            }
+        })));
     }
 
-    let rc = if let Some(f) = callback {
+    let rc = if callback.is_some() {
         ffi::sqlite3_config(
             ffi::SQLITE_CONFIG_LOG,
             log_callback as extern "C" fn(_, _, _),
-            f as *mut c_void,
+            ptr::null_mut::<c_void>(),
         )
     } else {
         let nullptr: *mut c_void = ptr::null_mut();
@@ -45,6 +99,7 @@ pub unsafe fn config_log(callback: Option<fn(c_int, &str)>) -> crate::Result<()>
     };
 
     if rc == ffi::SQLITE_OK {
+        *LOG_CALLBACK.lock().unwrap_or_else(|e| e.into_inner()) = callback;
         Ok(())
     } else {
         Err(crate::error::error_from_sqlite_code(rc, None))
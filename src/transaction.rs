@@ -260,7 +260,7 @@ impl Savepoint<'_> {
     }
 
     #[inline]
-    fn new_(conn: &Connection) -> Result<Savepoint<'_>> {
+    pub(crate) fn new_(conn: &Connection) -> Result<Savepoint<'_>> {
         Savepoint::with_name_(conn, "_rusqlite_sp")
     }
 
@@ -371,6 +371,109 @@ impl Drop for Savepoint<'_> {
     }
 }
 
+/// A transaction, or a savepoint if a transaction was already open, as
+/// returned by [`Connection::begin_unit`].
+///
+/// Like [`Transaction`] and [`Savepoint`], a unit rolls back by default when
+/// it is dropped.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// # use rusqlite::{Connection, Result};
+/// // works whether or not the caller already opened a transaction
+/// fn transfer(conn: &Connection, from: i64, to: i64, amount: i64) -> Result<()> {
+///     let unit = conn.begin_unit()?;
+///     unit.execute("UPDATE account SET balance = balance - ?1 WHERE id = ?2", [amount, from])?;
+///     unit.execute("UPDATE account SET balance = balance + ?1 WHERE id = ?2", [amount, to])?;
+///     unit.commit()
+/// }
+/// ```
+#[derive(Debug)]
+pub enum TransactionUnit<'conn> {
+    /// A top-level transaction: no transaction was open.
+    Transaction(Transaction<'conn>),
+    /// A savepoint within the transaction which was already open.
+    Savepoint(Savepoint<'conn>),
+}
+
+impl TransactionUnit<'_> {
+    /// Whether this unit is a savepoint nested in an outer transaction.
+    #[inline]
+    #[must_use]
+    pub fn is_nested(&self) -> bool {
+        matches!(self, TransactionUnit::Savepoint(_))
+    }
+
+    /// Get the current setting for what happens to the unit when it is
+    /// dropped.
+    #[inline]
+    #[must_use]
+    pub fn drop_behavior(&self) -> DropBehavior {
+        match self {
+            TransactionUnit::Transaction(tx) => tx.drop_behavior(),
+            TransactionUnit::Savepoint(sp) => sp.drop_behavior(),
+        }
+    }
+
+    /// Configure the unit to perform the specified action when it is dropped.
+    #[inline]
+    pub fn set_drop_behavior(&mut self, drop_behavior: DropBehavior) {
+        match self {
+            TransactionUnit::Transaction(tx) => tx.set_drop_behavior(drop_behavior),
+            TransactionUnit::Savepoint(sp) => sp.set_drop_behavior(drop_behavior),
+        }
+    }
+
+    /// Consumes and commits the unit: commits the transaction, or releases
+    /// the savepoint (its changes are then part of the outer transaction).
+    #[inline]
+    pub fn commit(self) -> Result<()> {
+        match self {
+            TransactionUnit::Transaction(tx) => tx.commit(),
+            TransactionUnit::Savepoint(sp) => sp.commit(),
+        }
+    }
+
+    /// Consumes and rolls back the unit. A savepoint is rolled back and
+    /// released, leaving the outer transaction open.
+    #[inline]
+    pub fn rollback(self) -> Result<()> {
+        match self {
+            TransactionUnit::Transaction(tx) => tx.rollback(),
+            TransactionUnit::Savepoint(mut sp) => {
+                sp.set_drop_behavior(DropBehavior::Rollback);
+                sp.finish()
+            }
+        }
+    }
+
+    /// Consumes the unit, committing or rolling back according to the
+    /// current setting (see `drop_behavior`).
+    ///
+    /// Functionally equivalent to the `Drop` implementation, but allows
+    /// callers to see any errors that occur.
+    #[inline]
+    pub fn finish(self) -> Result<()> {
+        match self {
+            TransactionUnit::Transaction(tx) => tx.finish(),
+            TransactionUnit::Savepoint(sp) => sp.finish(),
+        }
+    }
+}
+
+impl Deref for TransactionUnit<'_> {
+    type Target = Connection;
+
+    #[inline]
+    fn deref(&self) -> &Connection {
+        match self {
+            TransactionUnit::Transaction(tx) => tx,
+            TransactionUnit::Savepoint(sp) => sp,
+        }
+    }
+}
+
 /// Transaction state of a database
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -467,6 +570,31 @@ impl Connection {
         Transaction::new_unchecked(self, TransactionBehavior::Deferred)
     }
 
+    /// Begin a transactional unit of work: a new (deferred) transaction if
+    /// none is open, or a savepoint within the open one otherwise.
+    ///
+    /// This lets code be transactional without knowing whether its caller
+    /// has already opened a transaction: rolling back a nested unit only
+    /// undoes its own changes, and leaves the outer transaction open.
+    /// See [`TransactionUnit`].
+    ///
+    /// Whether a transaction is open is checked with
+    /// [`is_autocommit`](Connection::is_autocommit) rather than with the
+    /// [`transaction_state`](Connection::transaction_state), which is still
+    /// `None` after a `BEGIN` until the database is accessed.
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if the underlying SQLite call fails.
+    pub fn begin_unit(&self) -> Result<TransactionUnit<'_>> {
+        if self.is_autocommit() {
+            Transaction::new_unchecked(self, TransactionBehavior::Deferred)
+                .map(TransactionUnit::Transaction)
+        } else {
+            Savepoint::new_(self).map(TransactionUnit::Savepoint)
+        }
+    }
+
     /// Begin a new savepoint with the default behavior (DEFERRED).
     ///
     /// The savepoint defaults to rolling back when it is dropped. If you want
@@ -509,7 +637,18 @@ impl Connection {
         Savepoint::with_name(self, name)
     }
 
-    /// Determine the transaction state of a database
+    /// Determine the transaction state of a database.
+    ///
+    /// If `db_name` is `None`, the highest transaction state of any schema on
+    /// this connection is returned. Unlike [`Connection::is_autocommit`], this
+    /// tells apart a `BEGIN` that has not touched the database yet
+    /// ([`TransactionState::None`]) from an open read or write transaction.
+    ///
+    /// See [`sqlite3_txn_state`](https://www.sqlite.org/c3ref/txn_state.html).
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `db_name` is not the name of an attached database.
     #[cfg(feature = "modern_sqlite")] // 3.37.0
     #[cfg_attr(docsrs, doc(cfg(feature = "modern_sqlite")))]
     pub fn transaction_state(
@@ -531,6 +670,61 @@ mod test {
         Ok(db)
     }
 
+    #[test]
+    fn test_begin_unit() -> Result<()> {
+        let db = checked_memory_handle()?;
+        {
+            let unit = db.begin_unit()?;
+            assert!(!unit.is_nested());
+            unit.execute_batch("INSERT INTO foo VALUES(1)")?;
+            unit.commit()?;
+        }
+        assert!(db.is_autocommit());
+        {
+            let unit = db.begin_unit()?;
+            unit.execute_batch("INSERT INTO foo VALUES(2)")?;
+            unit.rollback()?;
+        }
+        assert!(db.is_autocommit());
+        assert_eq!(1, db.one_column::<i32>("SELECT SUM(x) FROM foo")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_begin_nested_unit() -> Result<()> {
+        let mut db = checked_memory_handle()?;
+        {
+            let tx = db.transaction()?;
+            tx.execute_batch("INSERT INTO foo VALUES(1)")?;
+            {
+                let unit = tx.begin_unit()?;
+                assert!(unit.is_nested());
+                unit.execute_batch("INSERT INTO foo VALUES(2)")?;
+                unit.rollback()?;
+            }
+            {
+                let unit = tx.begin_unit()?;
+                unit.execute_batch("INSERT INTO foo VALUES(4)")?;
+                // default: rollback
+            }
+            {
+                let unit = tx.begin_unit()?;
+                unit.execute_batch("INSERT INTO foo VALUES(8)")?;
+                unit.commit()?;
+            }
+            // the outer transaction is still open
+            assert!(!tx.is_autocommit());
+            tx.commit()?;
+        }
+        assert_eq!(9, db.one_column::<i32>("SELECT SUM(x) FROM foo")?);
+
+        // after a BEGIN without any access yet
+        db.execute_batch("BEGIN")?;
+        assert!(db.begin_unit()?.is_nested());
+        db.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
     #[test]
     fn test_drop() -> Result<()> {
         let mut db = checked_memory_handle()?;
@@ -775,4 +969,29 @@ mod test {
         db.execute_batch("ROLLBACK")?;
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "modern_sqlite")]
+    fn txn_state_statements() -> Result<()> {
+        use super::TransactionState;
+        use crate::DatabaseName;
+        let mut db = checked_memory_handle()?;
+        {
+            let tx = db.transaction()?;
+            assert_eq!(TransactionState::None, tx.transaction_state(None)?);
+            tx.query_row("SELECT COUNT(*) FROM foo", [], |_| Ok(()))?;
+            assert_eq!(TransactionState::Read, tx.transaction_state(None)?);
+            tx.execute("INSERT INTO foo VALUES(1)", [])?;
+            assert_eq!(TransactionState::Write, tx.transaction_state(None)?);
+            assert_eq!(
+                TransactionState::None,
+                tx.transaction_state(Some(DatabaseName::Temp))?
+            );
+            tx.commit()?;
+        }
+        assert_eq!(TransactionState::None, db.transaction_state(None)?);
+        db.transaction_state(Some(DatabaseName::Attached("nope")))
+            .unwrap_err();
+        Ok(())
+    }
 }
@@ -0,0 +1,88 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`bytes::Bytes`] and
+//! [`bytes::BytesMut`] stored as blobs.
+use crate::types::{FromSql, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::Result;
+use bytes::{Bytes, BytesMut};
+
+/// Serialize `Bytes` to a blob, without copy.
+impl ToSql for Bytes {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(&self[..]))
+    }
+}
+
+/// Serialize `BytesMut` to a blob, without copy.
+impl ToSql for BytesMut {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(&self[..]))
+    }
+}
+
+/// Deserialize a blob to `Bytes`. The content is copied, as the buffer is
+/// owned by SQLite.
+impl FromSql for Bytes {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        value.as_blob().map(Bytes::copy_from_slice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::types::Type;
+    use crate::{Connection, Error, Result};
+    use bytes::{Bytes, BytesMut};
+
+    fn checked_memory_handle() -> Result<Connection> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo (b BLOB, t TEXT)")?;
+        Ok(db)
+    }
+
+    #[test]
+    fn test_bytes_round_trip() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let payload = Bytes::from_static(b"\x00\x01payload\xff");
+        db.execute("INSERT INTO foo (b) VALUES (?1)", [&payload])?;
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"mutable");
+        db.execute("INSERT INTO foo (b) VALUES (?1)", [&buf])?;
+
+        let mut stmt = db.prepare("SELECT b FROM foo ORDER BY rowid")?;
+        let rows = stmt.query_map([], |r| r.get::<_, Bytes>(0))?;
+        let values = rows.collect::<Result<Vec<_>>>()?;
+        assert_eq!(vec![payload, Bytes::from_static(b"mutable")], values);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_bytes() -> Result<()> {
+        let db = checked_memory_handle()?;
+        db.execute("INSERT INTO foo (b) VALUES (?1)", [Bytes::new()])?;
+        let (b, len): (Bytes, i64) = db.query_row("SELECT b, length(b) FROM foo", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })?;
+        assert!(b.is_empty());
+        assert_eq!(0, len);
+        let typ: String = db.one_column("SELECT typeof(b) FROM foo")?;
+        assert_eq!("blob", typ);
+        Ok(())
+    }
+
+    #[test]
+    fn test_bytes_invalid_type() -> Result<()> {
+        let db = checked_memory_handle()?;
+        db.execute("INSERT INTO foo (t) VALUES ('text')", [])?;
+        let err = db
+            .query_row("SELECT t FROM foo", [], |r| r.get::<_, Bytes>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(0, _, Type::Text)));
+        let err = db
+            .query_row("SELECT b FROM foo", [], |r| r.get::<_, Bytes>(0))
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidColumnType(0, _, Type::Null)));
+        Ok(())
+    }
+}
@@ -2,7 +2,10 @@
 
 use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
-use crate::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use super::{julian_day_to_unix_millis, unix_millis_to_julian_day};
+use crate::types::{
+    FromSql, FromSqlError, FromSqlResult, JulianDay, ToSql, ToSqlOutput, UnixTimestamp, ValueRef,
+};
 use crate::Result;
 
 /// ISO 8601 calendar date without timezone => "YYYY-MM-DD"
@@ -63,16 +66,22 @@ impl ToSql for NaiveDateTime {
     }
 }
 
-/// "YYYY-MM-DD HH:MM:SS"/"YYYY-MM-DD HH:MM:SS.SSS" => ISO 8601 combined date
-/// and time without timezone. ("YYYY-MM-DDTHH:MM:SS"/"YYYY-MM-DDTHH:MM:SS.SSS"
-/// also supported)
+/// "YYYY-MM-DD HH:MM"/"YYYY-MM-DD HH:MM:SS"/"YYYY-MM-DD HH:MM:SS.SSS" => ISO
+/// 8601 combined date and time without timezone. ("YYYY-MM-DDTHH:MM:SS.SSS"
+/// and a trailing "Z" are also supported, as are `REAL` Julian day numbers.)
 impl FromSql for NaiveDateTime {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Real(jd) = value {
+            return julian_day(jd).map(|dt| dt.naive_utc());
+        }
         value.as_str().and_then(|s| {
-            let fmt = if s.len() >= 11 && s.as_bytes()[10] == b'T' {
-                "%FT%T%.f"
-            } else {
-                "%F %T%.f"
+            let s = s.strip_suffix('Z').unwrap_or(s);
+            let t = s.len() >= 11 && s.as_bytes()[10] == b'T';
+            let fmt = match (t, s.len()) {
+                (true, 16) => "%FT%H:%M",
+                (true, _) => "%FT%T%.f",
+                (false, 16) => "%F %H:%M",
+                (false, _) => "%F %T%.f",
             };
 
             match NaiveDateTime::parse_from_str(s, fmt) {
@@ -83,6 +92,12 @@ impl FromSql for NaiveDateTime {
     }
 }
 
+/// Julian day number => UTC date and time.
+fn julian_day(jd: f64) -> FromSqlResult<DateTime<Utc>> {
+    let millis = julian_day_to_unix_millis(jd)?;
+    DateTime::from_timestamp_millis(millis).ok_or(FromSqlError::OutOfRange(millis))
+}
+
 /// UTC time => UTC RFC3339 timestamp
 /// ("YYYY-MM-DD HH:MM:SS.SSS+00:00").
 impl ToSql for DateTime<Utc> {
@@ -114,8 +129,13 @@ impl ToSql for DateTime<FixedOffset> {
 }
 
 /// RFC3339 ("YYYY-MM-DD HH:MM:SS.SSS[+-]HH:MM") into `DateTime<Utc>`.
+/// Values without an offset are assumed to be UTC, and `REAL` values are read
+/// as Julian day numbers.
 impl FromSql for DateTime<Utc> {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Real(jd) = value {
+            return julian_day(jd);
+        }
         {
             // Try to parse value as rfc3339 first.
             let s = value.as_str()?;
@@ -146,20 +166,66 @@ impl FromSql for DateTime<Local> {
 }
 
 /// RFC3339 ("YYYY-MM-DD HH:MM:SS.SSS[+-]HH:MM") into `DateTime<FixedOffset>`.
+/// Values without an offset, and `REAL` Julian day numbers, get a zero offset.
 impl FromSql for DateTime<FixedOffset> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Real(jd) = value {
+            return julian_day(jd).map(|dt| dt.fixed_offset());
+        }
+        let s = value.as_str()?;
+        Self::parse_from_rfc3339(s)
+            .or_else(|_| Self::parse_from_str(s, "%F %T%.f%:z"))
+            .or_else(|e| {
+                NaiveDateTime::column_result(value)
+                    .map(|dt| dt.and_utc().fixed_offset())
+                    .map_err(|_| FromSqlError::Other(Box::new(e)))
+            })
+    }
+}
+
+/// `DateTime<Utc>` => seconds since the Unix epoch, as `INTEGER`.
+impl ToSql for UnixTimestamp<DateTime<Utc>> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.timestamp()))
+    }
+}
+
+/// `INTEGER` seconds since the Unix epoch => `DateTime<Utc>`.
+impl FromSql for UnixTimestamp<DateTime<Utc>> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let secs = i64::column_result(value)?;
+        DateTime::from_timestamp(secs, 0)
+            .map(UnixTimestamp)
+            .ok_or(FromSqlError::OutOfRange(secs))
+    }
+}
+
+/// `DateTime<Utc>` => Julian day number, as `REAL`.
+impl ToSql for JulianDay<DateTime<Utc>> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(unix_millis_to_julian_day(
+            self.0.timestamp_millis(),
+        )))
+    }
+}
+
+/// Julian day number => `DateTime<Utc>`.
+impl FromSql for JulianDay<DateTime<Utc>> {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
-        let s = String::column_result(value)?;
-        Self::parse_from_rfc3339(s.as_str())
-            .or_else(|_| Self::parse_from_str(s.as_str(), "%F %T%.f%:z"))
-            .map_err(|e| FromSqlError::Other(Box::new(e)))
+        f64::column_result(value)
+            .and_then(julian_day)
+            .map(JulianDay)
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        types::{FromSql, ValueRef},
+        types::{FromSql, JulianDay, UnixTimestamp, ValueRef},
         Connection, Result,
     };
     use chrono::{
@@ -316,4 +382,92 @@ mod test {
         DateTime::<Utc>::column_result(ValueRef::Text(b"1970-01-01T00:00:00Z")).unwrap();
         DateTime::<Utc>::column_result(ValueRef::Text(b"1970-01-01T00:00:00+00")).unwrap();
     }
+
+    #[test]
+    fn test_sqlite_time_formats() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let dt = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        let dt_milli = dt + Duration::try_milliseconds(123).unwrap();
+        let utc = Utc.from_utc_datetime(&dt);
+        let utc_milli = Utc.from_utc_datetime(&dt_milli);
+
+        for (s, expected) in [
+            ("2024-01-02 03:04", dt - Duration::try_seconds(5).unwrap()),
+            ("2024-01-02 03:04:05", dt),
+            ("2024-01-02T03:04:05", dt),
+            ("2024-01-02T03:04:05.123Z", dt_milli),
+            ("2024-01-02 03:04:05.123", dt_milli),
+        ] {
+            let v: NaiveDateTime = db.query_row("SELECT ?1", [s], |r| r.get(0))?;
+            assert_eq!(expected, v, "{s}");
+        }
+
+        let v: DateTime<Utc> = db.one_column("SELECT '2024-01-02 03:04:05'")?;
+        assert_eq!(utc, v);
+        let v: DateTime<Utc> = db.one_column("SELECT '2024-01-02T03:04:05.123Z'")?;
+        assert_eq!(utc_milli, v);
+        let v: DateTime<FixedOffset> = db.one_column("SELECT '2024-01-02T03:04:05.123Z'")?;
+        assert_eq!(utc_milli, v);
+        let v: DateTime<FixedOffset> = db.one_column("SELECT '2024-01-02 03:04:05'")?;
+        assert_eq!(utc, v);
+
+        // Julian day numbers, as returned by `julianday()`
+        let v: DateTime<Utc> = db.one_column("SELECT julianday('2024-01-02 03:04:05.123')")?;
+        assert_eq!(utc_milli, v);
+        let v: NaiveDateTime = db.one_column("SELECT julianday('2024-01-02 03:04:05')")?;
+        assert_eq!(dt, v);
+        let v: DateTime<FixedOffset> = db.one_column("SELECT 2440587.5")?;
+        assert_eq!(DateTime::UNIX_EPOCH, v);
+        db.one_column::<DateTime<Utc>>("SELECT -1.0").unwrap_err();
+        // integers are not Julian days
+        db.one_column::<DateTime<Utc>>("SELECT 2440587")
+            .unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_unix_timestamp() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let utc = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let ts = UnixTimestamp(utc + Duration::try_milliseconds(123).unwrap());
+        db.execute("INSERT INTO foo (i) VALUES (?1)", [ts])?;
+
+        let (secs, typ): (i64, String) = db.query_row("SELECT i, typeof(i) FROM foo", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })?;
+        assert_eq!((utc.timestamp(), "integer"), (secs, typ.as_str()));
+        // sub-second precision is dropped
+        let v: UnixTimestamp<DateTime<Utc>> = db.one_column("SELECT i FROM foo")?;
+        assert_eq!(UnixTimestamp(utc), v);
+
+        let s: String = db.one_column("SELECT datetime(i, 'unixepoch') FROM foo")?;
+        assert_eq!("2024-01-02 03:04:05", s);
+        let v: UnixTimestamp<DateTime<Utc>> =
+            db.one_column("SELECT unixepoch('2024-01-02 03:04:05')")?;
+        assert_eq!(UnixTimestamp(utc), v);
+        Ok(())
+    }
+
+    #[test]
+    fn test_julian_day() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let utc = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap()
+            + Duration::try_milliseconds(123).unwrap();
+        db.execute("INSERT INTO foo (f) VALUES (?1)", [JulianDay(utc)])?;
+
+        let typ: String = db.one_column("SELECT typeof(f) FROM foo")?;
+        assert_eq!("real", typ);
+        let v: JulianDay<DateTime<Utc>> = db.one_column("SELECT f FROM foo")?;
+        assert_eq!(JulianDay(utc), v);
+
+        let s: String = db.one_column("SELECT strftime('%Y-%m-%d %H:%M:%f', f) FROM foo")?;
+        assert_eq!("2024-01-02 03:04:05.123", s);
+        let v: JulianDay<DateTime<Utc>> =
+            db.one_column("SELECT julianday('2024-01-02 03:04:05.123')")?;
+        assert_eq!(JulianDay(utc), v);
+        Ok(())
+    }
 }
@@ -0,0 +1,165 @@
+use std::fmt;
+
+use super::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::Result;
+
+/// A decimal number with `SCALE` digits after the decimal point, stored as
+/// an `INTEGER`: the value multiplied by 10<sup>`SCALE`</sup>.
+///
+/// This avoids the rounding errors of binary floating-point numbers for
+/// values such as prices or measurements, without a decimal library: a
+/// price of `19.99` stored as `FixedPoint::<2>` is the integer `1999`, which
+/// SQLite sums and compares exactly. `SCALE` must be at most 18, so that
+/// the multiplier fits in an `i64`.
+///
+/// `REAL` values are also accepted when reading (e.g. from a column filled
+/// by another application), and are rounded to the nearest multiple of
+/// 10<sup>-`SCALE`</sup>.
+///
+/// ```rust
+/// # use rusqlite::types::FixedPoint;
+/// let price = FixedPoint::<2>::from_units(1999);
+/// assert_eq!("19.99", price.to_string());
+/// assert_eq!(Some(price), FixedPoint::from_f64(19.99));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FixedPoint<const SCALE: u32>(i64);
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+    /// 10<sup>`SCALE`</sup>
+    const FACTOR: i64 = 10i64.pow(SCALE);
+
+    /// The number `units` × 10<sup>-`SCALE`</sup>, e.g. a number of cents
+    /// for `SCALE = 2`.
+    #[inline]
+    #[must_use]
+    pub const fn from_units(units: i64) -> FixedPoint<SCALE> {
+        FixedPoint(units)
+    }
+
+    /// The value multiplied by 10<sup>`SCALE`</sup>, as stored.
+    #[inline]
+    #[must_use]
+    pub const fn units(self) -> i64 {
+        self.0
+    }
+
+    /// The integer `i`, or `None` if `i` × 10<sup>`SCALE`</sup> does not fit
+    /// in an `i64`.
+    #[inline]
+    #[must_use]
+    pub fn from_int(i: i64) -> Option<FixedPoint<SCALE>> {
+        i.checked_mul(Self::FACTOR).map(FixedPoint)
+    }
+
+    /// `f` rounded to `SCALE` decimal places, or `None` if `f` is not finite
+    /// or out of range.
+    #[must_use]
+    pub fn from_f64(f: f64) -> Option<FixedPoint<SCALE>> {
+        let units = (f * Self::FACTOR as f64).round();
+        // i64::MAX is not exactly representable, 2^63 is
+        if units.is_finite() && units >= i64::MIN as f64 && units < i64::MAX as f64 {
+            Some(FixedPoint(units as i64))
+        } else {
+            None
+        }
+    }
+
+    /// The value as an `f64`, possibly rounded.
+    #[inline]
+    #[must_use]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::FACTOR as f64
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for FixedPoint<SCALE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let factor = Self::FACTOR.unsigned_abs();
+        if SCALE == 0 {
+            write!(f, "{sign}{abs}")
+        } else {
+            let width = SCALE as usize;
+            write!(f, "{sign}{}.{:0width$}", abs / factor, abs % factor)
+        }
+    }
+}
+
+impl<const SCALE: u32> ToSql for FixedPoint<SCALE> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0))
+    }
+}
+
+impl<const SCALE: u32> FromSql for FixedPoint<SCALE> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Integer(i) => Ok(FixedPoint(i)),
+            ValueRef::Real(f) => FixedPoint::from_f64(f)
+                .ok_or_else(|| FromSqlError::Other(format!("{f} is out of range").into())),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FixedPoint;
+    use crate::{Connection, Error, Result};
+
+    type Price = FixedPoint<2>;
+
+    #[test]
+    fn test_fixed_point_prices() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE item(price INTEGER)")?;
+        let prices = ["0.10", "0.20", "19.99", "-0.05", "1000000.01"];
+        for p in prices {
+            let price = Price::from_f64(p.parse().unwrap()).unwrap();
+            assert_eq!(p, price.to_string());
+            db.execute("INSERT INTO item VALUES (?1)", [price])?;
+        }
+        let mut stmt = db.prepare("SELECT price FROM item ORDER BY rowid")?;
+        let read: Vec<Price> = stmt.query_map([], |r| r.get(0))?.collect::<Result<_>>()?;
+        assert_eq!(
+            prices.to_vec(),
+            read.iter().map(Price::to_string).collect::<Vec<_>>()
+        );
+        assert_eq!(Price::from_units(1999), read[2]);
+
+        // sums are exact, unlike 0.1 + 0.2 as f64
+        let total: Price = db.one_column("SELECT sum(price) FROM item WHERE rowid <= 2")?;
+        assert_eq!(Price::from_units(30), total);
+        let typ: String = db.one_column("SELECT typeof(price) FROM item LIMIT 1")?;
+        assert_eq!("integer", typ);
+
+        // REAL values are rounded
+        let p: Price = db.one_column("SELECT 1.234")?;
+        assert_eq!(Price::from_units(123), p);
+        let p: FixedPoint<0> = db.one_column("SELECT 7")?;
+        assert_eq!("7", p.to_string());
+        db.one_column::<Price>("SELECT 'x'").unwrap_err();
+        assert!(matches!(
+            db.one_column::<Price>("SELECT 1e300"),
+            Err(Error::FromSqlConversionFailure(..))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_point_overflow() {
+        assert_eq!(Some(Price::from_units(500)), Price::from_int(5));
+        assert_eq!(None, Price::from_int(i64::MAX / 10));
+        assert_eq!(None, Price::from_f64(f64::NAN));
+        assert_eq!(None, Price::from_f64(f64::INFINITY));
+        assert_eq!(None, Price::from_f64(1e17));
+        assert_eq!(
+            "-92233720368547758.08",
+            Price::from_units(i64::MIN).to_string()
+        );
+        assert_eq!(-0.05, Price::from_units(-5).to_f64());
+    }
+}
@@ -83,6 +83,17 @@ pub type FromSqlResult<T> = Result<T, FromSqlError>;
 pub trait FromSql: Sized {
     /// Converts SQLite value into Rust value.
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self>;
+
+    /// Converts SQLite value into Rust value, for a connection reading
+    /// booleans leniently (see [`Connection::set_lenient_bool`](crate::Connection::set_lenient_bool)).
+    ///
+    /// Only `bool` (and `Option<bool>`) read differently, the default being
+    /// [`FromSql::column_result`].
+    #[doc(hidden)]
+    #[inline]
+    fn column_result_lenient_bool(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Self::column_result(value)
+    }
 }
 
 macro_rules! from_sql_integral(
@@ -167,6 +178,23 @@ impl FromSql for bool {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         i64::column_result(value).map(|i| i != 0)
     }
+
+    fn column_result_lenient_bool(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Text(t) => {
+                const TRUE: [&[u8]; 4] = [b"true", b"t", b"yes", b"1"];
+                const FALSE: [&[u8]; 4] = [b"false", b"f", b"no", b"0"];
+                if TRUE.iter().any(|s| s.eq_ignore_ascii_case(t)) {
+                    Ok(true)
+                } else if FALSE.iter().any(|s| s.eq_ignore_ascii_case(t)) {
+                    Ok(false)
+                } else {
+                    Err(FromSqlError::InvalidType)
+                }
+            }
+            _ => Self::column_result(value),
+        }
+    }
 }
 
 impl FromSql for String {
@@ -243,6 +271,14 @@ impl<T: FromSql> FromSql for Option<T> {
             _ => FromSql::column_result(value).map(Some),
         }
     }
+
+    #[inline]
+    fn column_result_lenient_bool(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Null => Ok(None),
+            _ => T::column_result_lenient_bool(value).map(Some),
+        }
+    }
 }
 
 impl FromSql for Value {
@@ -29,6 +29,10 @@
 //! stored as an `INTEGER`, not a `REAL` (unless the column is part of a
 //! [STRICT table](https://www.sqlite.org/stricttables.html)).
 //!
+//! Floating-point infinities are stored and read back unchanged, but SQLite
+//! has no `NaN`: a `NaN` parameter is bound as `NULL`, or rejected, depending
+//! on [`Connection::set_nan_policy`](crate::Connection::set_nan_policy).
+//!
 //! If the `time` feature is enabled, implementations are
 //! provided for `time::OffsetDateTime` that use the RFC 3339 date/time format,
 //! `"%Y-%m-%dT%H:%M:%S.%fZ"`, to store time values as strings.  These values
@@ -70,16 +74,25 @@ impl ToSql for DateTimeSql {
 //! implements [`ToSql`] or [`FromSql`] for the cases where you want to know if
 //! a value was NULL (which gets translated to `None`).
 
+pub use self::fixed_point::FixedPoint;
 pub use self::from_sql::{FromSql, FromSqlError, FromSqlResult};
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use self::timestamp::{JulianDay, UnixTimestamp};
 pub use self::to_sql::{ToSql, ToSqlOutput};
+#[cfg(feature = "uuid")]
+pub use self::uuid::UuidText;
 pub use self::value::Value;
 pub use self::value_ref::ValueRef;
 
 use std::fmt;
 
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+mod bytes;
 #[cfg(feature = "chrono")]
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 mod chrono;
+mod fixed_point;
 mod from_sql;
 #[cfg(feature = "serde_json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
@@ -87,10 +100,16 @@ mod serde_json;
 #[cfg(feature = "time")]
 #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
 mod time;
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "chrono", feature = "time"))))]
+mod timestamp;
 mod to_sql;
 #[cfg(feature = "url")]
 #[cfg_attr(docsrs, doc(cfg(feature = "url")))]
 mod url;
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+mod uuid;
 mod value;
 mod value_ref;
 
@@ -137,9 +156,34 @@ impl fmt::Display for Type {
     }
 }
 
+/// Julian day number of the Unix epoch (1970-01-01 00:00:00 UTC).
+#[cfg(any(feature = "chrono", feature = "time"))]
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+
+/// Convert a Julian day number, as returned by SQLite's `julianday()`, to
+/// milliseconds since the Unix epoch.
+///
+/// Like SQLite, only Julian days from 0 (4714-11-24 BC) up to 9999-12-31 are
+/// accepted and the result is rounded to the millisecond.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn julian_day_to_unix_millis(jd: f64) -> FromSqlResult<i64> {
+    if !(0.0..5_373_484.5).contains(&jd) {
+        return Err(FromSqlError::Other(
+            format!("Julian day {jd} is out of range").into(),
+        ));
+    }
+    Ok(((jd - UNIX_EPOCH_JULIAN_DAY) * 86_400_000.0).round() as i64)
+}
+
+/// Convert milliseconds since the Unix epoch to a Julian day number.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn unix_millis_to_julian_day(millis: i64) -> f64 {
+    millis as f64 / 86_400_000.0 + UNIX_EPOCH_JULIAN_DAY
+}
+
 #[cfg(test)]
 mod test {
-    use super::Value;
+    use super::{Type, Value};
     use crate::{params, Connection, Error, Result, Statement};
     use std::os::raw::{c_double, c_int};
 
@@ -161,6 +205,48 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_u8_array() -> Result<()> {
+        let db = checked_memory_handle()?;
+
+        let uuid = [7u8; 16];
+        let hash: [u8; 32] = std::array::from_fn(|i| i as u8);
+        db.execute("INSERT INTO foo(b) VALUES (?1)", [uuid])?;
+        db.execute("INSERT INTO foo(b) VALUES (?1)", [hash])?;
+
+        let mut stmt = db.prepare("SELECT b FROM foo ORDER BY rowid")?;
+        let (v16, v32): ([u8; 16], [u8; 32]) = {
+            let mut rows = stmt.query([])?;
+            let v16 = rows.next()?.unwrap().get(0)?;
+            let v32 = rows.next()?.unwrap().get(0)?;
+            (v16, v32)
+        };
+        assert_eq!(v16, uuid);
+        assert_eq!(v32, hash);
+
+        // the stored blob is too short
+        let err = db
+            .query_row("SELECT b FROM foo ORDER BY rowid", [], |r| {
+                r.get::<_, [u8; 32]>(0)
+            })
+            .unwrap_err();
+        match err {
+            Error::FromSqlConversionFailure(0, Type::Blob, ref e) => {
+                assert_eq!(
+                    e.to_string(),
+                    "Cannot read 32 byte value out of 16 byte blob"
+                );
+            }
+            ref e => panic!("unexpected error {e:?}"),
+        }
+        // or too long
+        db.query_row("SELECT b FROM foo WHERE length(b) = 32", [], |r| {
+            r.get::<_, [u8; 16]>(0)
+        })
+        .unwrap_err();
+        Ok(())
+    }
+
     #[test]
     fn test_empty_blob() -> Result<()> {
         let db = checked_memory_handle()?;
@@ -334,6 +420,44 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_value_round_trip() -> Result<()> {
+        let src = Connection::open_in_memory()?;
+        src.execute_batch(
+            "CREATE TABLE t(n);
+             INSERT INTO t VALUES (NULL), (42), (2.0), ('42'), (x'0102'), ('');",
+        )?;
+        let dst = Connection::open_in_memory()?;
+        dst.execute_batch("CREATE TABLE t(n)")?;
+
+        let mut stmt = src.prepare("SELECT n FROM t ORDER BY rowid")?;
+        let values = stmt
+            .query_map([], |r| r.get::<_, Value>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            vec![
+                Value::Null,
+                Value::Integer(42),
+                Value::Real(2.0),
+                Value::Text("42".to_owned()),
+                Value::Blob(vec![1, 2]),
+                Value::Text(String::new()),
+            ],
+            values
+        );
+        for v in &values {
+            dst.execute("INSERT INTO t VALUES (?1)", [v])?;
+        }
+
+        let types = |db: &Connection| -> Result<Vec<(String, Value)>> {
+            let mut stmt = db.prepare("SELECT typeof(n), n FROM t ORDER BY rowid")?;
+            let rows = stmt.query_map([], |r| Ok((r.get(0)?, r.get(1)?)))?;
+            rows.collect()
+        };
+        assert_eq!(types(&src)?, types(&dst)?);
+        Ok(())
+    }
+
     #[test]
     fn test_dynamic_type() -> Result<()> {
         use super::Value;
@@ -11,7 +11,10 @@
 //! Time String that contain an optional timezone without an explicit date are unsupported.
 //! All other assumptions described in [Time Values](https://sqlite.org/lang_datefunc.html#time_values) section are unsupported.
 
-use crate::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use super::{julian_day_to_unix_millis, unix_millis_to_julian_day};
+use crate::types::{
+    FromSql, FromSqlError, FromSqlResult, JulianDay, ToSql, ToSqlOutput, UnixTimestamp, ValueRef,
+};
 use crate::{Error, Result};
 use time::format_description::FormatItem;
 use time::macros::format_description;
@@ -35,7 +38,7 @@ const TIME_FORMAT: &[FormatItem<'_>] = format_description!(
 );
 const PRIMITIVE_DATE_TIME_FORMAT: &[FormatItem<'_>] = format_description!(
     version = 2,
-    "[year]-[month]-[day][first [ ][T]][hour]:[minute][optional [:[second][optional [.[subsecond]]]]]"
+    "[year]-[month]-[day][first [ ][T]][hour]:[minute][optional [:[second][optional [.[subsecond]]]]][optional [Z]]"
 );
 const UTC_DATE_TIME_FORMAT: &[FormatItem<'_>] = format_description!(
     version = 2,
@@ -63,8 +66,12 @@ impl ToSql for OffsetDateTime {
 
 // Supports parsing formats 2-7 from https://www.sqlite.org/lang_datefunc.html
 // Formats 2-7 without a timezone assumes UTC
+// `REAL` values are read as Julian day numbers (format 12)
 impl FromSql for OffsetDateTime {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Real(jd) = value {
+            return julian_day(jd);
+        }
         value.as_str().and_then(|s| {
             if let Some(b' ') = s.as_bytes().get(23) {
                 // legacy
@@ -143,10 +150,14 @@ impl ToSql for PrimitiveDateTime {
 /// YYYY-MM-DDTHH:MM:SS
 /// YYYY-MM-DD HH:MM:SS.SSS
 /// YYYY-MM-DDTHH:MM:SS.SSS
+/// (optionally followed by "Z") or a `REAL` Julian day number
 /// => ISO 8601 combined date and time with timezone
 impl FromSql for PrimitiveDateTime {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        if let ValueRef::Real(jd) = value {
+            return julian_day(jd).map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()));
+        }
         value.as_str().and_then(|s| {
             PrimitiveDateTime::parse(s, &PRIMITIVE_DATE_TIME_FORMAT)
                 .map_err(|err| FromSqlError::Other(err.into()))
@@ -154,8 +165,55 @@ impl FromSql for PrimitiveDateTime {
     }
 }
 
+/// Julian day number => UTC date and time.
+fn julian_day(jd: f64) -> FromSqlResult<OffsetDateTime> {
+    let millis = julian_day_to_unix_millis(jd)?;
+    OffsetDateTime::from_unix_timestamp_nanos(i128::from(millis) * 1_000_000)
+        .map_err(|err| FromSqlError::Other(Box::new(err)))
+}
+
+/// `OffsetDateTime` => seconds since the Unix epoch, as `INTEGER`.
+impl ToSql for UnixTimestamp<OffsetDateTime> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.unix_timestamp()))
+    }
+}
+
+/// `INTEGER` seconds since the Unix epoch => UTC `OffsetDateTime`.
+impl FromSql for UnixTimestamp<OffsetDateTime> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        i64::column_result(value).and_then(|secs| {
+            OffsetDateTime::from_unix_timestamp(secs)
+                .map(UnixTimestamp)
+                .map_err(|err| FromSqlError::Other(Box::new(err)))
+        })
+    }
+}
+
+/// `OffsetDateTime` => Julian day number, as `REAL`.
+impl ToSql for JulianDay<OffsetDateTime> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        let millis = (self.0.unix_timestamp_nanos() / 1_000_000) as i64;
+        Ok(ToSqlOutput::from(unix_millis_to_julian_day(millis)))
+    }
+}
+
+/// Julian day number => UTC `OffsetDateTime`.
+impl FromSql for JulianDay<OffsetDateTime> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        f64::column_result(value)
+            .and_then(julian_day)
+            .map(JulianDay)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::types::{JulianDay, UnixTimestamp};
     use crate::{Connection, Result};
     use time::macros::{date, datetime, time};
     use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
@@ -349,6 +407,10 @@ mod test {
             ("2013-10-07 08:23", datetime!(2013-10-07 8:23)),
             ("2013-10-07 08:23:19", datetime!(2013-10-07 8:23:19)),
             ("2013-10-07 08:23:19.111", datetime!(2013-10-07 8:23:19.111)),
+            (
+                "2013-10-07T08:23:19.111Z",
+                datetime!(2013-10-07 8:23:19.111),
+            ),
         ];
 
         for (s, t) in tests {
@@ -423,4 +485,55 @@ mod test {
         result.unwrap();
         Ok(())
     }
+
+    #[test]
+    fn test_julian_day() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let v: OffsetDateTime = db.one_column("SELECT '2024-01-02 03:04:05'")?;
+        assert_eq!(datetime!(2024-01-02 3:04:05 UTC), v);
+        let v: OffsetDateTime = db.one_column("SELECT '2024-01-02T03:04:05.123Z'")?;
+        assert_eq!(datetime!(2024-01-02 3:04:05.123 UTC), v);
+
+        let v: OffsetDateTime = db.one_column("SELECT julianday('2024-01-02T03:04:05.123Z')")?;
+        assert_eq!(datetime!(2024-01-02 3:04:05.123 UTC), v);
+        let v: PrimitiveDateTime = db.one_column("SELECT julianday('2024-01-02 03:04:05')")?;
+        assert_eq!(datetime!(2024-01-02 3:04:05), v);
+        let v: OffsetDateTime = db.one_column("SELECT 2440587.5")?;
+        assert_eq!(OffsetDateTime::UNIX_EPOCH, v);
+        db.one_column::<OffsetDateTime>("SELECT -1.0").unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_unix_timestamp() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let ts = UnixTimestamp(datetime!(2024-01-02 3:04:05.123 +1));
+        db.execute("INSERT INTO foo (i) VALUES (?1)", [ts])?;
+
+        let typ: String = db.one_column("SELECT typeof(i) FROM foo")?;
+        assert_eq!("integer", typ);
+        // sub-second precision is dropped
+        let v: UnixTimestamp<OffsetDateTime> = db.one_column("SELECT i FROM foo")?;
+        assert_eq!(UnixTimestamp(datetime!(2024-01-02 2:04:05 UTC)), v);
+
+        let s: String = db.one_column("SELECT datetime(i, 'unixepoch') FROM foo")?;
+        assert_eq!("2024-01-02 02:04:05", s);
+        Ok(())
+    }
+
+    #[test]
+    fn test_julian_day_storage() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let dt = datetime!(2024-01-02 3:04:05.123 UTC);
+        db.execute("INSERT INTO foo (f) VALUES (?1)", [JulianDay(dt)])?;
+
+        let typ: String = db.one_column("SELECT typeof(f) FROM foo")?;
+        assert_eq!("real", typ);
+        let v: JulianDay<OffsetDateTime> = db.one_column("SELECT f FROM foo")?;
+        assert_eq!(JulianDay(dt), v);
+
+        let s: String = db.one_column("SELECT strftime('%Y-%m-%d %H:%M:%f', f) FROM foo")?;
+        assert_eq!("2024-01-02 03:04:05.123", s);
+        Ok(())
+    }
 }
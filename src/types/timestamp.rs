@@ -0,0 +1,25 @@
+//! Wrappers to store date and time values as numbers rather than as text.
+
+/// A date and time stored as an `INTEGER` number of seconds since the Unix
+/// epoch (1970-01-01 00:00:00 UTC), like SQLite's `unixepoch()` returns.
+///
+/// Sub-second precision is dropped when storing. Such values can be read back
+/// by SQLite's date functions with the `'unixepoch'` modifier, e.g.
+/// `datetime(t, 'unixepoch')`.
+///
+/// Implemented for `chrono::DateTime<Utc>` (with the `chrono` feature) and
+/// `time::OffsetDateTime` (with the `time` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnixTimestamp<T>(pub T);
+
+/// A date and time stored as a `REAL` Julian day number, like SQLite's
+/// `julianday()` returns.
+///
+/// Values are rounded to the millisecond, which is the precision SQLite uses
+/// for Julian days. Such values can be read back by SQLite's date functions
+/// without any modifier, e.g. `datetime(t)`.
+///
+/// Implemented for `chrono::DateTime<Utc>` (with the `chrono` feature) and
+/// `time::OffsetDateTime` (with the `time` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JulianDay<T>(pub T);
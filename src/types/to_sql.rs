@@ -217,10 +217,7 @@ macro_rules! to_sql_self_fallible(
             #[inline]
             fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
                 Ok(ToSqlOutput::Owned(Value::Integer(
-                    i64::try_from(*self).map_err(
-                        // TODO: Include the values in the error message.
-                        |err| Error::ToSqlConversionFailure(err.into())
-                    )?
+                    i64::try_from(*self).map_err(|_| out_of_range(&*self))?
                 )))
             }
         }
@@ -230,17 +227,21 @@ macro_rules! to_sql_self_fallible(
             #[inline]
             fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
                 Ok(ToSqlOutput::Owned(Value::Integer(
-                    i64::try_from(self.get()).map_err(
-                        // TODO: Include the values in the error message.
-                        |err| Error::ToSqlConversionFailure(err.into())
-                    )?
+                    i64::try_from(self.get()).map_err(|_| out_of_range(&*self))?
                 )))
             }
         }
     )
 );
 
-// Special implementations for usize and u64 because these conversions can fail.
+fn out_of_range(value: &dyn std::fmt::Display) -> Error {
+    Error::ToSqlConversionFailure(
+        format!("{value} is out of range for an SQLite integer (greater than i64::MAX)").into(),
+    )
+}
+
+// Special implementations for usize and u64 because these conversions can fail
+// (instead of wrapping around to negative values).
 to_sql_self_fallible!(u64);
 to_sql_self_fallible!(usize);
 to_sql_self_fallible!(non_zero std::num::NonZeroU64);
@@ -328,6 +329,30 @@ mod test {
         is_to_sql::<usize>();
     }
 
+    #[test]
+    fn test_u64_out_of_range() {
+        use crate::types::{ToSqlOutput, Value};
+        use crate::Error;
+
+        let max = i64::MAX as u64;
+        assert_eq!(
+            ToSqlOutput::Owned(Value::Integer(i64::MAX)),
+            max.to_sql().unwrap()
+        );
+        for value in [max + 1, u64::MAX] {
+            match value.to_sql() {
+                Err(Error::ToSqlConversionFailure(err)) => {
+                    assert!(err.to_string().starts_with(&value.to_string()), "{err}");
+                }
+                r => panic!("unexpected result {r:?}"),
+            }
+        }
+        let nz = std::num::NonZeroU64::new(u64::MAX).unwrap();
+        nz.to_sql().unwrap_err();
+        #[cfg(target_pointer_width = "64")]
+        usize::MAX.to_sql().unwrap_err();
+    }
+
     #[test]
     fn test_nonzero_types() {
         is_to_sql::<std::num::NonZeroI8>();
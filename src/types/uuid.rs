@@ -0,0 +1,109 @@
+//! [`ToSql`] and [`FromSql`] implementation for [`uuid::Uuid`] stored as text.
+use crate::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use crate::Result;
+use uuid::Uuid;
+
+/// A [`Uuid`] stored as hyphenated `TEXT` (e.g.
+/// `"67e55044-10b1-426f-9247-bb680e5fe0c8"`) rather than as a 16-byte `BLOB`
+/// (which is how `Uuid` itself is stored).
+///
+/// When read, both the hyphenated and the simple (no hyphens) forms are
+/// accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UuidText(pub Uuid);
+
+impl From<Uuid> for UuidText {
+    #[inline]
+    fn from(id: Uuid) -> UuidText {
+        UuidText(id)
+    }
+}
+
+impl From<UuidText> for Uuid {
+    #[inline]
+    fn from(id: UuidText) -> Uuid {
+        id.0
+    }
+}
+
+/// Serialize `UuidText` to hyphenated text.
+impl ToSql for UuidText {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.hyphenated().to_string()))
+    }
+}
+
+/// Deserialize hyphenated or simple text to `UuidText`.
+impl FromSql for UuidText {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let s = value.as_str()?;
+        Uuid::parse_str(s)
+            .map(UuidText)
+            .map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UuidText;
+    use crate::types::Type;
+    use crate::{Connection, Error, Result};
+    use uuid::Uuid;
+
+    fn checked_memory_handle() -> Result<Connection> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE foo (id)")?;
+        Ok(db)
+    }
+
+    #[test]
+    fn test_uuid_text() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let id = Uuid::new_v4();
+        db.execute("INSERT INTO foo (id) VALUES (?1)", [UuidText(id)])?;
+
+        let (text, found): (String, UuidText) =
+            db.query_row("SELECT id, id FROM foo", [], |r| Ok((r.get(0)?, r.get(1)?)))?;
+        assert_eq!(text, id.hyphenated().to_string());
+        assert_eq!(found, UuidText(id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_uuid_text_simple() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let id = Uuid::new_v4();
+        db.execute(
+            "INSERT INTO foo (id) VALUES (?1)",
+            [id.simple().to_string()],
+        )?;
+        let found: UuidText = db.one_column("SELECT id FROM foo")?;
+        assert_eq!(Uuid::from(found), id);
+
+        db.execute("UPDATE foo SET id = 'not a uuid'", [])?;
+        let err = db.one_column::<UuidText>("SELECT id FROM foo").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::FromSqlConversionFailure(0, Type::Text, _)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_uuid_blob_and_text() -> Result<()> {
+        let db = checked_memory_handle()?;
+        let id = Uuid::new_v4();
+        // stored as a BLOB
+        db.execute("INSERT INTO foo (id) VALUES (?1)", [id])?;
+        assert_eq!(db.one_column::<Uuid>("SELECT id FROM foo")?, id);
+
+        let err = db.one_column::<UuidText>("SELECT id FROM foo").unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidColumnType(0, ref name, Type::Blob) if name == "id"),
+            "{err:?}"
+        );
+        Ok(())
+    }
+}
@@ -5,6 +5,11 @@ use super::{Null, Type};
 ///
 /// See [`ValueRef`](crate::types::ValueRef) for a non-owning dynamic type
 /// value.
+///
+/// Reading a `Value` gives the value exactly as stored, and binding it back
+/// stores the same type (e.g. `Text("1")` stays `TEXT` and `Real(2.0)` stays
+/// `REAL`), so `Value`s can be used to copy rows between databases. The
+/// affinity of the destination column still applies, as for any value.
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     /// The value is a `NULL` value.
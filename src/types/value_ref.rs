@@ -32,6 +32,24 @@ impl ValueRef<'_> {
             ValueRef::Blob(_) => Type::Blob,
         }
     }
+
+    /// Copies the value into an owned [`Value`], e.g. to keep it after the
+    /// row, hook or function call it was borrowed from.
+    ///
+    /// Unlike the `From<ValueRef>` conversion, which panics, TEXT which is
+    /// not valid UTF-8 is copied with invalid sequences replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    #[inline]
+    #[must_use]
+    pub fn into_owned(self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Integer(i) => Value::Integer(i),
+            ValueRef::Real(r) => Value::Real(r),
+            ValueRef::Text(s) => Value::Text(String::from_utf8_lossy(s).into_owned()),
+            ValueRef::Blob(b) => Value::Blob(b.to_vec()),
+        }
+    }
 }
 
 impl<'a> ValueRef<'a> {
@@ -261,3 +279,49 @@ impl<'a> ValueRef<'a> {
     // TODO sqlite3_value_nochange // 3.22.0 & VTab xUpdate
     // TODO sqlite3_value_frombind // 3.28.0
 }
+
+#[cfg(test)]
+mod test {
+    use super::ValueRef;
+    use crate::types::Value;
+    use crate::{Connection, Result};
+
+    #[test]
+    fn test_into_owned() -> Result<()> {
+        assert_eq!(Value::Null, ValueRef::Null.into_owned());
+        assert_eq!(Value::Integer(-7), ValueRef::Integer(-7).into_owned());
+        assert_eq!(Value::Real(1.5), ValueRef::Real(1.5).into_owned());
+        assert_eq!(
+            Value::Text("héllo".to_owned()),
+            ValueRef::from("héllo").into_owned()
+        );
+        assert_eq!(
+            Value::Blob(vec![0, 1, 255]),
+            ValueRef::Blob(&[0, 1, 255]).into_owned()
+        );
+        assert_eq!(
+            Value::Text("a\u{fffd}b".to_owned()),
+            ValueRef::Text(b"a\xffb").into_owned()
+        );
+
+        // the copy outlives the row it was borrowed from
+        let db = Connection::open_in_memory()?;
+        let mut stmt = db.prepare("SELECT NULL, 1, 2.5, 'text', x'00ff'")?;
+        let values = stmt.query_row([], |row| {
+            (0..5)
+                .map(|i| Ok(row.get_ref(i)?.into_owned()))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        assert_eq!(
+            vec![
+                Value::Null,
+                Value::Integer(1),
+                Value::Real(2.5),
+                Value::Text("text".to_owned()),
+                Value::Blob(vec![0, 255]),
+            ],
+            values
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use crate::error::misuse;
+use crate::{quote_identifier, Connection, Params, Result, Savepoint};
+
+impl Connection {
+    /// Insert `rows` into `table`, or update the existing rows which conflict
+    /// with them on `conflict_keys`, and return the number of rows inserted
+    /// or updated.
+    ///
+    /// Each row gives the values of `columns`, in order. On conflict, the
+    /// columns which are not keys are set to their new value; rows made of
+    /// keys only are left as they are. `conflict_keys` must be columns of a
+    /// `PRIMARY KEY` or `UNIQUE` constraint (or unique index) of the table.
+    ///
+    /// The statement
+    /// `INSERT INTO table(columns) VALUES (...) ON CONFLICT(conflict_keys) DO UPDATE SET column = excluded.column, ...`
+    /// is prepared once and run for each row, within a savepoint: either all
+    /// the rows are upserted, or none is.
+    ///
+    /// See [UPSERT](https://sqlite.org/lang_upsert.html) (SQLite 3.24.0 or
+    /// later).
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// # use rusqlite::{Connection, Result};
+    /// fn save_prices(conn: &Connection, prices: &[(String, f64)]) -> Result<usize> {
+    ///     conn.upsert(
+    ///         "price",
+    ///         &["product", "amount"],
+    ///         &["product"],
+    ///         prices.iter().map(|(product, amount)| (product, amount)),
+    ///     )
+    /// }
+    /// ```
+    ///
+    /// # Failure
+    ///
+    /// Will return `Err` if `columns` or `conflict_keys` is empty, if a key is
+    /// not one of `columns`, if a row does not have as many values as
+    /// `columns`, or if the underlying SQLite call fails.
+    pub fn upsert<I, P>(
+        &self,
+        table: &str,
+        columns: &[&str],
+        conflict_keys: &[&str],
+        rows: I,
+    ) -> Result<usize>
+    where
+        I: IntoIterator<Item = P>,
+        P: Params,
+    {
+        let sql = upsert_sql(table, columns, conflict_keys)?;
+        let sp = Savepoint::new_(self)?;
+        let mut changes = 0;
+        {
+            let mut stmt = sp.prepare(&sql)?;
+            for row in rows {
+                changes += stmt.execute(row)?;
+            }
+        }
+        sp.commit()?;
+        Ok(changes)
+    }
+}
+
+fn upsert_sql(table: &str, columns: &[&str], conflict_keys: &[&str]) -> Result<String> {
+    if columns.is_empty() || conflict_keys.is_empty() {
+        return Err(misuse(
+            "upsert requires at least one column and one conflict key",
+        ));
+    }
+    if let Some(key) = conflict_keys.iter().find(|k| !columns.contains(k)) {
+        return Err(misuse(format!(
+            "conflict key {key} is not an upserted column"
+        )));
+    }
+    let names = columns
+        .iter()
+        .map(|c| quote_identifier(c))
+        .collect::<Vec<_>>();
+    let placeholders = (1..=columns.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>();
+    let keys = conflict_keys
+        .iter()
+        .map(|k| quote_identifier(k))
+        .collect::<Vec<_>>();
+    let updates = columns
+        .iter()
+        .filter(|c| !conflict_keys.contains(c))
+        .map(|c| {
+            let c = quote_identifier(c);
+            format!("{c} = excluded.{c}")
+        })
+        .collect::<Vec<_>>();
+    let action = if updates.is_empty() {
+        "NOTHING".to_owned()
+    } else {
+        format!("UPDATE SET {}", updates.join(", "))
+    };
+    Ok(format!(
+        "INSERT INTO {}({}) VALUES ({}) ON CONFLICT({}) DO {action}",
+        quote_identifier(table),
+        names.join(", "),
+        placeholders.join(", "),
+        keys.join(", "),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::upsert_sql;
+    use crate::{Connection, Result, ToSql};
+
+    #[test]
+    fn test_upsert_sql() -> Result<()> {
+        assert_eq!(
+            "INSERT INTO \"order\"(\"id\", \"group\", \"qty\") VALUES (?1, ?2, ?3) \
+             ON CONFLICT(\"id\") DO UPDATE SET \"group\" = excluded.\"group\", \
+             \"qty\" = excluded.\"qty\"",
+            upsert_sql("order", &["id", "group", "qty"], &["id"])?
+        );
+        assert_eq!(
+            "INSERT INTO \"tag\"(\"name\") VALUES (?1) ON CONFLICT(\"name\") DO NOTHING",
+            upsert_sql("tag", &["name"], &["name"])?
+        );
+        upsert_sql("tag", &[], &["name"]).unwrap_err();
+        upsert_sql("tag", &["name"], &[]).unwrap_err();
+        upsert_sql("tag", &["name"], &["id"]).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch(
+            "CREATE TABLE stock(shop TEXT, item TEXT, qty INTEGER, note TEXT DEFAULT 'x',
+                                PRIMARY KEY (shop, item));
+             INSERT INTO stock VALUES ('a', 'apple', 1, 'kept'), ('a', 'pear', 2, 'kept');",
+        )?;
+        let rows: Vec<(&str, &str, i64)> =
+            vec![("a", "apple", 10), ("b", "apple", 5), ("a", "kiwi", 3)];
+        let n = db.upsert(
+            "stock",
+            &["shop", "item", "qty"],
+            &["shop", "item"],
+            rows.iter().copied(),
+        )?;
+        assert_eq!(3, n);
+
+        let mut stmt = db.prepare("SELECT shop, item, qty, note FROM stock ORDER BY shop, item")?;
+        let content = stmt
+            .query_map([], |r| {
+                Ok((
+                    r.get::<_, String>(0)?,
+                    r.get::<_, String>(1)?,
+                    r.get::<_, i64>(2)?,
+                    r.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        let row = |s: &str, i: &str, q: i64, n: &str| (s.to_owned(), i.to_owned(), q, n.to_owned());
+        assert_eq!(
+            vec![
+                row("a", "apple", 10, "kept"),
+                row("a", "kiwi", 3, "x"),
+                row("a", "pear", 2, "kept"),
+                row("b", "apple", 5, "x"),
+            ],
+            content
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_is_atomic() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        db.execute_batch("CREATE TABLE t(k INTEGER PRIMARY KEY, v TEXT NOT NULL)")?;
+        let rows: [[&dyn ToSql; 2]; 2] = [[&1, &"one"], [&2, &None::<String>]];
+        db.upsert("t", &["k", "v"], &["k"], rows).unwrap_err();
+        assert_eq!(0, db.one_column::<i64>("SELECT count(*) FROM t")?);
+        Ok(())
+    }
+}
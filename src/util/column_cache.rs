@@ -0,0 +1,75 @@
+#[cfg(test)]
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Maps column names, as spelled by the caller, to column indices.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct ColumnIndexCache {
+    indices: RefCell<BTreeMap<String, usize>>,
+    // number of misses, for tests
+    #[cfg(test)]
+    scans: Cell<usize>,
+}
+
+impl ColumnIndexCache {
+    /// Look `name` up, calling `scan` on a miss.
+    ///
+    /// Column names change if the statement is recompiled after a schema
+    /// change, so a cached index is only returned if `is_valid` confirms it
+    /// still matches `name`.
+    pub fn get_or_insert_with<V, F>(&self, name: &str, is_valid: V, scan: F) -> Option<usize>
+    where
+        V: FnOnce(usize) -> bool,
+        F: FnOnce() -> Option<usize>,
+    {
+        let mut cache = self.indices.borrow_mut();
+        // Avoid entry API, needs allocation to test membership.
+        if let Some(&idx) = cache.get(name) {
+            if is_valid(idx) {
+                return Some(idx);
+            }
+        }
+        #[cfg(test)]
+        self.scans.set(self.scans.get() + 1);
+        match scan() {
+            Some(idx) => {
+                cache.insert(name.to_owned(), idx);
+                Some(idx)
+            }
+            None => {
+                cache.remove(name);
+                None
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn scans(&self) -> usize {
+        self.scans.get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn test_cache() {
+        let c = ColumnIndexCache::default();
+        let v = c.get_or_insert_with("foo", |_| panic!("nothing cached yet"), || Some(3));
+        assert_eq!(v, Some(3));
+        let v = c.get_or_insert_with(
+            "foo",
+            |idx| idx == 3,
+            || panic!("shouldn't be called this time"),
+        );
+        assert_eq!(v, Some(3));
+        // stale entry
+        let v = c.get_or_insert_with("foo", |_| false, || Some(1));
+        assert_eq!(v, Some(1));
+        let v = c.get_or_insert_with("foo", |_| false, || None);
+        assert_eq!(v, None);
+        let v = c.get_or_insert_with("foo", |_| panic!("entry was removed"), || None);
+        assert_eq!(v, None);
+    }
+}
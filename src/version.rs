@@ -1,5 +1,5 @@
 use crate::ffi;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 
 /// Returns the SQLite version as an integer; e.g., `3016002` for version
 /// 3.16.2.
@@ -25,3 +25,61 @@ pub fn version() -> &'static str {
     cstr.to_str()
         .expect("SQLite version string is not valid UTF8 ?!")
 }
+
+/// Returns the options SQLite was compiled with, without the `SQLITE_`
+/// prefix; e.g., `"ENABLE_FTS5"` or `"THREADSAFE=1"`.
+///
+/// See [`sqlite3_compileoption_get()`](https://www.sqlite.org/c3ref/compileoption_get.html).
+///
+/// # Panics
+///
+/// Panics when an option is not valid UTF-8.
+pub fn compile_options() -> impl Iterator<Item = &'static str> {
+    (0..)
+        .map(|n| unsafe { ffi::sqlite3_compileoption_get(n) })
+        .take_while(|p| !p.is_null())
+        .map(|p| {
+            let cstr = unsafe { CStr::from_ptr(p) };
+            cstr.to_str()
+                .expect("SQLite compile option is not valid UTF8 ?!")
+        })
+}
+
+/// Returns whether SQLite was compiled with the option `name`, with or
+/// without the `SQLITE_` prefix and without any `=value` suffix; e.g.,
+/// `has_compile_option("ENABLE_FTS5")`.
+///
+/// See [`sqlite3_compileoption_used()`](https://www.sqlite.org/c3ref/compileoption_get.html).
+#[must_use]
+pub fn has_compile_option(name: &str) -> bool {
+    match CString::new(name) {
+        Ok(name) => unsafe { ffi::sqlite3_compileoption_used(name.as_ptr()) != 0 },
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compile_options, has_compile_option, version, version_number};
+
+    #[test]
+    fn test_version() {
+        assert!(!version().is_empty());
+        assert!(version().starts_with('3'));
+        assert!(version_number() >= 3_000_000);
+    }
+
+    #[test]
+    fn test_compile_options() {
+        let threadsafe = compile_options()
+            .find(|o| o.starts_with("THREADSAFE="))
+            .unwrap();
+        assert_ne!(threadsafe, "THREADSAFE=0");
+        assert!(has_compile_option("THREADSAFE"));
+        assert!(has_compile_option("SQLITE_THREADSAFE"));
+        assert!(!has_compile_option("NOT_A_REAL_OPTION"));
+        assert!(!has_compile_option("THREAD\0SAFE"));
+        #[cfg(feature = "bundled")]
+        assert!(has_compile_option("ENABLE_FTS5"));
+    }
+}
@@ -103,10 +103,16 @@ const ZERO_MODULE: ffi::sqlite3_module = unsafe {
 
 macro_rules! module {
     ($lt:lifetime, $vt:ty, $ct:ty, $xc:expr, $xd:expr, $xu:expr) => {
+        module!($lt, $vt, $ct, $xc, $xd, $xu, None)
+    };
+    ($lt:lifetime, $vt:ty, $ct:ty, $xc:expr, $xd:expr, $xu:expr, $xs:expr) => {
     #[allow(clippy::needless_update)]
     &Module {
         base: ffi::sqlite3_module {
-            // We don't use V3
+            // V3 only adds xShadowName, V4 (xIntegrity) is not used
+            #[cfg(feature = "modern_sqlite")]
+            iVersion: 3,
+            #[cfg(not(feature = "modern_sqlite"))]
             iVersion: 2,
             xCreate: $xc,
             xConnect: Some(rust_connect::<$vt>),
@@ -130,6 +136,8 @@ macro_rules! module {
             xSavepoint: None,
             xRelease: None,
             xRollbackTo: None,
+            #[cfg(feature = "modern_sqlite")]
+            xShadowName: $xs,
             ..ZERO_MODULE
         },
         phantom: PhantomData::<&$lt $vt>,
@@ -147,10 +155,10 @@ pub fn update_module<'vtab, T: UpdateVTab<'vtab>>() -> &'static Module<'vtab, T>
             module!('vtab, T, T::Cursor, None, None, Some(rust_update::<T>))
         }
         VTabKind::Eponymous => {
-            module!('vtab, T, T::Cursor, Some(rust_connect::<T>), Some(rust_disconnect::<T>), Some(rust_update::<T>))
+            module!('vtab, T, T::Cursor, Some(rust_connect::<T>), Some(rust_disconnect::<T>), Some(rust_update::<T>), Some(rust_shadow_name::<T>))
         }
         _ => {
-            module!('vtab, T, T::Cursor, Some(rust_create::<T>), Some(rust_destroy::<T>), Some(rust_update::<T>))
+            module!('vtab, T, T::Cursor, Some(rust_create::<T>), Some(rust_destroy::<T>), Some(rust_update::<T>), Some(rust_shadow_name::<T>))
         }
     }
 }
@@ -165,12 +173,12 @@ pub fn read_only_module<'vtab, T: CreateVTab<'vtab>>() -> &'static Module<'vtab,
         VTabKind::Eponymous => {
             // A virtual table is eponymous if its xCreate method is the exact same function
             // as the xConnect method
-            module!('vtab, T, T::Cursor, Some(rust_connect::<T>), Some(rust_disconnect::<T>), None)
+            module!('vtab, T, T::Cursor, Some(rust_connect::<T>), Some(rust_disconnect::<T>), None, Some(rust_shadow_name::<T>))
         }
         _ => {
             // The xConnect and xCreate methods may do the same thing, but they must be
             // different so that the virtual table is not an eponymous virtual table.
-            module!('vtab, T, T::Cursor, Some(rust_create::<T>), Some(rust_destroy::<T>), None)
+            module!('vtab, T, T::Cursor, Some(rust_create::<T>), Some(rust_destroy::<T>), None, Some(rust_shadow_name::<T>))
         }
     }
 }
@@ -300,6 +308,25 @@ pub trait CreateVTab<'vtab>: VTab<'vtab> {
     fn destroy(&self) -> Result<()> {
         Ok(())
     }
+
+    /// Whether `suffix` names one of the shadow tables of this module, that
+    /// is, whether table `<vtab>_<suffix>` stores the content of virtual
+    /// table `<vtab>`.
+    ///
+    /// Shadow tables are read-only for ordinary SQL when the connection is
+    /// in [defensive](crate::config::DbConfig::SQLITE_DBCONFIG_DEFENSIVE)
+    /// mode, so that they cannot be corrupted; the virtual table itself can
+    /// still modify them.
+    ///
+    /// No shadow table by default.
+    /// SQLite >= 3.26.0, only used with the `modern_sqlite` feature.
+    /// (See [SQLite doc](https://sqlite.org/vtab.html#the_xshadowname_method))
+    #[inline]
+    #[must_use]
+    fn shadow_name(suffix: &str) -> bool {
+        let _ = suffix;
+        false
+    }
 }
 
 /// Writable virtual table instance trait.
@@ -1080,6 +1107,20 @@ where
     }
 }
 
+#[cfg(feature = "modern_sqlite")]
+unsafe extern "C" fn rust_shadow_name<'vtab, T>(suffix: *const c_char) -> c_int
+where
+    T: CreateVTab<'vtab>,
+{
+    if suffix.is_null() {
+        return 0;
+    }
+    match std::ffi::CStr::from_ptr(suffix).to_str() {
+        Ok(suffix) => c_int::from(T::shadow_name(suffix)),
+        Err(_) => 0,
+    }
+}
+
 unsafe extern "C" fn rust_open<'vtab, T>(
     vtab: *mut ffi::sqlite3_vtab,
     pp_cursor: *mut *mut ffi::sqlite3_vtab_cursor,
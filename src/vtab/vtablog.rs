@@ -145,6 +145,10 @@ impl<'vtab> CreateVTab<'vtab> for VTabLog {
         println!("VTabLog::destroy({})", self.i_inst);
         Ok(())
     }
+
+    fn shadow_name(suffix: &str) -> bool {
+        suffix == "shadow"
+    }
 }
 
 impl<'vtab> UpdateVTab<'vtab> for VTabLog {
@@ -267,7 +271,9 @@ unsafe impl VTabCursor for VTabLogCursor<'_> {
 
 #[cfg(test)]
 mod test {
+    use crate::config::DbConfig;
     use crate::{Connection, Result};
+
     #[test]
     fn test_module() -> Result<()> {
         let db = Connection::open_in_memory()?;
@@ -293,4 +299,28 @@ mod test {
         )?;
         Ok(())
     }
+
+    #[test]
+    fn test_shadow_table() -> Result<()> {
+        let db = Connection::open_in_memory()?;
+        super::load_module(&db)?;
+        db.execute_batch(
+            "CREATE VIRTUAL TABLE log USING vtablog(schema='CREATE TABLE x(a)', rows=1);
+             CREATE TABLE log_shadow(x);
+             CREATE TABLE log_other(x);
+             INSERT INTO log_shadow VALUES (1);",
+        )?;
+        db.set_db_config(DbConfig::SQLITE_DBCONFIG_DEFENSIVE, true)?;
+        db.execute("INSERT INTO log_shadow VALUES (2)", [])
+            .unwrap_err();
+        db.execute("DELETE FROM log_shadow", []).unwrap_err();
+        // can still be read
+        assert_eq!(1, db.one_column::<i64>("SELECT x FROM log_shadow")?);
+        // not a shadow table
+        db.execute("INSERT INTO log_other VALUES (1)", [])?;
+
+        db.set_db_config(DbConfig::SQLITE_DBCONFIG_DEFENSIVE, false)?;
+        db.execute("INSERT INTO log_shadow VALUES (2)", [])?;
+        Ok(())
+    }
 }
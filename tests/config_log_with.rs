@@ -0,0 +1,45 @@
+//! This file contains unit tests for `rusqlite::trace::config_log_with`. This
+//! function affects SQLite process-wide and so is not safe to run as a normal
+//! #[test] in the library.
+
+#[cfg(feature = "trace")]
+fn main() {
+    use std::os::raw::c_int;
+    use std::sync::{Arc, Mutex};
+
+    use rusqlite::{ffi, trace, Connection};
+
+    let logs_received: Arc<Mutex<Vec<(c_int, String)>>> = Arc::default();
+    let log_handler = |logs: Arc<Mutex<Vec<_>>>| {
+        move |err, message: &str| logs.lock().unwrap().push((err, message.to_owned()))
+    };
+
+    unsafe { trace::config_log_with(log_handler(logs_received.clone())) }.unwrap();
+    trace::log(10, "First message from rusqlite");
+    unsafe { trace::clear_log() }.unwrap();
+    trace::log(11, "Second message from rusqlite");
+
+    assert_eq!(
+        *logs_received.lock().unwrap(),
+        [(10, "First message from rusqlite".to_owned())]
+    );
+
+    // SQLite logs a warning each time it builds an automatic index
+    unsafe { trace::config_log_with(log_handler(logs_received.clone())) }.unwrap();
+    let db = Connection::open_in_memory().unwrap();
+    db.execute_batch(
+        "CREATE TABLE t1(a); CREATE TABLE t2(b);
+         INSERT INTO t1 VALUES (1), (2); INSERT INTO t2 VALUES (1), (2);",
+    )
+    .unwrap();
+    db.query_row("SELECT COUNT(*) FROM t1, t2 WHERE a = b", [], |_| Ok(()))
+        .unwrap();
+
+    let logs_received = logs_received.lock().unwrap();
+    assert!(logs_received.iter().any(|(err, msg)| {
+        *err == ffi::SQLITE_WARNING_AUTOINDEX && msg.contains("automatic index")
+    }));
+}
+
+#[cfg(not(feature = "trace"))]
+fn main() {}
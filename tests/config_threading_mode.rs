@@ -0,0 +1,16 @@
+//! This file contains unit tests for `rusqlite::config::config_threading_mode`.
+//! This function affects SQLite process-wide and so is not safe to run as a
+//! normal #[test] in the library.
+
+#[cfg(not(feature = "loadable_extension"))]
+#[test]
+fn test_config_threading_mode() {
+    use rusqlite::config::{config_threading_mode, ThreadingMode};
+    use rusqlite::Connection;
+
+    unsafe { config_threading_mode(ThreadingMode::MultiThread) }.unwrap();
+    let db = Connection::open_in_memory().unwrap();
+    db.execute_batch("CREATE TABLE foo(x)").unwrap();
+    // too late: SQLite is already initialized
+    unsafe { config_threading_mode(ThreadingMode::Serialized) }.unwrap_err();
+}